@@ -0,0 +1,98 @@
+use crate::types::FileTypeMapper;
+use dbsync_core::{
+    error::{Error, Result},
+    schema::{ColumnSchema, TableSchema},
+    types::{DbsyncType, TypeMapper},
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+// 显式配置的列, 代替表头或字段名和类型推断
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnConfig {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+    #[serde(default = "default_nullable")]
+    pub nullable: bool,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+impl ColumnConfig {
+    pub(crate) fn dbsync_type(&self) -> Result<DbsyncType> {
+        FileTypeMapper
+            .to_dbsync_type(&self.data_type)
+            .map_err(|e| Error::Config(format!("Column {}: {}", self.name, e)))
+    }
+
+    pub(crate) fn column_schema(&self) -> Result<ColumnSchema> {
+        Ok(ColumnSchema::new(&self.name, self.dbsync_type()?).with_nullable(self.nullable))
+    }
+}
+
+// 校验列类型, 配置了列时主键列必须在其中
+pub(crate) fn validate_columns(
+    columns: Option<&[ColumnConfig]>,
+    primary_key: &[String],
+) -> Result<()> {
+    let Some(columns) = columns else {
+        return Ok(());
+    };
+    for column in columns {
+        column.dbsync_type()?;
+    }
+    match primary_key
+        .iter()
+        .find(|key| !columns.iter().any(|c| &c.name == *key))
+    {
+        Some(key) => Err(Error::Config(format!(
+            "Primary key column {} is not in columns",
+            key
+        ))),
+        None => Ok(()),
+    }
+}
+
+// 文件对应的表结构, 表名为不含扩展名的文件名. 主键列不能为 NULL
+pub(crate) fn file_table_schema(
+    path: &str,
+    columns: Vec<ColumnSchema>,
+    primary_key: &[String],
+) -> Result<TableSchema> {
+    let table = Path::new(path)
+        .file_stem()
+        .map_or_else(|| path.to_string(), |s| s.to_string_lossy().into_owned());
+    let mut schema = TableSchema::new(table);
+    for mut column in columns {
+        if schema.column(&column.name).is_some() {
+            return Err(Error::Config(format!(
+                "Duplicate column name {} in {}",
+                column.name, path
+            )));
+        }
+        if primary_key.contains(&column.name) {
+            column.nullable = false;
+        }
+        schema = schema.with_column(column);
+    }
+    if let Some(key) = primary_key.iter().find(|key| schema.column(key).is_none()) {
+        return Err(Error::Config(format!(
+            "Primary key column {} is not in {}",
+            key, path
+        )));
+    }
+    Ok(schema.with_primary_key(primary_key.to_vec()))
+}
+
+// 日志中列出的列名和类型
+pub(crate) fn describe_columns(schema: &TableSchema) -> String {
+    schema
+        .columns
+        .iter()
+        .map(|column| format!("{} {:?}", column.name, column.data_type))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
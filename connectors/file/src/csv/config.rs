@@ -0,0 +1,247 @@
+use super::format::CsvFormat;
+use crate::column::{validate_columns, ColumnConfig};
+use dbsync_core::connector::SaveMode;
+use dbsync_core::error::{Error, Result};
+use encoding_rs::Encoding;
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    static ref SOURCE_SCHEMA: Validator = {
+        let schema = include_str!("../../schema/csv_source.json");
+        let schema = serde_json::from_str(schema).unwrap();
+        Validator::new(&schema).unwrap()
+    };
+    static ref SINK_SCHEMA: Validator = {
+        let schema = include_str!("../../schema/csv_sink.json");
+        let schema = serde_json::from_str(schema).unwrap();
+        Validator::new(&schema).unwrap()
+    };
+}
+
+// source 和 sink 共用的格式选项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvOptions {
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    #[serde(default = "default_quote")]
+    pub quote: char,
+    #[serde(default = "default_header")]
+    pub header: bool,
+    #[serde(default = "default_encoding")]
+    pub encoding: String,
+    #[serde(default)]
+    pub null_value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvSourceConfig {
+    pub path: String,
+    #[serde(flatten)]
+    pub options: CsvOptions,
+    #[serde(default)]
+    pub columns: Option<Vec<ColumnConfig>>,
+    #[serde(default = "default_infer_rows")]
+    pub infer_rows: usize,
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+// 哪些字段加引号, NULL 总是不加引号
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Quoting {
+    #[default]
+    Minimal, // 只有含分隔符, 引号, 换行或与 null_value 相同的字段
+    All,        // 所有非 NULL 字段
+    NonNumeric, // 数值以外的所有非 NULL 字段
+    None,       // 不加引号, 需要引号的字段报错
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CsvSinkConfig {
+    pub path: String,
+    #[serde(flatten)]
+    pub options: CsvOptions,
+    #[serde(default)]
+    pub quoting: Quoting,
+    #[serde(default)]
+    pub save_mode: SaveMode,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+fn default_quote() -> char {
+    '"'
+}
+fn default_header() -> bool {
+    true
+}
+fn default_encoding() -> String {
+    "utf-8".to_string()
+}
+fn default_infer_rows() -> usize {
+    1000
+}
+fn default_batch_size() -> usize {
+    1000
+}
+
+impl CsvOptions {
+    // 按字节解析记录, 分隔符, 引号和换行在 ASCII 兼容的编码中都是单字节
+    pub(crate) fn format(&self) -> Result<CsvFormat> {
+        let byte = |c: char, name: &str| -> Result<u8> {
+            match u8::try_from(c) {
+                Ok(b) if b.is_ascii() && b != b'\r' && b != b'\n' => Ok(b),
+                _ => Err(Error::Config(format!(
+                    "{} must be an ASCII character other than a line break",
+                    name
+                ))),
+            }
+        };
+        let format = CsvFormat {
+            delimiter: byte(self.delimiter, "delimiter")?,
+            quote: byte(self.quote, "quote")?,
+        };
+        if format.delimiter == format.quote {
+            return Err(Error::Config(
+                "delimiter and quote must be different characters".to_string(),
+            ));
+        }
+        Ok(format)
+    }
+
+    pub(crate) fn encoding(&self) -> Result<&'static Encoding> {
+        let encoding = Encoding::for_label(self.encoding.as_bytes())
+            .ok_or_else(|| Error::Config(format!("Unknown encoding: {}", self.encoding)))?;
+        if !encoding.is_ascii_compatible() {
+            return Err(Error::Config(format!(
+                "Encoding {} is not ASCII compatible",
+                encoding.name()
+            )));
+        }
+        Ok(encoding)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let format = self.format()?;
+        let encoding = self.encoding()?;
+        // GBK, Big5, Shift_JIS 等多字节编码中, 汉字的后续字节可以是 0x40-0x7E,
+        // GB 18030 的四字节字符中还有数字, 这些字符作分隔符或引号会把一个字符拆开
+        if encoding != encoding_rs::UTF_8 && !encoding.is_single_byte() {
+            for (name, byte) in [("delimiter", format.delimiter), ("quote", format.quote)] {
+                if byte >= 0x40 || byte.is_ascii_digit() {
+                    return Err(Error::Config(format!(
+                        "{} {:?} can occur inside {} characters, use a punctuation character below '@' such as ',' ';' or a tab",
+                        name,
+                        byte as char,
+                        encoding.name()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CsvSourceConfig {
+    pub fn from_json(value: Value) -> Result<Self> {
+        if let Err(error) = SOURCE_SCHEMA.validate(&value) {
+            return Err(Error::Config(format!(
+                "Schema validation failed: {}",
+                error
+            )));
+        }
+
+        let config: Self = serde_json::from_value(value)
+            .map_err(|e| Error::Config(format!("Invalid CSV config: {}", e)))?;
+        config.options.validate()?;
+        validate_columns(config.columns.as_deref(), &config.primary_key)?;
+        Ok(config)
+    }
+}
+
+impl CsvSinkConfig {
+    pub fn from_json(value: Value) -> Result<Self> {
+        if let Err(error) = SINK_SCHEMA.validate(&value) {
+            return Err(Error::Config(format!(
+                "Schema validation failed: {}",
+                error
+            )));
+        }
+
+        let config: Self = serde_json::from_value(value)
+            .map_err(|e| Error::Config(format!("Invalid CSV config: {}", e)))?;
+        config.options.validate()?;
+        Ok(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::types::DbsyncType;
+    use serde_json::json;
+
+    #[test]
+    fn test_source_config_validation() {
+        let config = CsvSourceConfig::from_json(json!({
+            "path": "users.csv",
+            "delimiter": ";",
+            "columns": [
+                {"name": "id", "type": "BIGINT", "nullable": false},
+                {"name": "price", "type": "decimal(10,2)"}
+            ],
+            "primary_key": ["id"]
+        }))
+        .unwrap();
+        assert_eq!(config.options.delimiter, ';');
+        assert_eq!(config.options.quote, '"');
+        assert!(config.options.header);
+        assert_eq!(config.options.encoding().unwrap(), encoding_rs::UTF_8);
+        assert_eq!(
+            config.columns.unwrap()[1].dbsync_type().unwrap(),
+            DbsyncType::Decimal(10, 2)
+        );
+
+        for invalid in [
+            json!({"path": "a.csv", "delimiter": ",,"}),
+            json!({"path": "a.csv", "delimiter": "\""}),
+            json!({"path": "a.csv", "delimiter": "\n"}),
+            json!({"path": "a.csv", "delimiter": "；"}),
+            json!({"path": "a.csv", "encoding": "utf-16le"}),
+            json!({"path": "a.csv", "encoding": "gbk", "delimiter": "|"}),
+            json!({"path": "a.csv", "encoding": "shift_jis", "quote": "`"}),
+            json!({"path": "a.csv", "encoding": "no-such-encoding"}),
+            json!({"path": "a.csv", "columns": [{"name": "id", "type": "STRING"}]}),
+            json!({"path": "a.csv", "columns": [{"name": "id", "type": "INT"}], "primary_key": ["key"]}),
+        ] {
+            assert!(CsvSourceConfig::from_json(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sink_config_validation() {
+        let config = CsvSinkConfig::from_json(json!({
+            "path": "users.csv",
+            "quoting": "non_numeric",
+            "encoding": "GBK",
+            "null_value": "\\N"
+        }))
+        .unwrap();
+        assert_eq!(config.quoting, Quoting::NonNumeric);
+        assert_eq!(config.save_mode, SaveMode::Overwrite);
+        assert_eq!(config.options.encoding().unwrap(), encoding_rs::GBK);
+        assert_eq!(config.options.null_value, "\\N");
+
+        assert!(CsvSinkConfig::from_json(json!({
+            "path": "users.csv",
+            "quoting": "always"
+        }))
+        .is_err());
+    }
+}
@@ -0,0 +1,189 @@
+use std::io;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+// CSV 的分隔符和引号字符, 都是 ASCII 字符. 记录以 \n 或 \r\n 结尾,
+// 字段中的引号写成两个引号 (RFC 4180)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CsvFormat {
+    pub delimiter: u8,
+    pub quote: u8,
+}
+
+// 一个字段的原始字节, 尚未按编码解码. 带引号的字段不会被当作 NULL
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct RawField {
+    pub bytes: Vec<u8>,
+    pub quoted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    FieldStart,
+    Unquoted,
+    Quoted,
+    // 引号字段中读到一个引号, 可能是字段结束, 也可能是转义的引号
+    QuoteInQuoted,
+}
+
+impl CsvFormat {
+    // 读取下一条记录的字段, 返回消耗的字节数, 文件结束时返回 None.
+    // 引号内可以有换行, 记录之间的空行被跳过
+    pub(crate) async fn read_record<R: AsyncBufRead + Unpin>(
+        &self,
+        reader: &mut R,
+        line: &mut Vec<u8>,
+        fields: &mut Vec<RawField>,
+    ) -> io::Result<Option<u64>> {
+        fields.clear();
+        let mut field = RawField::default();
+        let mut state = State::FieldStart;
+        let mut started = false;
+        let mut consumed = 0;
+
+        loop {
+            line.clear();
+            let n = reader.read_until(b'\n', line).await?;
+            consumed += n as u64;
+            if n == 0 {
+                return match state {
+                    State::Quoted => Err(invalid_data("unterminated quoted field")),
+                    _ if !started => Ok(None),
+                    _ => {
+                        fields.push(field);
+                        Ok(Some(consumed))
+                    }
+                };
+            }
+            if !started && (line.as_slice() == b"\n" || line.as_slice() == b"\r\n") {
+                continue;
+            }
+            started = true;
+
+            for (i, &byte) in line.iter().enumerate() {
+                let line_end = byte == b'\n' || (byte == b'\r' && line[i + 1..] == *b"\n");
+                match state {
+                    State::Quoted if byte == self.quote => state = State::QuoteInQuoted,
+                    State::Quoted => field.bytes.push(byte),
+                    State::QuoteInQuoted if byte == self.quote => {
+                        field.bytes.push(byte);
+                        state = State::Quoted;
+                    }
+                    State::FieldStart if byte == self.quote => {
+                        field.quoted = true;
+                        state = State::Quoted;
+                    }
+                    _ if byte == self.delimiter => {
+                        fields.push(std::mem::take(&mut field));
+                        state = State::FieldStart;
+                    }
+                    _ if line_end => {
+                        // \r\n 中的 \n 在下一次循环结束记录
+                        if byte == b'\n' {
+                            fields.push(field);
+                            return Ok(Some(consumed));
+                        }
+                    }
+                    State::QuoteInQuoted => {
+                        return Err(invalid_data("unexpected character after closing quote"))
+                    }
+                    State::FieldStart | State::Unquoted => {
+                        field.bytes.push(byte);
+                        state = State::Unquoted;
+                    }
+                }
+            }
+        }
+    }
+
+    // 字段中有分隔符, 引号或换行时必须加引号
+    pub(crate) fn needs_quotes(&self, text: &str) -> bool {
+        text.bytes()
+            .any(|b| b == self.delimiter || b == self.quote || b == b'\r' || b == b'\n')
+    }
+
+    pub(crate) fn push_field(&self, out: &mut String, text: &str, quoted: bool) {
+        if !quoted {
+            out.push_str(text);
+            return;
+        }
+        let quote = self.quote as char;
+        out.push(quote);
+        for c in text.chars() {
+            if c == quote {
+                out.push(quote);
+            }
+            out.push(c);
+        }
+        out.push(quote);
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORMAT: CsvFormat = CsvFormat {
+        delimiter: b',',
+        quote: b'"',
+    };
+
+    async fn read_all(data: &[u8]) -> io::Result<Vec<Vec<(String, bool)>>> {
+        let mut reader = data;
+        let (mut line, mut fields) = (Vec::new(), Vec::new());
+        let mut records = Vec::new();
+        let mut total = 0;
+        while let Some(consumed) = FORMAT
+            .read_record(&mut reader, &mut line, &mut fields)
+            .await?
+        {
+            total += consumed;
+            records.push(
+                fields
+                    .iter()
+                    .map(|f| (String::from_utf8(f.bytes.clone()).unwrap(), f.quoted))
+                    .collect(),
+            );
+        }
+        assert_eq!(total, data.len() as u64);
+        Ok(records)
+    }
+
+    #[tokio::test]
+    async fn test_read_records() {
+        let records = read_all(b"a,\"b,\"\"c\"\"\",\r\n\n\"line\r\nbreak\",,\"\"\nlast")
+            .await
+            .unwrap();
+        let field = |text: &str, quoted| (text.to_string(), quoted);
+        assert_eq!(
+            records,
+            vec![
+                vec![field("a", false), field("b,\"c\"", true), field("", false)],
+                vec![
+                    field("line\r\nbreak", true),
+                    field("", false),
+                    field("", true)
+                ],
+                vec![field("last", false)],
+            ]
+        );
+
+        assert!(read_all(b"\"open\nfield").await.is_err());
+        assert!(read_all(b"\"a\"b,c\n").await.is_err());
+    }
+
+    #[test]
+    fn test_push_field() {
+        let mut out = String::new();
+        FORMAT.push_field(&mut out, "say \"hi\"", true);
+        out.push(',');
+        FORMAT.push_field(&mut out, "plain", false);
+        assert_eq!(out, "\"say \"\"hi\"\"\",plain");
+        assert!(FORMAT.needs_quotes("a,b"));
+        assert!(FORMAT.needs_quotes("a\nb"));
+        assert!(!FORMAT.needs_quotes("a;b"));
+    }
+}
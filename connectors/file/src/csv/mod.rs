@@ -0,0 +1,8 @@
+mod config;
+mod format;
+mod sink;
+mod source;
+
+pub use config::{CsvSinkConfig, CsvSourceConfig, Quoting};
+pub use sink::CsvSink;
+pub use source::CsvSource;
@@ -0,0 +1,174 @@
+use super::config::{CsvSinkConfig, Quoting};
+use super::format::CsvFormat;
+use crate::output::{open_output, SharedOutput};
+use crate::types::format_value;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{BatchSchema, ConnectorConfig, Context, DataBatch, Operation, Record, Sink},
+    error::{Error, Result},
+    types::DbsyncValue,
+};
+use encoding_rs::Encoding;
+use std::path::Path;
+use tracing::info;
+
+#[derive(Clone)]
+pub struct CsvSink {
+    config: CsvSinkConfig,
+    format: CsvFormat,
+    encoding: &'static Encoding,
+    output: SharedOutput,
+    // SaveMode::Ignore 且文件已存在时跳过所有写入
+    skip_writes: bool,
+    // 上下文中的列名, 没有写入任何行时用它写表头
+    columns: Option<Vec<String>>,
+}
+
+impl CsvSink {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = CsvSinkConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            format: config.options.format()?,
+            encoding: config.options.encoding()?,
+            config,
+            output: SharedOutput::default(),
+            skip_writes: false,
+            columns: None,
+        })
+    }
+
+    // 按 quoting 写入一个非 NULL 字段
+    fn push_text(&self, out: &mut String, column: &str, text: &str, numeric: bool) -> Result<()> {
+        let needs_quotes = self.format.needs_quotes(text);
+        let quoted = match self.config.quoting {
+            // 与 null_value 相同的值加引号, 读回时不会被当作 NULL
+            Quoting::Minimal => needs_quotes || text == self.config.options.null_value,
+            Quoting::All => true,
+            Quoting::NonNumeric => !numeric || needs_quotes,
+            Quoting::None if needs_quotes => {
+                return Err(Error::Write(format!(
+                    "Column {}: value {:?} needs quotes but quoting is none",
+                    column, text
+                )))
+            }
+            Quoting::None => false,
+        };
+        self.format.push_field(out, text, quoted);
+        Ok(())
+    }
+
+    fn push_record(&self, out: &mut String, schema: &BatchSchema, record: &Record) -> Result<()> {
+        // 文件只能追加, 无法按主键更新或删除已写入的行
+        if matches!(record.op, Operation::Update | Operation::Delete) {
+            return Err(Error::Write(format!(
+                "CSV sink only appends rows, cannot apply {:?}",
+                record.op
+            )));
+        }
+        for (i, (value, field)) in record.values.iter().zip(&schema.fields).enumerate() {
+            if i > 0 {
+                out.push(self.config.options.delimiter);
+            }
+            if *value == DbsyncValue::Null {
+                out.push_str(&self.config.options.null_value);
+                continue;
+            }
+            let text = format_value(value, &field.data_type)
+                .map_err(|e| Error::Write(format!("Column {}: {}", field.name, e)))?;
+            let numeric = matches!(
+                value,
+                DbsyncValue::Integer(_)
+                    | DbsyncValue::UInteger(_)
+                    | DbsyncValue::Float(_)
+                    | DbsyncValue::Decimal(_)
+            );
+            self.push_text(out, &field.name, &text, numeric)?;
+        }
+        out.push('\n');
+        Ok(())
+    }
+
+    fn encode(&self, text: String) -> Result<Vec<u8>> {
+        if self.encoding == encoding_rs::UTF_8 {
+            return Ok(text.into_bytes());
+        }
+        let (bytes, _, had_errors) = self.encoding.encode(&text);
+        if had_errors {
+            return Err(Error::Write(format!(
+                "Text cannot be encoded in {}",
+                self.encoding.name()
+            )));
+        }
+        Ok(bytes.into_owned())
+    }
+
+    // 列名组成的表头, 不写表头时为 None
+    fn header<'a>(&self, names: impl Iterator<Item = &'a str>) -> Result<Option<Vec<u8>>> {
+        if !self.config.options.header {
+            return Ok(None);
+        }
+        let mut header = String::new();
+        for (i, name) in names.enumerate() {
+            if i > 0 {
+                header.push(self.config.options.delimiter);
+            }
+            self.push_text(&mut header, name, name, false)?;
+        }
+        header.push('\n');
+        self.encode(header).map(Some)
+    }
+}
+
+#[async_trait]
+impl Sink for CsvSink {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let path = Path::new(&self.config.path);
+        info!("Opening CSV sink: {}", path.display());
+
+        let output = open_output(path, self.config.save_mode, ctx).await?;
+        self.skip_writes = output.is_none();
+        self.output.set(output).await;
+        self.columns = ctx
+            .schema
+            .as_ref()
+            .map(|schema| schema.columns.iter().map(|c| c.name.clone()).collect());
+        Ok(())
+    }
+
+    async fn write_batch(&mut self, batch: DataBatch) -> Result<()> {
+        if batch.records.is_empty() || self.skip_writes {
+            return Ok(());
+        }
+
+        let mut text = String::new();
+        for record in &batch.records {
+            self.push_record(&mut text, &batch.schema, record)?;
+        }
+        // 表头以第一个写入的批次的列为准, 转换可能改变了列
+        let header = self.header(batch.schema.names())?;
+        self.output
+            .write(header.as_deref(), &self.encode(text)?)
+            .await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.output.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        // 没有任何数据时也写入表头
+        if let (false, Some(columns)) = (self.skip_writes, &self.columns) {
+            if let Some(header) = self.header(columns.iter().map(String::as_str))? {
+                self.output.write_header(&header).await?;
+            }
+        }
+        info!("Closing CSV file {}", self.config.path);
+        self.output.close().await
+    }
+
+    fn clone_box(&self) -> Box<dyn Sink> {
+        Box::new(self.clone())
+    }
+}
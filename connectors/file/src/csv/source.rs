@@ -0,0 +1,357 @@
+use super::config::CsvSourceConfig;
+use super::format::{CsvFormat, RawField};
+use crate::column::{describe_columns, file_table_schema, ColumnConfig};
+use crate::index::{key_row, row_key, RowIndex, ROW_NUMBER};
+use crate::infer::TypeInference;
+use crate::types::parse_value;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchSchema, ConnectorConfig, Context, DataBatch, KeyRange, KeyValue, RangeBatch, Record,
+        ShardedSource, Source,
+    },
+    error::{Error, Result},
+    schema::{ColumnSchema, TableSchema},
+    types::DbsyncValue,
+};
+use encoding_rs::Encoding;
+use std::any::Any;
+use std::borrow::Cow;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tracing::info;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+#[derive(Clone)]
+pub struct CsvSource {
+    config: CsvSourceConfig,
+    format: CsvFormat,
+    encoding: &'static Encoding,
+    // init 时由列配置或表头和推断的类型确定
+    table_schema: Option<TableSchema>,
+    schema: Option<Arc<BatchSchema>>,
+    // init 时扫描整个文件建立, 所有 reader 共享
+    index: Arc<RowIndex>,
+    // read_batch 的游标, 即已读取的行数
+    cursor: u64,
+}
+
+impl CsvSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = CsvSourceConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            format: config.options.format()?,
+            encoding: config.options.encoding()?,
+            config,
+            table_schema: None,
+            schema: None,
+            index: Arc::new(RowIndex::default()),
+            cursor: 0,
+        })
+    }
+
+    fn schema(&self) -> Result<&Arc<BatchSchema>> {
+        self.schema
+            .as_ref()
+            .ok_or_else(|| Error::Read("CSV source is not initialized".into()))
+    }
+
+    async fn open(&self, offset: u64) -> Result<BufReader<File>> {
+        let error =
+            |e: std::io::Error| Error::Read(format!("Failed to open {}: {}", self.config.path, e));
+        let mut file = File::open(&self.config.path).await.map_err(error)?;
+        file.seek(SeekFrom::Start(offset)).await.map_err(error)?;
+        Ok(BufReader::new(file))
+    }
+
+    async fn next_record(
+        &self,
+        reader: &mut BufReader<File>,
+        line: &mut Vec<u8>,
+        fields: &mut Vec<RawField>,
+        row: u64,
+    ) -> Result<Option<u64>> {
+        self.format
+            .read_record(reader, line, fields)
+            .await
+            .map_err(|e| Error::Read(format!("{}: row {}: {}", self.config.path, row, e)))
+    }
+
+    fn decode<'a>(&self, field: &'a RawField, row: u64, column: usize) -> Result<Cow<'a, str>> {
+        self.encoding
+            .decode_without_bom_handling_and_without_replacement(&field.bytes)
+            .ok_or_else(|| {
+                Error::Read(format!(
+                    "{}: row {}, column {}: invalid {} text",
+                    self.config.path,
+                    row,
+                    column + 1,
+                    self.encoding.name()
+                ))
+            })
+    }
+
+    // 没有引号且与 null_value 相同的字段为 NULL
+    fn is_null(&self, field: &RawField, text: &str) -> bool {
+        !field.quoted && text == self.config.options.null_value
+    }
+
+    fn to_record(&self, fields: &[RawField], row: u64, schema: &BatchSchema) -> Result<Record> {
+        if fields.len() != schema.len() {
+            return Err(self.width_error(row, fields.len(), schema.len()));
+        }
+        fields
+            .iter()
+            .zip(&schema.fields)
+            .enumerate()
+            .map(|(column, (field, schema_field))| {
+                let text = self.decode(field, row, column)?;
+                if self.is_null(field, &text) {
+                    return Ok(DbsyncValue::Null);
+                }
+                parse_value(&text, &schema_field.data_type).map_err(|e| {
+                    Error::Read(format!(
+                        "{}: row {}, column {}: {}",
+                        self.config.path, row, schema_field.name, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Record::new)
+    }
+
+    fn width_error(&self, row: u64, found: usize, expected: usize) -> Error {
+        Error::Read(format!(
+            "{}: row {} has {} fields, expected {}",
+            self.config.path, row, found, expected
+        ))
+    }
+
+    // 读取行号在 (after, end] 内的下一批数据, 返回最后一行的行号
+    async fn read_rows(
+        &self,
+        after: u64,
+        end: u64,
+        limit: usize,
+    ) -> Result<Option<(DataBatch, u64)>> {
+        let schema = self.schema()?;
+        let last = end.min(self.index.rows()).min(after + limit as u64);
+        if after >= last {
+            return Ok(None);
+        }
+
+        let (mut row, offset) = self.index.seek(after + 1);
+        let mut reader = self.open(offset).await?;
+        let (mut line, mut fields) = (Vec::new(), Vec::new());
+        let mut records = Vec::new();
+        while row <= last {
+            if self
+                .next_record(&mut reader, &mut line, &mut fields, row)
+                .await?
+                .is_none()
+            {
+                return Err(Error::Read(format!(
+                    "{}: row {} not found, the file changed while reading",
+                    self.config.path, row
+                )));
+            }
+            if row > after {
+                records.push(self.to_record(&fields, row, schema)?);
+            }
+            row += 1;
+        }
+
+        Ok(Some((DataBatch::new(schema.clone(), records), last)))
+    }
+
+    // 扫描整个文件: 读取表头, 校验每行的字段数, 建立行索引, 并用前 infer_rows 行推断列类型
+    async fn scan(&mut self) -> Result<TableSchema> {
+        let mut reader = self.open(0).await?;
+        let mut offset = 0;
+        let buffer = reader.fill_buf().await?;
+        if self.encoding == encoding_rs::UTF_8 && buffer.starts_with(UTF8_BOM) {
+            reader.consume(UTF8_BOM.len());
+            offset += UTF8_BOM.len() as u64;
+        }
+
+        let (mut line, mut fields) = (Vec::new(), Vec::new());
+        let header = if self.config.options.header {
+            match self
+                .next_record(&mut reader, &mut line, &mut fields, 0)
+                .await?
+            {
+                Some(consumed) => {
+                    offset += consumed;
+                    let names = fields
+                        .iter()
+                        .enumerate()
+                        .map(|(column, field)| Ok(self.decode(field, 0, column)?.into_owned()))
+                        .collect::<Result<Vec<_>>>()?;
+                    Some(names)
+                }
+                None => None,
+            }
+        } else {
+            None
+        };
+        if let (Some(header), Some(columns)) = (&header, &self.config.columns) {
+            if header.len() != columns.len() {
+                return Err(Error::Config(format!(
+                    "{} has {} header fields but {} columns are configured",
+                    self.config.path,
+                    header.len(),
+                    columns.len()
+                )));
+            }
+        }
+
+        let mut width = self
+            .config
+            .columns
+            .as_ref()
+            .map(Vec::len)
+            .or(header.as_ref().map(Vec::len));
+        let mut inference = Vec::new();
+        let mut index = RowIndex::default();
+        loop {
+            let row = index.rows() + 1;
+            let Some(consumed) = self
+                .next_record(&mut reader, &mut line, &mut fields, row)
+                .await?
+            else {
+                break;
+            };
+            let width = *width.get_or_insert(fields.len());
+            if fields.len() != width {
+                return Err(self.width_error(row, fields.len(), width));
+            }
+            if self.config.columns.is_none() && row <= self.config.infer_rows as u64 {
+                inference.resize_with(width, TypeInference::default);
+                for (column, field) in fields.iter().enumerate() {
+                    let text = self.decode(field, row, column)?;
+                    if !self.is_null(field, &text) {
+                        inference[column].add(&text);
+                    }
+                }
+            }
+            index.push(offset);
+            offset += consumed;
+        }
+
+        let columns = match (&self.config.columns, header) {
+            (Some(columns), _) => columns
+                .iter()
+                .map(ColumnConfig::column_schema)
+                .collect::<Result<Vec<_>>>()?,
+            (None, header) => {
+                let width = width.ok_or_else(|| {
+                    Error::Read(format!(
+                        "{} is empty and no columns are configured",
+                        self.config.path
+                    ))
+                })?;
+                inference.resize_with(width, TypeInference::default);
+                let names = header
+                    .unwrap_or_else(|| (1..=width).map(|i| format!("column_{}", i)).collect());
+                names
+                    .into_iter()
+                    .zip(&inference)
+                    .map(|(name, inference)| ColumnSchema::new(name, inference.data_type()))
+                    .collect()
+            }
+        };
+
+        let schema = file_table_schema(&self.config.path, columns, &self.config.primary_key)?;
+        self.index = Arc::new(index);
+        Ok(schema)
+    }
+}
+
+#[async_trait]
+impl Source for CsvSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        info!("Opening CSV source: {}", self.config.path);
+        let schema = self.scan().await?;
+        info!(
+            "Indexed {} rows of {}, columns: {}",
+            self.index.rows(),
+            self.config.path,
+            describe_columns(&schema)
+        );
+
+        self.schema = Some(Arc::new(schema.batch_schema()));
+        ctx.set_schema(schema.clone());
+        self.table_schema = Some(schema);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        match self.read_rows(self.cursor, u64::MAX, batch_size).await? {
+            Some((batch, last_row)) => {
+                self.cursor = last_row;
+                Ok(Some(batch))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ShardedSource for CsvSource {
+    async fn get_total_records(&self) -> Result<i64> {
+        Ok(self.index.rows() as i64)
+    }
+
+    async fn get_primary_key(&self) -> Result<Vec<String>> {
+        Ok(vec![ROW_NUMBER.to_string()])
+    }
+
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+        Ok(self.index.id_range())
+    }
+
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        self.index.split(chunks)
+    }
+
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>> {
+        let after = after.map(key_row).transpose()?.unwrap_or(0);
+        let end = key_row(end)?;
+        let batch = self.read_rows(after, end, self.config.batch_size).await?;
+        Ok(batch.map(|(batch, last_row)| RangeBatch {
+            batch,
+            last_key: row_key(last_row),
+        }))
+    }
+
+    async fn get_schema(&self) -> Result<TableSchema> {
+        self.table_schema
+            .clone()
+            .ok_or_else(|| Error::Read("CSV source is not initialized".into()))
+    }
+}
@@ -0,0 +1,83 @@
+use dbsync_core::{
+    connector::{KeyRange, KeyValue},
+    error::{Error, Result},
+    types::DbsyncValue,
+};
+
+// 文件按数据行的行号 (从 1 开始, 不含表头和空行) 切分和读取, 行号不在输出的列中
+pub(crate) const ROW_NUMBER: &str = "row_number";
+
+// 每隔多少行记录一次行首的字节偏移
+const STRIDE: u64 = 1024;
+
+// 数据行的稀疏索引, 行号从 1 开始. 按行号区间读取时先定位到区间前最近的
+// 记录点, 再顺序跳过至多 STRIDE - 1 行, 索引大小与文件大小无关
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RowIndex {
+    rows: u64,
+    // 第 i * STRIDE + 1 行的行首偏移
+    offsets: Vec<u64>,
+}
+
+impl RowIndex {
+    // 按顺序加入下一行, offset 为该行行首的字节偏移
+    pub(crate) fn push(&mut self, offset: u64) {
+        if self.rows.is_multiple_of(STRIDE) {
+            self.offsets.push(offset);
+        }
+        self.rows += 1;
+    }
+
+    pub(crate) fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    // 第 row 行之前 (含) 最近的记录点: (行号, 字节偏移)
+    pub(crate) fn seek(&self, row: u64) -> (u64, u64) {
+        let index = ((row.max(1) - 1) / STRIDE).min(self.offsets.len().saturating_sub(1) as u64);
+        let offset = self.offsets.get(index as usize).copied().unwrap_or(0);
+        (index * STRIDE + 1, offset)
+    }
+
+    // 第一行和最后一行的行号, 没有数据行时为 None
+    pub(crate) fn id_range(&self) -> Option<(KeyValue, KeyValue)> {
+        (self.rows > 0).then(|| (row_key(1), row_key(self.rows)))
+    }
+
+    pub(crate) fn split(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        let Some((min, max)) = self.id_range() else {
+            return Ok(Vec::new());
+        };
+        KeyRange::split_integer(&min, &max, chunks)
+            .ok_or_else(|| Error::Read(format!("Cannot split rows {:?}..{:?}", min, max)))
+    }
+}
+
+pub(crate) fn row_key(row: u64) -> KeyValue {
+    KeyValue(vec![DbsyncValue::Integer(row as i64)])
+}
+
+pub(crate) fn key_row(key: &KeyValue) -> Result<u64> {
+    match key.0.as_slice() {
+        [DbsyncValue::Integer(row)] if *row >= 0 => Ok(*row as u64),
+        _ => Err(Error::Read(format!("Invalid row number key: {:?}", key.0))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_index_seek() {
+        let mut index = RowIndex::default();
+        for row in 0..3000 {
+            index.push(row * 10 + 7);
+        }
+        assert_eq!(index.rows(), 3000);
+        assert_eq!(index.seek(1), (1, 7));
+        assert_eq!(index.seek(1024), (1, 7));
+        assert_eq!(index.seek(1025), (1025, 10247));
+        assert_eq!(index.seek(3000), (2049, 20487));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::types::{decimal_digits, parse_date, parse_datetime, parse_time};
+use dbsync_core::types::DbsyncType;
+use serde_json::Value;
+
+// MySQL DECIMAL 的最大精度和小数位数
+const MAX_PRECISION: usize = 65;
+const MAX_SCALE: usize = 30;
+
+// 从样本文本推断一列的类型. 每个样本排除它不符合的候选类型,
+// 最后取剩下的最窄的类型; 没有非 NULL 样本或没有候选类型时为 TEXT
+#[derive(Debug, Clone)]
+pub(crate) struct TypeInference {
+    samples: usize,
+    boolean: bool,
+    integer: bool,
+    decimal: bool,
+    double: bool,
+    date: bool,
+    time: bool,
+    datetime: bool,
+    timestamp: bool,
+    // 样本中整数部分和小数部分的最大位数
+    integer_digits: usize,
+    scale: usize,
+    // 样本中秒的最大小数位数
+    fsp: u8,
+}
+
+impl Default for TypeInference {
+    fn default() -> Self {
+        Self {
+            samples: 0,
+            boolean: true,
+            integer: true,
+            decimal: true,
+            double: true,
+            date: true,
+            time: true,
+            datetime: true,
+            timestamp: true,
+            integer_digits: 0,
+            scale: 0,
+            fsp: 0,
+        }
+    }
+}
+
+impl TypeInference {
+    // 加入一个非 NULL 的样本
+    pub(crate) fn add(&mut self, text: &str) {
+        self.samples += 1;
+        let text = text.trim();
+
+        // 只认 true / false, 0 和 1 推断为整数
+        self.boolean &= text.eq_ignore_ascii_case("true") || text.eq_ignore_ascii_case("false");
+
+        // 带前导零的数字 (如邮编 007) 按文本处理, 以免丢失前导零
+        let digits = decimal_digits(text).filter(|_| !has_leading_zero(text));
+        self.integer &= digits.is_some_and(|(_, scale)| scale == 0) && text.parse::<i64>().is_ok();
+        self.decimal &= digits.is_some();
+        if let Some((integer_digits, scale)) = digits {
+            self.integer_digits = self.integer_digits.max(integer_digits);
+            self.scale = self.scale.max(scale);
+        }
+        self.double &= digits.is_some() || (is_float(text) && !has_leading_zero(text));
+
+        self.date &= parse_date(text).is_some();
+        match parse_time(text) {
+            Some((_, fsp)) => self.fsp = self.fsp.max(fsp),
+            None => self.time = false,
+        }
+        match parse_datetime(text) {
+            Some(datetime) => {
+                self.fsp = self.fsp.max(datetime.fsp);
+                self.datetime &= !datetime.zoned;
+                self.timestamp &= datetime.zoned;
+            }
+            None => {
+                self.datetime = false;
+                self.timestamp = false;
+            }
+        }
+    }
+
+    pub(crate) fn data_type(&self) -> DbsyncType {
+        if self.samples == 0 {
+            DbsyncType::Text
+        } else if self.boolean {
+            DbsyncType::Boolean
+        } else if self.integer {
+            DbsyncType::BigInt
+        } else if self.decimal && self.integer_digits + self.scale <= MAX_PRECISION {
+            let scale = self.scale.min(MAX_SCALE);
+            let precision = (self.integer_digits + scale).max(1);
+            DbsyncType::Decimal(precision as u8, scale as u8)
+        } else if self.double || self.decimal {
+            DbsyncType::Double
+        } else if self.date {
+            DbsyncType::Date
+        } else if self.time {
+            DbsyncType::Time(self.fsp)
+        } else if self.datetime {
+            DbsyncType::DateTime(self.fsp)
+        } else if self.timestamp {
+            DbsyncType::Timestamp(self.fsp)
+        } else {
+            DbsyncType::Text
+        }
+    }
+}
+
+// 从 JSON 样本推断一列的类型. 数字和布尔值按 JSON 原生类型推断, 字符串按
+// 文本推断; 出现对象, 数组或多种 JSON 类型混合时为 JSON
+#[derive(Debug, Clone, Default)]
+pub(crate) struct JsonInference {
+    boolean: bool,
+    number: bool,
+    string: bool,
+    nested: bool,
+    float: bool,
+    negative: bool,
+    // 超出 i64 范围的无符号整数
+    unsigned: bool,
+    text: TypeInference,
+}
+
+impl JsonInference {
+    pub(crate) fn add(&mut self, value: &Value) {
+        match value {
+            Value::Null => {}
+            Value::Bool(_) => self.boolean = true,
+            Value::Number(n) => {
+                self.number = true;
+                match (n.as_i64(), n.is_u64()) {
+                    (Some(i), _) => self.negative |= i < 0,
+                    (None, true) => self.unsigned = true,
+                    (None, false) => self.float = true,
+                }
+            }
+            // 浮点数的 NaN 和无穷大写成字符串
+            Value::String(text) if matches!(text.as_str(), "NaN" | "inf" | "-inf") => {
+                self.number = true;
+                self.float = true;
+            }
+            Value::String(text) => {
+                self.string = true;
+                self.text.add(text);
+            }
+            Value::Array(_) | Value::Object(_) => self.nested = true,
+        }
+    }
+
+    pub(crate) fn data_type(&self) -> DbsyncType {
+        let kinds = [self.boolean, self.number, self.string, self.nested]
+            .iter()
+            .filter(|kind| **kind)
+            .count();
+        if kinds == 0 {
+            DbsyncType::Text
+        } else if kinds > 1 || self.nested {
+            DbsyncType::Json
+        } else if self.boolean {
+            DbsyncType::Boolean
+        } else if self.number {
+            match (self.float, self.unsigned, self.negative) {
+                (true, _, _) => DbsyncType::Double,
+                (false, true, true) => DbsyncType::Decimal(20, 0),
+                (false, true, false) => DbsyncType::UnsignedBigInt,
+                (false, false, _) => DbsyncType::BigInt,
+            }
+        } else {
+            // 字符串中的数字是 DECIMAL 的文本形式, 字符串 "true" 仍是文本
+            match self.text.data_type() {
+                DbsyncType::BigInt => DbsyncType::Decimal(self.text.integer_digits.max(1) as u8, 0),
+                DbsyncType::Boolean | DbsyncType::Double => DbsyncType::Text,
+                data_type => data_type,
+            }
+        }
+    }
+}
+
+fn has_leading_zero(text: &str) -> bool {
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    unsigned.len() > 1 && unsigned.starts_with('0') && !unsigned.starts_with("0.")
+}
+
+// 带指数的浮点数, 如 1.5e-3. 不接受 inf 和 NaN, 以免把单词推断为数字
+fn is_float(text: &str) -> bool {
+    text.bytes()
+        .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+        && text.parse::<f64>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn infer(samples: &[&str]) -> DbsyncType {
+        let mut inference = TypeInference::default();
+        for sample in samples {
+            inference.add(sample);
+        }
+        inference.data_type()
+    }
+
+    #[test]
+    fn test_infer_types() {
+        assert_eq!(infer(&[]), DbsyncType::Text);
+        assert_eq!(infer(&["true", "FALSE"]), DbsyncType::Boolean);
+        assert_eq!(infer(&["1", "0", "-42"]), DbsyncType::BigInt);
+        assert_eq!(infer(&["1", "-12.5", "0.125"]), DbsyncType::Decimal(5, 3));
+        assert_eq!(infer(&["1.5", "2e10"]), DbsyncType::Double);
+        assert_eq!(infer(&["99999999999999999999"]), DbsyncType::Decimal(20, 0));
+        assert_eq!(infer(&["2024-01-02"]), DbsyncType::Date);
+        assert_eq!(infer(&["12:30", "-838:59:59.5"]), DbsyncType::Time(1));
+        assert_eq!(
+            infer(&["2024-01-02 03:04:05", "2024-01-02T03:04:05.123"]),
+            DbsyncType::DateTime(3)
+        );
+        assert_eq!(
+            infer(&["2024-01-02T03:04:05Z", "2024-01-02T03:04:05+08:00"]),
+            DbsyncType::Timestamp(0)
+        );
+    }
+
+    #[test]
+    fn test_infer_falls_back_to_text() {
+        assert_eq!(infer(&["007", "123"]), DbsyncType::Text);
+        assert_eq!(infer(&["1", "abc"]), DbsyncType::Text);
+        assert_eq!(infer(&["inf", "NaN"]), DbsyncType::Text);
+        assert_eq!(infer(&["2024-01-02", "12:30"]), DbsyncType::Text);
+        assert_eq!(
+            infer(&["2024-01-02T03:04:05", "2024-01-02T03:04:05Z"]),
+            DbsyncType::Text
+        );
+    }
+
+    #[test]
+    fn test_infer_json_types() {
+        let infer = |samples: Vec<Value>| {
+            let mut inference = JsonInference::default();
+            for sample in &samples {
+                inference.add(sample);
+            }
+            inference.data_type()
+        };
+        assert_eq!(infer(vec![json!(null)]), DbsyncType::Text);
+        assert_eq!(
+            infer(vec![json!(1), json!(-2), json!(null)]),
+            DbsyncType::BigInt
+        );
+        assert_eq!(
+            infer(vec![json!(1), json!(u64::MAX)]),
+            DbsyncType::UnsignedBigInt
+        );
+        assert_eq!(infer(vec![json!(1), json!(0.5)]), DbsyncType::Double);
+        assert_eq!(infer(vec![json!(1.5), json!("NaN")]), DbsyncType::Double);
+        assert_eq!(infer(vec![json!(true)]), DbsyncType::Boolean);
+        assert_eq!(
+            infer(vec![json!("12.50"), json!("3")]),
+            DbsyncType::Decimal(4, 2)
+        );
+        assert_eq!(infer(vec![json!("123")]), DbsyncType::Decimal(3, 0));
+        assert_eq!(infer(vec![json!("true")]), DbsyncType::Text);
+        assert_eq!(
+            infer(vec![json!("2024-01-02T03:04:05.5Z")]),
+            DbsyncType::Timestamp(1)
+        );
+        assert_eq!(infer(vec![json!({"a": 1})]), DbsyncType::Json);
+        assert_eq!(infer(vec![json!(1), json!("a")]), DbsyncType::Json);
+    }
+}
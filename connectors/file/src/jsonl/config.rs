@@ -0,0 +1,125 @@
+use crate::column::{validate_columns, ColumnConfig};
+use dbsync_core::connector::SaveMode;
+use dbsync_core::error::{Error, Result};
+use jsonschema::Validator;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+lazy_static::lazy_static! {
+    static ref SOURCE_SCHEMA: Validator = {
+        let schema = include_str!("../../schema/jsonl_source.json");
+        let schema = serde_json::from_str(schema).unwrap();
+        Validator::new(&schema).unwrap()
+    };
+    static ref SINK_SCHEMA: Validator = {
+        let schema = include_str!("../../schema/jsonl_sink.json");
+        let schema = serde_json::from_str(schema).unwrap();
+        Validator::new(&schema).unwrap()
+    };
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlSourceConfig {
+    pub path: String,
+    #[serde(default)]
+    pub columns: Option<Vec<ColumnConfig>>,
+    #[serde(default = "default_infer_rows")]
+    pub infer_rows: usize,
+    #[serde(default)]
+    pub primary_key: Vec<String>,
+    // 保存变更类型的字段, 不作为列读取
+    #[serde(default)]
+    pub op_field: Option<String>,
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlSinkConfig {
+    pub path: String,
+    // 设置后每个对象带上记录的变更类型, 可以写入 Update 和 Delete
+    #[serde(default)]
+    pub op_field: Option<String>,
+    #[serde(default)]
+    pub save_mode: SaveMode,
+}
+
+fn default_infer_rows() -> usize {
+    1000
+}
+fn default_batch_size() -> usize {
+    1000
+}
+
+impl JsonlSourceConfig {
+    pub fn from_json(value: Value) -> Result<Self> {
+        if let Err(error) = SOURCE_SCHEMA.validate(&value) {
+            return Err(Error::Config(format!(
+                "Schema validation failed: {}",
+                error
+            )));
+        }
+
+        let config: Self = serde_json::from_value(value)
+            .map_err(|e| Error::Config(format!("Invalid JSON Lines config: {}", e)))?;
+        validate_columns(config.columns.as_deref(), &config.primary_key)?;
+        if let (Some(columns), Some(op_field)) = (&config.columns, &config.op_field) {
+            if columns.iter().any(|c| &c.name == op_field) {
+                return Err(Error::Config(format!(
+                    "op_field {} is also a column",
+                    op_field
+                )));
+            }
+        }
+        Ok(config)
+    }
+}
+
+impl JsonlSinkConfig {
+    pub fn from_json(value: Value) -> Result<Self> {
+        if let Err(error) = SINK_SCHEMA.validate(&value) {
+            return Err(Error::Config(format!(
+                "Schema validation failed: {}",
+                error
+            )));
+        }
+
+        serde_json::from_value(value)
+            .map_err(|e| Error::Config(format!("Invalid JSON Lines config: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_config_validation() {
+        let config = JsonlSourceConfig::from_json(json!({
+            "path": "events.jsonl",
+            "columns": [{"name": "id", "type": "BIGINT"}],
+            "primary_key": ["id"],
+            "op_field": "_op"
+        }))
+        .unwrap();
+        assert_eq!(config.infer_rows, 1000);
+        assert_eq!(config.op_field.as_deref(), Some("_op"));
+
+        let config = JsonlSinkConfig::from_json(json!({"path": "events.jsonl"})).unwrap();
+        assert_eq!(config.op_field, None);
+        assert_eq!(config.save_mode, SaveMode::Overwrite);
+
+        for invalid in [
+            json!({"path": ""}),
+            json!({"path": "a.jsonl", "op_field": ""}),
+            json!({"path": "a.jsonl", "columns": [{"name": "id", "type": "INT"}], "op_field": "id"}),
+            json!({"path": "a.jsonl", "columns": [{"name": "id", "type": "INT"}], "primary_key": ["key"]}),
+        ] {
+            assert!(JsonlSourceConfig::from_json(invalid).is_err());
+        }
+        assert!(
+            JsonlSinkConfig::from_json(json!({"path": "a.jsonl", "save_mode": "merge"})).is_err()
+        );
+    }
+}
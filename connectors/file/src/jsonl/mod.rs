@@ -0,0 +1,7 @@
+mod config;
+mod sink;
+mod source;
+
+pub use config::{JsonlSinkConfig, JsonlSourceConfig};
+pub use sink::JsonlSink;
+pub use source::JsonlSource;
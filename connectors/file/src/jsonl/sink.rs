@@ -0,0 +1,111 @@
+use super::config::JsonlSinkConfig;
+use crate::output::{open_output, SharedOutput};
+use crate::types::to_json_value;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{BatchSchema, ConnectorConfig, Context, DataBatch, Operation, Record, Sink},
+    error::{Error, Result},
+};
+use std::path::Path;
+use tracing::info;
+
+#[derive(Clone)]
+pub struct JsonlSink {
+    config: JsonlSinkConfig,
+    output: SharedOutput,
+    // SaveMode::Ignore 且文件已存在时跳过所有写入
+    skip_writes: bool,
+}
+
+impl JsonlSink {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = JsonlSinkConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            config,
+            output: SharedOutput::default(),
+            skip_writes: false,
+        })
+    }
+
+    // 一条记录写成一行 JSON 对象, 字段按批次 schema 的列顺序排列
+    fn push_record(&self, out: &mut Vec<u8>, schema: &BatchSchema, record: &Record) -> Result<()> {
+        // 没有 op_field 时无法表示更新和删除, 只能追加
+        if self.config.op_field.is_none()
+            && matches!(record.op, Operation::Update | Operation::Delete)
+        {
+            return Err(Error::Write(format!(
+                "JSON Lines sink without op_field only appends rows, cannot apply {:?}",
+                record.op
+            )));
+        }
+        out.push(b'{');
+        for (i, (value, field)) in record.values.iter().zip(&schema.fields).enumerate() {
+            if i > 0 {
+                out.push(b',');
+            }
+            let json = to_json_value(value, &field.data_type)
+                .map_err(|e| Error::Write(format!("Column {}: {}", field.name, e)))?;
+            serde_json::to_writer(&mut *out, &field.name)?;
+            out.push(b':');
+            serde_json::to_writer(&mut *out, &json)?;
+        }
+        if let Some(op_field) = &self.config.op_field {
+            if !record.values.is_empty() {
+                out.push(b',');
+            }
+            serde_json::to_writer(&mut *out, op_field)?;
+            out.push(b':');
+            serde_json::to_writer(&mut *out, &record.op)?;
+        }
+        out.extend_from_slice(b"}\n");
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Sink for JsonlSink {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let path = Path::new(&self.config.path);
+        info!("Opening JSON Lines sink: {}", path.display());
+
+        let output = open_output(path, self.config.save_mode, ctx).await?;
+        self.skip_writes = output.is_none();
+        self.output.set(output).await;
+        Ok(())
+    }
+
+    async fn write_batch(&mut self, batch: DataBatch) -> Result<()> {
+        if batch.records.is_empty() || self.skip_writes {
+            return Ok(());
+        }
+        if let Some(op_field) = &self.config.op_field {
+            if batch.schema.index_of(op_field).is_some() {
+                return Err(Error::Write(format!(
+                    "op_field {} is also a column",
+                    op_field
+                )));
+            }
+        }
+
+        let mut data = Vec::new();
+        for record in &batch.records {
+            self.push_record(&mut data, &batch.schema, record)?;
+        }
+        self.output.write(None, &data).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.output.flush().await
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        info!("Closing JSON Lines file {}", self.config.path);
+        self.output.close().await
+    }
+
+    fn clone_box(&self) -> Box<dyn Sink> {
+        Box::new(self.clone())
+    }
+}
@@ -0,0 +1,328 @@
+use super::config::JsonlSourceConfig;
+use crate::column::{describe_columns, file_table_schema, ColumnConfig};
+use crate::index::{key_row, row_key, RowIndex, ROW_NUMBER};
+use crate::infer::JsonInference;
+use crate::types::from_json_value;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchSchema, ConnectorConfig, Context, DataBatch, KeyRange, KeyValue, Operation,
+        RangeBatch, Record, ShardedSource, Source,
+    },
+    error::{Error, Result},
+    schema::{ColumnSchema, TableSchema},
+};
+use serde::de::{self, Deserializer, MapAccess};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::any::Any;
+use std::fmt;
+use std::io::SeekFrom;
+use std::sync::Arc;
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncSeekExt, BufReader};
+use tracing::info;
+
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+// 保持字段顺序的 JSON 对象, 推断的列按字段首次出现的顺序排列
+struct OrderedObject(Vec<(String, Value)>);
+
+impl<'de> Deserialize<'de> for OrderedObject {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> de::Visitor<'de> for Visitor {
+            type Value = OrderedObject;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a JSON object")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> std::result::Result<OrderedObject, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    fields.push(entry);
+                }
+                Ok(OrderedObject(fields))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor)
+    }
+}
+
+#[derive(Clone)]
+pub struct JsonlSource {
+    config: JsonlSourceConfig,
+    // init 时由列配置或推断的类型确定
+    table_schema: Option<TableSchema>,
+    schema: Option<Arc<BatchSchema>>,
+    // init 时扫描整个文件建立, 所有 reader 共享
+    index: Arc<RowIndex>,
+    // read_batch 的游标, 即已读取的行数
+    cursor: u64,
+}
+
+impl JsonlSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = JsonlSourceConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            config,
+            table_schema: None,
+            schema: None,
+            index: Arc::new(RowIndex::default()),
+            cursor: 0,
+        })
+    }
+
+    fn schema(&self) -> Result<&Arc<BatchSchema>> {
+        self.schema
+            .as_ref()
+            .ok_or_else(|| Error::Read("JSON Lines source is not initialized".into()))
+    }
+
+    async fn open(&self, offset: u64) -> Result<BufReader<File>> {
+        let error =
+            |e: std::io::Error| Error::Read(format!("Failed to open {}: {}", self.config.path, e));
+        let mut file = File::open(&self.config.path).await.map_err(error)?;
+        file.seek(SeekFrom::Start(offset)).await.map_err(error)?;
+        Ok(BufReader::new(file))
+    }
+
+    // 读取下一个非空行, 返回消耗的字节数 (含跳过的空行), 文件结束时返回 None
+    async fn next_line(
+        &self,
+        reader: &mut BufReader<File>,
+        line: &mut Vec<u8>,
+    ) -> Result<Option<u64>> {
+        let mut consumed = 0;
+        loop {
+            line.clear();
+            let n = reader
+                .read_until(b'\n', line)
+                .await
+                .map_err(|e| Error::Read(format!("Failed to read {}: {}", self.config.path, e)))?;
+            if n == 0 {
+                return Ok(None);
+            }
+            consumed += n as u64;
+            if !line.iter().all(u8::is_ascii_whitespace) {
+                return Ok(Some(consumed));
+            }
+        }
+    }
+
+    fn parse<'a, T: Deserialize<'a>>(&self, line: &'a [u8], row: u64) -> Result<T> {
+        serde_json::from_slice(line)
+            .map_err(|e| Error::Read(format!("{}: row {}: {}", self.config.path, row, e)))
+    }
+
+    fn to_record(&self, line: &[u8], row: u64, schema: &BatchSchema) -> Result<Record> {
+        let object: Map<String, Value> = self.parse(line, row)?;
+        let values = schema
+            .fields
+            .iter()
+            .map(|field| {
+                let json = object.get(&field.name).unwrap_or(&Value::Null);
+                from_json_value(json, &field.data_type).map_err(|e| {
+                    Error::Read(format!(
+                        "{}: row {}, column {}: {}",
+                        self.config.path, row, field.name, e
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let op = match self.config.op_field.as_ref().and_then(|f| object.get(f)) {
+            None | Some(Value::Null) => Operation::Insert,
+            Some(op) => Operation::deserialize(op).map_err(|_| {
+                Error::Read(format!(
+                    "{}: row {}: invalid operation {}",
+                    self.config.path, row, op
+                ))
+            })?,
+        };
+        Ok(Record::new(values).with_op(op))
+    }
+
+    // 读取行号在 (after, end] 内的下一批数据, 返回最后一行的行号
+    async fn read_rows(
+        &self,
+        after: u64,
+        end: u64,
+        limit: usize,
+    ) -> Result<Option<(DataBatch, u64)>> {
+        let schema = self.schema()?;
+        let last = end.min(self.index.rows()).min(after + limit as u64);
+        if after >= last {
+            return Ok(None);
+        }
+
+        let (mut row, offset) = self.index.seek(after + 1);
+        let mut reader = self.open(offset).await?;
+        let mut line = Vec::new();
+        let mut records = Vec::new();
+        while row <= last {
+            if self.next_line(&mut reader, &mut line).await?.is_none() {
+                return Err(Error::Read(format!(
+                    "{}: row {} not found, the file changed while reading",
+                    self.config.path, row
+                )));
+            }
+            if row > after {
+                records.push(self.to_record(&line, row, schema)?);
+            }
+            row += 1;
+        }
+
+        Ok(Some((DataBatch::new(schema.clone(), records), last)))
+    }
+
+    // 扫描整个文件: 校验每行都是 JSON 对象, 建立行索引, 并用前 infer_rows 行推断列
+    async fn scan(&mut self) -> Result<TableSchema> {
+        let mut reader = self.open(0).await?;
+        let mut offset = 0;
+        let buffer = reader.fill_buf().await?;
+        if buffer.starts_with(UTF8_BOM) {
+            reader.consume(UTF8_BOM.len());
+            offset += UTF8_BOM.len() as u64;
+        }
+
+        let mut inference: Vec<(String, JsonInference)> = Vec::new();
+        let mut index = RowIndex::default();
+        let mut line = Vec::new();
+        while let Some(consumed) = self.next_line(&mut reader, &mut line).await? {
+            let row = index.rows() + 1;
+            let object: OrderedObject = self.parse(&line, row)?;
+            if self.config.columns.is_none() && row <= self.config.infer_rows as u64 {
+                for (name, value) in &object.0 {
+                    if self.config.op_field.as_ref() == Some(name) {
+                        continue;
+                    }
+                    let position = match inference.iter().position(|(n, _)| n == name) {
+                        Some(position) => position,
+                        None => {
+                            inference.push((name.clone(), JsonInference::default()));
+                            inference.len() - 1
+                        }
+                    };
+                    inference[position].1.add(value);
+                }
+            }
+            // 行首在跳过的空行之后
+            index.push(offset + consumed - line.len() as u64);
+            offset += consumed;
+        }
+
+        let columns = match &self.config.columns {
+            Some(columns) => columns
+                .iter()
+                .map(ColumnConfig::column_schema)
+                .collect::<Result<Vec<_>>>()?,
+            None if inference.is_empty() => {
+                return Err(Error::Read(format!(
+                    "{} has no fields and no columns are configured",
+                    self.config.path
+                )))
+            }
+            None => inference
+                .iter()
+                .map(|(name, inference)| ColumnSchema::new(name, inference.data_type()))
+                .collect(),
+        };
+
+        let schema = file_table_schema(&self.config.path, columns, &self.config.primary_key)?;
+        self.index = Arc::new(index);
+        Ok(schema)
+    }
+}
+
+#[async_trait]
+impl Source for JsonlSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        info!("Opening JSON Lines source: {}", self.config.path);
+        let schema = self.scan().await?;
+        info!(
+            "Indexed {} rows of {}, columns: {}",
+            self.index.rows(),
+            self.config.path,
+            describe_columns(&schema)
+        );
+
+        self.schema = Some(Arc::new(schema.batch_schema()));
+        ctx.set_schema(schema.clone());
+        self.table_schema = Some(schema);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        match self.read_rows(self.cursor, u64::MAX, batch_size).await? {
+            Some((batch, last_row)) => {
+                self.cursor = last_row;
+                Ok(Some(batch))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ShardedSource for JsonlSource {
+    async fn get_total_records(&self) -> Result<i64> {
+        Ok(self.index.rows() as i64)
+    }
+
+    async fn get_primary_key(&self) -> Result<Vec<String>> {
+        Ok(vec![ROW_NUMBER.to_string()])
+    }
+
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+        Ok(self.index.id_range())
+    }
+
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        self.index.split(chunks)
+    }
+
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>> {
+        let after = after.map(key_row).transpose()?.unwrap_or(0);
+        let end = key_row(end)?;
+        let batch = self.read_rows(after, end, self.config.batch_size).await?;
+        Ok(batch.map(|(batch, last_row)| RangeBatch {
+            batch,
+            last_key: row_key(last_row),
+        }))
+    }
+
+    async fn get_schema(&self) -> Result<TableSchema> {
+        self.table_schema
+            .clone()
+            .ok_or_else(|| Error::Read("JSON Lines source is not initialized".into()))
+    }
+}
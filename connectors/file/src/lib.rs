@@ -0,0 +1,11 @@
+mod column;
+mod csv;
+mod index;
+mod infer;
+mod jsonl;
+mod output;
+mod types;
+
+pub use csv::{CsvSink, CsvSinkConfig, CsvSource, CsvSourceConfig, Quoting};
+pub use jsonl::{JsonlSink, JsonlSinkConfig, JsonlSource, JsonlSourceConfig};
+pub use types::FileTypeMapper;
@@ -0,0 +1,134 @@
+use dbsync_core::{
+    connector::{Context, SaveMode},
+    error::{Error, Result},
+};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::Mutex;
+use tracing::info;
+
+// 输出文件, 所有 writer 共享同一个文件句柄, 每个批次整体写入, 不会与其他批次交错.
+// write 返回前数据已落盘, 任务在 write_batch 成功后记录的进度不会因崩溃丢失数据
+#[derive(Clone, Default)]
+pub(crate) struct SharedOutput(Arc<Mutex<Option<Output>>>);
+
+pub(crate) struct Output {
+    writer: BufWriter<File>,
+    // 文件中还没有任何内容
+    empty: bool,
+}
+
+// 按 save_mode 打开输出文件, SaveMode::Ignore 且文件已存在时返回 None.
+// 恢复任务时总是追加到已有的文件. 文件只能追加, 重新写入的区间会产生重复的行,
+// 而且多个 writer 写入的批次互相交错, 无法截断到某个区间之前, 因此不支持恢复按区间读取的任务
+pub(crate) async fn open_output(
+    path: &Path,
+    mode: SaveMode,
+    ctx: &Context,
+) -> Result<Option<Output>> {
+    if ctx.replay {
+        return Err(Error::Config(format!(
+            "Cannot resume writing to {}: partly written key ranges would be written twice, \
+             run the job again without resume",
+            path.display()
+        )));
+    }
+
+    let error =
+        |e: std::io::Error| Error::Write(format!("Failed to open {}: {}", path.display(), e));
+    let exists = tokio::fs::try_exists(path).await.map_err(error)?;
+
+    let append = if ctx.resume {
+        info!("Resuming job, appending to {}", path.display());
+        true
+    } else if exists {
+        match mode {
+            SaveMode::Overwrite | SaveMode::Truncate => {
+                info!("Overwriting existing file: {}", path.display());
+                false
+            }
+            SaveMode::Append => {
+                info!("Appending to existing file: {}", path.display());
+                true
+            }
+            SaveMode::ErrorIfExists => {
+                return Err(Error::Write(format!(
+                    "Target file {} already exists",
+                    path.display()
+                )));
+            }
+            SaveMode::Ignore => {
+                info!("Target file {} exists, skipping writes", path.display());
+                return Ok(None);
+            }
+        }
+    } else {
+        false
+    };
+
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)
+        .await
+        .map_err(error)?;
+    let empty = file.metadata().await.map_err(error)?.len() == 0;
+    Ok(Some(Output {
+        writer: BufWriter::new(file),
+        empty,
+    }))
+}
+
+impl SharedOutput {
+    pub(crate) async fn set(&self, output: Option<Output>) {
+        *self.0.lock().await = output;
+    }
+
+    // 写入一段内容并同步到磁盘, header 只在文件为空时写在它之前, 如 CSV 的表头
+    pub(crate) async fn write(&self, header: Option<&[u8]>, data: &[u8]) -> Result<()> {
+        let mut guard = self.0.lock().await;
+        let output = guard
+            .as_mut()
+            .ok_or_else(|| Error::Write("Output file is not open".into()))?;
+        if output.empty {
+            if let Some(header) = header {
+                output.writer.write_all(header).await?;
+                output.empty = false;
+            }
+        }
+        output.writer.write_all(data).await?;
+        output.empty &= data.is_empty();
+        output.writer.flush().await?;
+        output.writer.get_ref().sync_data().await?;
+        Ok(())
+    }
+
+    // 文件仍为空 (没有写入任何行) 时写入 header
+    pub(crate) async fn write_header(&self, header: &[u8]) -> Result<()> {
+        if let Some(output) = self.0.lock().await.as_mut() {
+            if output.empty {
+                output.writer.write_all(header).await?;
+                output.empty = false;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn flush(&self) -> Result<()> {
+        if let Some(output) = self.0.lock().await.as_mut() {
+            output.writer.flush().await?;
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn close(&self) -> Result<()> {
+        if let Some(mut output) = self.0.lock().await.take() {
+            output.writer.shutdown().await?;
+        }
+        Ok(())
+    }
+}
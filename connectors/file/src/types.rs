@@ -0,0 +1,602 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+use dbsync_core::{
+    error::{Error, Result},
+    types::{DbsyncType, DbsyncValue, SpatialType, TypeMapper, MICROS_PER_DAY, MICROS_PER_SECOND},
+};
+use serde_json::{Number, Value};
+
+// 配置中的列类型名, 与 MySQL 的类型名一致, 如 BIGINT, DECIMAL(10,2), DATETIME(3)
+#[derive(Clone)]
+pub struct FileTypeMapper;
+
+impl TypeMapper for FileTypeMapper {
+    fn to_dbsync_type(&self, source_type: &str) -> Result<DbsyncType> {
+        let invalid = || Error::Type(format!("Invalid column type: {}", source_type));
+        // 数组写作元素类型加 [], 如 BIGINT[]
+        if let Some(element) = source_type.trim().strip_suffix("[]") {
+            return Ok(DbsyncType::Array(Box::new(self.to_dbsync_type(element)?)));
+        }
+        let (name, params) = parse_type(source_type).ok_or_else(invalid)?;
+        let param = |index: usize, default: u32| -> Result<u32> {
+            params
+                .get(index)
+                .map_or(Ok(default), |p| p.parse().map_err(|_| invalid()))
+        };
+        let narrow = |value: u32| u8::try_from(value).map_err(|_| invalid());
+        // 秒的小数位数, 未指定时为 0, 与 MySQL 相同
+        let fsp = || -> Result<u8> {
+            match param(0, 0)? {
+                fsp @ 0..=6 => Ok(fsp as u8),
+                _ => Err(invalid()),
+            }
+        };
+
+        let dbsync_type = match name.as_str() {
+            "TINYINT" => DbsyncType::TinyInt,
+            "SMALLINT" => DbsyncType::SmallInt,
+            "MEDIUMINT" => DbsyncType::MediumInt,
+            "INT" | "INTEGER" => DbsyncType::Int,
+            "BIGINT" => DbsyncType::BigInt,
+            "TINYINT UNSIGNED" => DbsyncType::UnsignedTinyInt,
+            "SMALLINT UNSIGNED" => DbsyncType::UnsignedSmallInt,
+            "MEDIUMINT UNSIGNED" => DbsyncType::UnsignedMediumInt,
+            "INT UNSIGNED" | "INTEGER UNSIGNED" => DbsyncType::UnsignedInt,
+            "BIGINT UNSIGNED" => DbsyncType::UnsignedBigInt,
+            "BIT" => match param(0, 1)? {
+                bits @ 1..=64 => DbsyncType::Bit(bits as u8),
+                _ => return Err(invalid()),
+            },
+            "FLOAT" => DbsyncType::Float,
+            "DOUBLE" | "DOUBLE PRECISION" | "REAL" => DbsyncType::Double,
+            "DECIMAL" => DbsyncType::Decimal(narrow(param(0, 10)?)?, narrow(param(1, 0)?)?),
+            // 与 PostgreSQL 一致, 不带参数的 NUMERIC 不限制位数
+            "NUMERIC" if params.is_empty() => DbsyncType::Numeric(0, 0),
+            "NUMERIC" => DbsyncType::Numeric(narrow(param(0, 10)?)?, narrow(param(1, 0)?)?),
+            "CHAR" => DbsyncType::Char(param(0, 1)?),
+            "VARCHAR" => DbsyncType::VarChar(param(0, 255)?),
+            "TINYTEXT" => DbsyncType::TinyText,
+            "TEXT" => DbsyncType::Text,
+            "MEDIUMTEXT" => DbsyncType::MediumText,
+            "LONGTEXT" => DbsyncType::LongText,
+            "BOOL" | "BOOLEAN" => DbsyncType::Boolean,
+            "DATE" => DbsyncType::Date,
+            "TIME" => DbsyncType::Time(fsp()?),
+            "DATETIME" => DbsyncType::DateTime(fsp()?),
+            "TIMESTAMP" => DbsyncType::Timestamp(fsp()?),
+            "YEAR" => DbsyncType::Year,
+            "BINARY" => DbsyncType::Binary(param(0, 1)?),
+            "VARBINARY" => DbsyncType::VarBinary(param(0, 255)?),
+            "TINYBLOB" => DbsyncType::TinyBlob,
+            "BLOB" => DbsyncType::Blob,
+            "MEDIUMBLOB" => DbsyncType::MediumBlob,
+            "LONGBLOB" => DbsyncType::LongBlob,
+            "JSON" => DbsyncType::Json,
+            "UUID" => DbsyncType::Uuid,
+            "GEOMETRY" => DbsyncType::Spatial(SpatialType::Geometry),
+            "POINT" => DbsyncType::Spatial(SpatialType::Point),
+            "LINESTRING" => DbsyncType::Spatial(SpatialType::LineString),
+            "POLYGON" => DbsyncType::Spatial(SpatialType::Polygon),
+            "MULTIPOINT" => DbsyncType::Spatial(SpatialType::MultiPoint),
+            "MULTILINESTRING" => DbsyncType::Spatial(SpatialType::MultiLineString),
+            "MULTIPOLYGON" => DbsyncType::Spatial(SpatialType::MultiPolygon),
+            "GEOMETRYCOLLECTION" => DbsyncType::Spatial(SpatialType::GeometryCollection),
+            _ => return Err(invalid()),
+        };
+        Ok(dbsync_type)
+    }
+
+    fn to_target_type(&self, dbsync_type: &DbsyncType) -> Result<String> {
+        match dbsync_type {
+            DbsyncType::TinyInt => Ok("TINYINT".to_string()),
+            DbsyncType::SmallInt => Ok("SMALLINT".to_string()),
+            DbsyncType::MediumInt => Ok("MEDIUMINT".to_string()),
+            DbsyncType::Int => Ok("INT".to_string()),
+            DbsyncType::BigInt => Ok("BIGINT".to_string()),
+            DbsyncType::UnsignedTinyInt => Ok("TINYINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedSmallInt => Ok("SMALLINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedMediumInt => Ok("MEDIUMINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedInt => Ok("INT UNSIGNED".to_string()),
+            DbsyncType::UnsignedBigInt => Ok("BIGINT UNSIGNED".to_string()),
+            DbsyncType::Bit(n) => Ok(format!("BIT({})", n)),
+            DbsyncType::Float => Ok("FLOAT".to_string()),
+            DbsyncType::Double => Ok("DOUBLE".to_string()),
+            DbsyncType::Decimal(p, s) => Ok(format!("DECIMAL({},{})", p, s)),
+            t if t.is_unconstrained_numeric() => Ok("NUMERIC".to_string()),
+            DbsyncType::Numeric(p, s) => Ok(format!("NUMERIC({},{})", p, s)),
+            DbsyncType::Char(n) => Ok(format!("CHAR({})", n)),
+            DbsyncType::VarChar(n) => Ok(format!("VARCHAR({})", n)),
+            DbsyncType::TinyText => Ok("TINYTEXT".to_string()),
+            DbsyncType::MediumText => Ok("MEDIUMTEXT".to_string()),
+            DbsyncType::LongText => Ok("LONGTEXT".to_string()),
+            // 文件中只有成员的文本
+            DbsyncType::Text | DbsyncType::Enum(_) | DbsyncType::Set(_) => Ok("TEXT".to_string()),
+            DbsyncType::Date => Ok("DATE".to_string()),
+            DbsyncType::Time(fsp) => Ok(format!("TIME({})", fsp)),
+            DbsyncType::DateTime(fsp) => Ok(format!("DATETIME({})", fsp)),
+            DbsyncType::Timestamp(fsp) => Ok(format!("TIMESTAMP({})", fsp)),
+            DbsyncType::Year => Ok("YEAR".to_string()),
+            DbsyncType::Boolean => Ok("BOOLEAN".to_string()),
+            DbsyncType::Binary(n) => Ok(format!("BINARY({})", n)),
+            DbsyncType::VarBinary(n) => Ok(format!("VARBINARY({})", n)),
+            DbsyncType::TinyBlob => Ok("TINYBLOB".to_string()),
+            DbsyncType::Blob => Ok("BLOB".to_string()),
+            DbsyncType::MediumBlob => Ok("MEDIUMBLOB".to_string()),
+            DbsyncType::LongBlob => Ok("LONGBLOB".to_string()),
+            DbsyncType::Json => Ok("JSON".to_string()),
+            DbsyncType::Array(element) => Ok(format!("{}[]", self.to_target_type(element)?)),
+            DbsyncType::Uuid => Ok("UUID".to_string()),
+            DbsyncType::Spatial(spatial) => Ok(match spatial {
+                SpatialType::Geometry => "GEOMETRY",
+                SpatialType::Point => "POINT",
+                SpatialType::LineString => "LINESTRING",
+                SpatialType::Polygon => "POLYGON",
+                SpatialType::MultiPoint => "MULTIPOINT",
+                SpatialType::MultiLineString => "MULTILINESTRING",
+                SpatialType::MultiPolygon => "MULTIPOLYGON",
+                SpatialType::GeometryCollection => "GEOMETRYCOLLECTION",
+            }
+            .to_string()),
+            DbsyncType::Null => Err(Error::Type("No column type for NULL".to_string())),
+        }
+    }
+}
+
+// 把类型拆成大写的类型名和括号内的参数, 如 decimal( 10, 2 ) → ("DECIMAL", ["10", "2"])
+fn parse_type(source_type: &str) -> Option<(String, Vec<String>)> {
+    let source_type = source_type.trim().to_uppercase();
+    let (name, params) = match source_type.find('(') {
+        Some(open) => {
+            let close = open + source_type[open..].find(')')?;
+            let params = source_type[open + 1..close]
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .collect();
+            (
+                format!("{} {}", &source_type[..open], &source_type[close + 1..]),
+                params,
+            )
+        }
+        None => (source_type, Vec::new()),
+    };
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some((name, params))
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+// ISO 8601, 日期和时间之间用 T 分隔
+const DATETIME_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+fn days_to_date(days: i32) -> Option<NaiveDate> {
+    DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(TimeDelta::days(days.into()))
+}
+
+fn date_to_days(date: NaiveDate) -> i32 {
+    (date - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32
+}
+
+// 保留 fsp 位小数, 多余的位直接截断
+fn fraction(micros: u32, fsp: u8) -> String {
+    if fsp == 0 {
+        return String::new();
+    }
+    format!(".{:06}", micros)[..fsp as usize + 1].to_string()
+}
+
+// 列类型中秒的小数位数, 非时间类型的值按微秒输出
+fn type_fsp(data_type: &DbsyncType) -> u8 {
+    match data_type {
+        DbsyncType::Time(fsp) | DbsyncType::DateTime(fsp) | DbsyncType::Timestamp(fsp) => *fsp,
+        _ => 6,
+    }
+}
+
+fn format_datetime(micros: i64, fsp: u8) -> Option<String> {
+    let datetime = DateTime::from_timestamp_micros(micros)?.naive_utc();
+    Some(format!(
+        "{}{}",
+        datetime.format(DATETIME_FORMAT),
+        fraction(datetime.nanosecond() / 1000, fsp)
+    ))
+}
+
+// [-]HH:MM:SS[.ffffff], 小时数可以超过 24, 与 MySQL TIME 的取值范围一致
+fn format_time(micros: i64, fsp: u8) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.unsigned_abs();
+    let seconds = micros / MICROS_PER_SECOND as u64;
+    format!(
+        "{}{:02}:{:02}:{:02}{}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        fraction((micros % MICROS_PER_SECOND as u64) as u32, fsp)
+    )
+}
+
+// 小数部分的位数, 超过 6 位 (微秒) 时为 None
+fn fraction_digits(fraction: &str) -> Option<u8> {
+    (!fraction.is_empty() && fraction.len() <= 6 && fraction.bytes().all(|b| b.is_ascii_digit()))
+        .then_some(fraction.len() as u8)
+}
+
+// [-]HH:MM[:SS[.ffffff]], 返回微秒数和小数位数
+pub(crate) fn parse_time(text: &str) -> Option<(i64, u8)> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (clock, fraction) = match text.split_once('.') {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (text, None),
+    };
+    let fsp = match fraction {
+        Some(fraction) => fraction_digits(fraction)?,
+        None => 0,
+    };
+    let mut parts = clock.split(':').map(|part| {
+        (!part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()))
+            .then(|| part.parse::<i64>().ok())
+            .flatten()
+    });
+    let hours = parts.next()??;
+    let minutes = parts.next()??;
+    let seconds = parts.next().unwrap_or(Some(0))?;
+    if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    let fraction_micros = match fraction {
+        Some(fraction) => format!("{:0<6}", fraction).parse::<i64>().ok()?,
+        None => 0,
+    };
+    let micros = ((hours * 60 + minutes) * 60 + seconds) * MICROS_PER_SECOND + fraction_micros;
+    Some((if negative { -micros } else { micros }, fsp))
+}
+
+pub(crate) fn parse_date(text: &str) -> Option<i32> {
+    NaiveDate::parse_from_str(text, DATE_FORMAT)
+        .ok()
+        .map(date_to_days)
+}
+
+// 解析出的日期时间, 带时区的时刻已转换为 UTC
+pub(crate) struct ParsedDateTime {
+    pub micros: i64,
+    pub fsp: u8,
+    // 文本带有时区, 如 Z 或 +08:00
+    pub zoned: bool,
+}
+
+// ISO 8601 日期时间, 日期和时间之间可以用 T 或空格分隔
+pub(crate) fn parse_datetime(text: &str) -> Option<ParsedDateTime> {
+    let fsp = match text.split_once('.') {
+        Some((_, rest)) => {
+            let digits = rest.bytes().take_while(|b| b.is_ascii_digit()).count();
+            fraction_digits(&rest[..digits])?
+        }
+        None => 0,
+    };
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(ParsedDateTime {
+            micros: datetime.timestamp_micros(),
+            fsp,
+            zoned: true,
+        });
+    }
+    ["%Y-%m-%dT%H:%M:%S%.f", "%Y-%m-%d %H:%M:%S%.f"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+        .map(|datetime| ParsedDateTime {
+            micros: datetime.and_utc().timestamp_micros(),
+            fsp,
+            zoned: false,
+        })
+}
+
+// 不带指数的十进制数, 返回整数部分和小数部分的位数
+pub(crate) fn decimal_digits(text: &str) -> Option<(usize, usize)> {
+    let unsigned = text.strip_prefix(['-', '+']).unwrap_or(text);
+    let (integer, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if integer.is_empty() || !digits(integer) || !digits(fraction) || unsigned.ends_with('.') {
+        return None;
+    }
+    Some((integer.trim_start_matches('0').len(), fraction.len()))
+}
+
+pub(crate) fn parse_bool(text: &str) -> Option<bool> {
+    match text.to_ascii_lowercase().as_str() {
+        "true" | "t" | "yes" | "y" | "1" => Some(true),
+        "false" | "f" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+// 值的文本形式: 日期时间为 ISO 8601, TIMESTAMP 带 Z 后缀, 二进制为 base64
+pub(crate) fn format_value(value: &DbsyncValue, data_type: &DbsyncType) -> Result<String> {
+    let invalid = || Error::Type(format!("Invalid {:?} value: {:?}", data_type, value));
+    let text = match value {
+        DbsyncValue::Null => String::new(),
+        DbsyncValue::Integer(i) => i.to_string(),
+        DbsyncValue::UInteger(u) => u.to_string(),
+        DbsyncValue::Float(f) => f.to_string(),
+        DbsyncValue::Decimal(d) => d.clone(),
+        DbsyncValue::String(s) => s.clone(),
+        DbsyncValue::Boolean(b) => b.to_string(),
+        DbsyncValue::Date(days) => days_to_date(*days)
+            .ok_or_else(invalid)?
+            .format(DATE_FORMAT)
+            .to_string(),
+        DbsyncValue::Time(micros) => format_time(*micros, type_fsp(data_type)),
+        DbsyncValue::DateTime(micros) => {
+            format_datetime(*micros, type_fsp(data_type)).ok_or_else(invalid)?
+        }
+        DbsyncValue::Timestamp(micros) => {
+            format_datetime(*micros, type_fsp(data_type)).ok_or_else(invalid)? + "Z"
+        }
+        DbsyncValue::Binary(bytes) => STANDARD.encode(bytes),
+        DbsyncValue::Json(json) => json.to_string(),
+    };
+    Ok(text)
+}
+
+// 按列类型解析文本, 是 format_value 的逆过程
+pub(crate) fn parse_value(text: &str, data_type: &DbsyncType) -> Result<DbsyncValue> {
+    let invalid = || Error::Type(format!("Invalid {:?} value: {:?}", data_type, text));
+    let value = match data_type {
+        DbsyncType::Null => DbsyncValue::Null,
+        t if t.is_signed_integer() || *t == DbsyncType::Year => {
+            DbsyncValue::Integer(text.trim().parse().map_err(|_| invalid())?)
+        }
+        DbsyncType::Bit(_) => DbsyncValue::UInteger(text.trim().parse().map_err(|_| invalid())?),
+        t if t.is_unsigned_integer() => {
+            let u: u64 = text.trim().parse().map_err(|_| invalid())?;
+            match i64::try_from(u) {
+                Ok(i) => DbsyncValue::Integer(i),
+                Err(_) => DbsyncValue::UInteger(u),
+            }
+        }
+        DbsyncType::Float | DbsyncType::Double => {
+            DbsyncValue::Float(text.trim().parse().map_err(|_| invalid())?)
+        }
+        DbsyncType::Decimal(..) | DbsyncType::Numeric(..) => {
+            let text = text.trim();
+            decimal_digits(text).ok_or_else(invalid)?;
+            DbsyncValue::Decimal(text.to_string())
+        }
+        t if t.is_text() || *t == DbsyncType::Uuid => DbsyncValue::String(text.to_string()),
+        DbsyncType::Boolean => DbsyncValue::Boolean(parse_bool(text.trim()).ok_or_else(invalid)?),
+        DbsyncType::Date => DbsyncValue::Date(parse_date(text.trim()).ok_or_else(invalid)?),
+        DbsyncType::Time(_) => DbsyncValue::Time(parse_time(text.trim()).ok_or_else(invalid)?.0),
+        // 没有时区的 TIMESTAMP 按 UTC 解释
+        DbsyncType::DateTime(_) | DbsyncType::Timestamp(_) => {
+            let micros = match parse_datetime(text.trim()) {
+                Some(datetime) => datetime.micros,
+                None => {
+                    let days = parse_date(text.trim()).ok_or_else(invalid)?;
+                    i64::from(days) * MICROS_PER_DAY
+                }
+            };
+            match data_type {
+                DbsyncType::DateTime(_) => DbsyncValue::DateTime(micros),
+                _ => DbsyncValue::Timestamp(micros),
+            }
+        }
+        t if t.is_binary() => DbsyncValue::Binary(STANDARD.decode(text).map_err(|_| invalid())?),
+        DbsyncType::Json | DbsyncType::Array(_) => {
+            DbsyncValue::Json(serde_json::from_str(text).map_err(|_| invalid())?)
+        }
+        _ => return Err(invalid()),
+    };
+    Ok(value)
+}
+
+// 值的 JSON 形式: 整数, 浮点数和布尔值为 JSON 原生类型, JSON 文档原样嵌入,
+// 其余为 format_value 的文本. DECIMAL 写成字符串, 不会因 f64 丢失精度
+pub(crate) fn to_json_value(value: &DbsyncValue, data_type: &DbsyncType) -> Result<Value> {
+    let json = match value {
+        DbsyncValue::Null => Value::Null,
+        DbsyncValue::Integer(i) => Value::from(*i),
+        DbsyncValue::UInteger(u) => Value::from(*u),
+        // NaN 和无穷大没有 JSON 数字表示, 写为 "NaN", "inf" 和 "-inf", 读回时按文本解析
+        DbsyncValue::Float(f) => match Number::from_f64(*f) {
+            Some(n) => Value::Number(n),
+            None => Value::String(f.to_string()),
+        },
+        DbsyncValue::Boolean(b) => Value::Bool(*b),
+        DbsyncValue::Json(json) => json.clone(),
+        _ => Value::String(format_value(value, data_type)?),
+    };
+    Ok(json)
+}
+
+// 按列类型转换 JSON 值, 是 to_json_value 的逆过程
+pub(crate) fn from_json_value(json: &Value, data_type: &DbsyncType) -> Result<DbsyncValue> {
+    match (json, data_type) {
+        (Value::Null, _) => Ok(DbsyncValue::Null),
+        (_, DbsyncType::Json | DbsyncType::Array(_)) => Ok(DbsyncValue::Json(json.clone())),
+        (Value::String(text), _) => parse_value(text, data_type),
+        (Value::Bool(b), DbsyncType::Boolean) => Ok(DbsyncValue::Boolean(*b)),
+        _ => parse_value(&json.to_string(), data_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_type_names_round_trip() {
+        let mapper = FileTypeMapper;
+        assert_eq!(
+            mapper.to_dbsync_type("decimal( 10, 2 )").unwrap(),
+            DbsyncType::Decimal(10, 2)
+        );
+        assert_eq!(
+            mapper.to_dbsync_type("int unsigned").unwrap(),
+            DbsyncType::UnsignedInt
+        );
+        assert_eq!(
+            mapper.to_dbsync_type("DATETIME").unwrap(),
+            DbsyncType::DateTime(0)
+        );
+        assert!(mapper.to_dbsync_type("TIME(7)").is_err());
+        assert!(mapper.to_dbsync_type("STRING").is_err());
+
+        for dbsync_type in [
+            DbsyncType::BigInt,
+            DbsyncType::UnsignedBigInt,
+            DbsyncType::Bit(3),
+            DbsyncType::Numeric(20, 4),
+            DbsyncType::Numeric(0, 0),
+            DbsyncType::VarChar(64),
+            DbsyncType::Timestamp(6),
+            DbsyncType::VarBinary(16),
+            DbsyncType::Array(Box::new(DbsyncType::Int)),
+            DbsyncType::Spatial(SpatialType::Point),
+        ] {
+            let name = mapper.to_target_type(&dbsync_type).unwrap();
+            assert_eq!(mapper.to_dbsync_type(&name).unwrap(), dbsync_type);
+        }
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let cases = [
+            (DbsyncValue::Integer(-42), DbsyncType::BigInt, "-42"),
+            (
+                DbsyncValue::UInteger(u64::MAX),
+                DbsyncType::UnsignedBigInt,
+                "18446744073709551615",
+            ),
+            (DbsyncValue::Float(1.5), DbsyncType::Double, "1.5"),
+            (
+                DbsyncValue::Decimal("-123.4500".into()),
+                DbsyncType::Decimal(10, 4),
+                "-123.4500",
+            ),
+            (
+                DbsyncValue::String(" a,b ".into()),
+                DbsyncType::Text,
+                " a,b ",
+            ),
+            (DbsyncValue::Boolean(true), DbsyncType::Boolean, "true"),
+            (DbsyncValue::Date(19724), DbsyncType::Date, "2024-01-02"),
+            (
+                DbsyncValue::Time(-(3600 + 1) * MICROS_PER_SECOND - 500_000),
+                DbsyncType::Time(1),
+                "-01:00:01.5",
+            ),
+            (
+                DbsyncValue::DateTime(1_704_164_645_123_456),
+                DbsyncType::DateTime(6),
+                "2024-01-02T03:04:05.123456",
+            ),
+            (
+                DbsyncValue::Timestamp(1_704_164_645_000_000),
+                DbsyncType::Timestamp(0),
+                "2024-01-02T03:04:05Z",
+            ),
+            (
+                DbsyncValue::Binary(vec![0, 1, 254, 255]),
+                DbsyncType::Blob,
+                "AAH+/w==",
+            ),
+            (
+                DbsyncValue::Json(serde_json::json!({"a": [1, null]})),
+                DbsyncType::Json,
+                r#"{"a":[1,null]}"#,
+            ),
+        ];
+        for (value, data_type, text) in cases {
+            assert_eq!(format_value(&value, &data_type).unwrap(), text);
+            assert_eq!(parse_value(text, &data_type).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_parse_lenient_text() {
+        assert_eq!(
+            parse_value("2024-01-02 11:04:05+08:00", &DbsyncType::Timestamp(0)).unwrap(),
+            DbsyncValue::Timestamp(1_704_164_645_000_000)
+        );
+        assert_eq!(
+            parse_value("2024-01-02", &DbsyncType::DateTime(0)).unwrap(),
+            DbsyncValue::DateTime(1_704_153_600_000_000)
+        );
+        assert_eq!(
+            parse_value("Yes", &DbsyncType::Boolean).unwrap(),
+            DbsyncValue::Boolean(true)
+        );
+        assert!(parse_value("1e3", &DbsyncType::Decimal(10, 0)).is_err());
+        assert!(parse_value("12:60", &DbsyncType::Time(0)).is_err());
+        assert_eq!(decimal_digits("-007.50"), Some((1, 2)));
+        assert_eq!(decimal_digits("1."), None);
+    }
+
+    #[test]
+    fn test_json_value_round_trip() {
+        let cases = [
+            (DbsyncValue::Integer(-42), DbsyncType::BigInt, json!(-42)),
+            (
+                DbsyncValue::UInteger(u64::MAX),
+                DbsyncType::UnsignedBigInt,
+                json!(u64::MAX),
+            ),
+            (
+                DbsyncValue::Decimal("12345678901234567890.12".into()),
+                DbsyncType::Decimal(22, 2),
+                json!("12345678901234567890.12"),
+            ),
+            (
+                DbsyncValue::Boolean(false),
+                DbsyncType::Boolean,
+                json!(false),
+            ),
+            (
+                DbsyncValue::Timestamp(1_704_164_645_000_000),
+                DbsyncType::Timestamp(0),
+                json!("2024-01-02T03:04:05Z"),
+            ),
+            (
+                DbsyncValue::Binary(vec![0, 1, 254, 255]),
+                DbsyncType::VarBinary(4),
+                json!("AAH+/w=="),
+            ),
+            (
+                DbsyncValue::Json(json!({"a": [1, null]})),
+                DbsyncType::Json,
+                json!({"a": [1, null]}),
+            ),
+            (DbsyncValue::Null, DbsyncType::Text, Value::Null),
+        ];
+        for (value, data_type, json) in cases {
+            assert_eq!(to_json_value(&value, &data_type).unwrap(), json);
+            assert_eq!(from_json_value(&json, &data_type).unwrap(), value);
+        }
+
+        for (f, text) in [(f64::INFINITY, "inf"), (f64::NEG_INFINITY, "-inf")] {
+            let json = to_json_value(&DbsyncValue::Float(f), &DbsyncType::Double).unwrap();
+            assert_eq!(json, json!(text));
+            assert_eq!(
+                from_json_value(&json, &DbsyncType::Double).unwrap(),
+                DbsyncValue::Float(f)
+            );
+        }
+        let nan = to_json_value(&DbsyncValue::Float(f64::NAN), &DbsyncType::Double).unwrap();
+        assert_eq!(nan, json!("NaN"));
+        assert!(matches!(
+            from_json_value(&nan, &DbsyncType::Double).unwrap(),
+            DbsyncValue::Float(f) if f.is_nan()
+        ));
+        assert_eq!(
+            from_json_value(&json!(12.5), &DbsyncType::Decimal(4, 1)).unwrap(),
+            DbsyncValue::Decimal("12.5".into())
+        );
+        assert!(from_json_value(&json!([1]), &DbsyncType::BigInt).is_err());
+    }
+}
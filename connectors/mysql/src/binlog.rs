@@ -0,0 +1,748 @@
+use crate::config::{connect_options, MySQLBinlogConfig};
+use crate::schema::{column_type, is_nullable, load_table_schema, COLUMN_TYPE_FIELDS};
+use crate::type_converter::{date_to_days, datetime_to_micros, int_to_boolean};
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchField, BatchSchema, ConnectorConfig, Context, DataBatch, Operation, Record, Source,
+        StreamBatch, StreamingSource,
+    },
+    error::{Error, Result},
+    types::{DbsyncType, DbsyncValue, MICROS_PER_SECOND},
+};
+use futures::StreamExt;
+use mysql_async::binlog::events::{Event, EventData, RowsEventData};
+use mysql_async::binlog::row::BinlogRow;
+use mysql_async::binlog::value::BinlogValue;
+use mysql_async::{BinlogStream, BinlogStreamRequest, Conn, Opts, Value as MySqlValue};
+use serde::{Deserialize, Serialize};
+use sqlx::mysql::MySqlPool;
+use sqlx::Row;
+use std::any::Any;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::info;
+
+// binlog 中的读取位置, 作为 checkpoint 的 position 保存
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BinlogPosition {
+    pub file: String,
+    pub position: u64,
+    // 最后一个已提交事务的 GTID, 仅用于排查, 恢复时按文件和位置定位
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gtid: Option<String>,
+}
+
+// 源表的一列, 按 ORDINAL_POSITION 排列, 与 binlog 行镜像中的值一一对应
+#[derive(Clone, Debug)]
+struct BinlogColumn {
+    name: String,
+    data_type: DbsyncType,
+    nullable: bool,
+    // UNSIGNED 整数列的位宽, binlog 中按有符号数记录, 需按位宽还原
+    unsigned_bits: Option<u32>,
+}
+
+pub struct MySQLBinlogSource {
+    config: MySQLBinlogConfig,
+    pool: Option<MySqlPool>,
+    database: String,
+    columns: Vec<BinlogColumn>,
+    // 输出批次共享的 schema, 与 columns 一一对应
+    schema: Arc<BatchSchema>,
+    stream: Option<BinlogStream>,
+    // 流已读到末尾 (stop_at_end)
+    finished: bool,
+    // 当前所在的 binlog 文件, 随 rotate 事件更新
+    file: String,
+    gtid: Option<String>,
+    // 最后一个已提交事务结束的位置, 即已读出的行对应的恢复点
+    committed: BinlogPosition,
+    // 已提交但尚未输出的行
+    rows: Vec<Record>,
+    // 当前事务中的行, 事务提交后才能输出
+    tx_rows: Vec<Record>,
+}
+
+impl MySQLBinlogSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        Ok(Self {
+            config: MySQLBinlogConfig::from_json(serde_json::Value::Object(
+                serde_json::Map::from_iter(config.properties),
+            ))?,
+            pool: None,
+            database: String::new(),
+            columns: Vec::new(),
+            schema: Arc::default(),
+            stream: None,
+            finished: false,
+            file: String::new(),
+            gtid: None,
+            committed: BinlogPosition::default(),
+            rows: Vec::new(),
+            tx_rows: Vec::new(),
+        })
+    }
+
+    fn pool(&self) -> Result<&MySqlPool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    // 只有 ROW 格式且记录完整行镜像的 binlog 才能还原出整行数据
+    async fn check_binlog_format(&self, pool: &MySqlPool) -> Result<()> {
+        let row = sqlx::query(
+            "SELECT CAST(@@GLOBAL.binlog_format AS CHAR), CAST(@@GLOBAL.binlog_row_image AS CHAR)",
+        )
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+
+        let format: String = row.get(0);
+        let row_image: String = row.get(1);
+        if !format.eq_ignore_ascii_case("ROW") || !row_image.eq_ignore_ascii_case("FULL") {
+            return Err(Error::Config(format!(
+                "Binlog CDC requires binlog_format=ROW and binlog_row_image=FULL, server has {} and {}",
+                format, row_image
+            )));
+        }
+        Ok(())
+    }
+
+    async fn get_columns(&self, pool: &MySqlPool) -> Result<Vec<BinlogColumn>> {
+        let query = format!(
+            r#"
+            SELECT
+                COLUMN_NAME,
+                CAST(IS_NULLABLE AS CHAR) AS IS_NULLABLE,
+                {}
+            FROM INFORMATION_SCHEMA.COLUMNS
+            WHERE TABLE_SCHEMA = DATABASE()
+            AND TABLE_NAME = ?
+            ORDER BY ORDINAL_POSITION
+            "#,
+            COLUMN_TYPE_FIELDS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(&self.config.table)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let data_type = column_type(row, self.config.tinyint1_as_boolean)?;
+                Ok(BinlogColumn {
+                    name: row.get("COLUMN_NAME"),
+                    nullable: is_nullable(row),
+                    unsigned_bits: unsigned_bits(&data_type),
+                    data_type,
+                })
+            })
+            .collect()
+    }
+
+    // 服务器当前的 binlog 位置, MySQL 8.4 起 SHOW MASTER STATUS 改名为 SHOW BINARY LOG STATUS
+    async fn current_position(&self) -> Result<BinlogPosition> {
+        let pool = self.pool()?;
+        let row = match sqlx::query("SHOW MASTER STATUS").fetch_optional(pool).await {
+            Ok(row) => row,
+            Err(_) => sqlx::query("SHOW BINARY LOG STATUS")
+                .fetch_optional(pool)
+                .await
+                .map_err(|e| Error::Read(e.to_string()))?,
+        };
+        let row = row.ok_or_else(|| {
+            Error::Config("Binary logging is disabled on the source server".into())
+        })?;
+
+        Ok(BinlogPosition {
+            file: row.try_get(0).map_err(|e| Error::Read(e.to_string()))?,
+            position: row.try_get(1).map_err(|e| Error::Read(e.to_string()))?,
+            gtid: None,
+        })
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<()> {
+        let log_pos = u64::from(event.header().log_pos());
+        let Some(data) = event.read_data().map_err(|e| Error::Read(e.to_string()))? else {
+            return Ok(());
+        };
+
+        match data {
+            EventData::RotateEvent(rotate) => self.file = rotate.name().into_owned(),
+            EventData::GtidEvent(gtid) => {
+                self.gtid = Some(format!("{}:{}", format_sid(&gtid.sid()), gtid.gno()))
+            }
+            EventData::RowsEvent(rows) => {
+                let records = self.decode_rows(&rows, log_pos)?;
+                self.tx_rows.extend(records);
+            }
+            EventData::XidEvent(_) => self.commit(log_pos),
+            // 非事务引擎的语句以 COMMIT 查询事件结束
+            EventData::QueryEvent(query) if query.query() == "COMMIT" => self.commit(log_pos),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn commit(&mut self, log_pos: u64) {
+        self.rows.append(&mut self.tx_rows);
+        self.committed = BinlogPosition {
+            file: self.file.clone(),
+            position: log_pos,
+            gtid: self.gtid.clone(),
+        };
+    }
+
+    // 解析一个行事件中属于源表的行
+    fn decode_rows(&self, rows: &RowsEventData, log_pos: u64) -> Result<Vec<Record>> {
+        let stream = self
+            .stream
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Binlog stream not started".into()))?;
+        let tme = stream.get_tme(rows.table_id()).ok_or_else(|| {
+            Error::Read(format!(
+                "Missing table map for table id {}",
+                rows.table_id()
+            ))
+        })?;
+        if tme.database_name() != self.database || tme.table_name() != self.config.table {
+            return Ok(Vec::new());
+        }
+        if tme.columns_count() != self.columns.len() as u64 {
+            return Err(Error::Read(format!(
+                "Table {} has {} columns in the binlog but {} when the job started, restart the job after schema changes",
+                self.config.table,
+                tme.columns_count(),
+                self.columns.len()
+            )));
+        }
+
+        let position = serde_json::to_value(BinlogPosition {
+            file: self.file.clone(),
+            position: log_pos,
+            gtid: self.gtid.clone(),
+        })?;
+
+        rows.rows(tme)
+            .map(|row| {
+                let record = match row.map_err(|e| Error::Read(e.to_string()))? {
+                    (None, Some(after)) => Record::new(binlog_values(after, &self.columns)?),
+                    (Some(before), Some(after)) => {
+                        Record::new(binlog_values(after, &self.columns)?)
+                            .with_op(Operation::Update)
+                            .with_before(binlog_values(before, &self.columns)?)
+                    }
+                    (Some(before), None) => Record::new(binlog_values(before, &self.columns)?)
+                        .with_op(Operation::Delete),
+                    (None, None) => {
+                        return Err(Error::Read("Binlog row event without row images".into()))
+                    }
+                };
+                Ok(record.with_position(position.clone()))
+            })
+            .collect()
+    }
+
+    // 输出所有已提交的行, 没有时返回 None
+    fn take_batch(&mut self) -> Result<Option<StreamBatch>> {
+        if self.rows.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some(StreamBatch {
+            batch: DataBatch::new(self.schema.clone(), std::mem::take(&mut self.rows)),
+            position: serde_json::to_value(&self.committed)?,
+        }))
+    }
+}
+
+impl Clone for MySQLBinlogSource {
+    // 复制连接无法共享, 克隆出的 source 需要重新 start
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            pool: self.pool.clone(),
+            database: self.database.clone(),
+            columns: self.columns.clone(),
+            schema: self.schema.clone(),
+            stream: None,
+            finished: false,
+            file: String::new(),
+            gtid: None,
+            committed: BinlogPosition::default(),
+            rows: Vec::new(),
+            tx_rows: Vec::new(),
+        }
+    }
+}
+
+// UNSIGNED 整数列的位宽
+fn unsigned_bits(data_type: &DbsyncType) -> Option<u32> {
+    match data_type {
+        DbsyncType::UnsignedTinyInt => Some(8),
+        DbsyncType::UnsignedSmallInt => Some(16),
+        DbsyncType::UnsignedMediumInt => Some(24),
+        DbsyncType::UnsignedInt => Some(32),
+        DbsyncType::UnsignedBigInt => Some(64),
+        _ => None,
+    }
+}
+
+// GTID 的 server uuid 部分
+fn format_sid(sid: &[u8; 16]) -> String {
+    let hex = hex::encode(sid);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+// "秒[.微秒]" 转换为微秒
+fn parse_timestamp(text: &str) -> Option<i64> {
+    let (seconds, micros) = text.split_once('.').unwrap_or((text, "0"));
+    let seconds: i64 = seconds.parse().ok()?;
+    let micros: i64 = format!("{:0<6}", micros).parse().ok()?;
+    seconds.checked_mul(MICROS_PER_SECOND)?.checked_add(micros)
+}
+
+fn binlog_values(row: BinlogRow, columns: &[BinlogColumn]) -> Result<Vec<DbsyncValue>> {
+    if row.len() != columns.len() {
+        return Err(Error::Read(format!(
+            "Binlog row has {} of {} columns, binlog_row_image must be FULL",
+            row.len(),
+            columns.len()
+        )));
+    }
+
+    row.unwrap()
+        .into_iter()
+        .zip(columns)
+        .map(|(value, column)| binlog_value(value, column))
+        .collect()
+}
+
+fn binlog_value(value: BinlogValue, column: &BinlogColumn) -> Result<DbsyncValue> {
+    let value = match value {
+        BinlogValue::Value(value) => value,
+        BinlogValue::Jsonb(json) => {
+            return serde_json::Value::try_from(json)
+                .map(DbsyncValue::Json)
+                .map_err(|e| Error::Type(e.to_string()));
+        }
+        BinlogValue::JsonDiff(_) => {
+            return Err(Error::Type(format!(
+                "Column {} has a partial JSON update, set binlog_row_value_options to ''",
+                column.name
+            )))
+        }
+    };
+
+    match value {
+        MySqlValue::NULL => Ok(DbsyncValue::Null),
+        MySqlValue::Int(i) => Ok(match (&column.data_type, column.unsigned_bits) {
+            (DbsyncType::Boolean, _) => DbsyncValue::Boolean(int_to_boolean(i, &column.name)?),
+            // 旧格式的 TIMESTAMP 为 UTC 秒数
+            (DbsyncType::Timestamp(_), _) => DbsyncValue::Timestamp(i * MICROS_PER_SECOND),
+            // ENUM 记录的是从 1 开始的成员序号, 0 表示插入了非法值后的空字符串
+            (DbsyncType::Enum(members), _) => DbsyncValue::String(
+                usize::try_from(i - 1)
+                    .ok()
+                    .and_then(|index| members.get(index))
+                    .cloned()
+                    .unwrap_or_default(),
+            ),
+            (_, Some(bits)) => DbsyncValue::UInteger(if bits == 64 {
+                i as u64
+            } else {
+                (i as u64) & ((1u64 << bits) - 1)
+            }),
+            (_, None) => DbsyncValue::Integer(i),
+        }),
+        MySqlValue::UInt(u) => Ok(DbsyncValue::UInteger(u)),
+        MySqlValue::Float(f) => Ok(DbsyncValue::Float(f as f64)),
+        MySqlValue::Double(d) => Ok(DbsyncValue::Float(d)),
+        MySqlValue::Date(year, month, day, hour, minute, second, micros) => {
+            let date = chrono::NaiveDate::from_ymd_opt(year.into(), month.into(), day.into())
+                .ok_or_else(|| {
+                    Error::Type(format!(
+                        "Invalid date {:04}-{:02}-{:02} in column {}",
+                        year, month, day, column.name
+                    ))
+                })?;
+            if column.data_type == DbsyncType::Date {
+                return Ok(DbsyncValue::Date(date_to_days(date)));
+            }
+            date.and_hms_micro_opt(hour.into(), minute.into(), second.into(), micros)
+                .map(|dt| DbsyncValue::DateTime(datetime_to_micros(dt)))
+                .ok_or_else(|| {
+                    Error::Type(format!(
+                        "Invalid time {:02}:{:02}:{:02} in column {}",
+                        hour, minute, second, column.name
+                    ))
+                })
+        }
+        MySqlValue::Time(negative, days, hours, minutes, seconds, micros) => {
+            let total = ((i64::from(days) * 24 + i64::from(hours)) * 3600
+                + i64::from(minutes) * 60
+                + i64::from(seconds))
+                * MICROS_PER_SECOND
+                + i64::from(micros);
+            Ok(DbsyncValue::Time(if negative { -total } else { total }))
+        }
+        MySqlValue::Bytes(bytes) => match &column.data_type {
+            // TIMESTAMP2 以 "秒[.微秒]" 的文本形式出现, 微秒固定 6 位, 与时区无关
+            DbsyncType::Timestamp(_) => {
+                let text = String::from_utf8_lossy(&bytes);
+                parse_timestamp(&text)
+                    .map(DbsyncValue::Timestamp)
+                    .ok_or_else(|| {
+                        Error::Type(format!(
+                            "Invalid timestamp {} in column {}",
+                            text, column.name
+                        ))
+                    })
+            }
+            DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _) => String::from_utf8(bytes)
+                .map(DbsyncValue::Decimal)
+                .map_err(|e| Error::Type(e.to_string())),
+            // BIT 为大端字节
+            DbsyncType::Bit(_) => Ok(DbsyncValue::UInteger(
+                bytes.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)),
+            )),
+            // SET 为小端位图, 第 n 位对应第 n 个成员
+            DbsyncType::Set(members) => Ok(DbsyncValue::String(
+                members
+                    .iter()
+                    .enumerate()
+                    .filter(|(n, _)| bytes.get(n / 8).is_some_and(|b| b & (1 << (n % 8)) != 0))
+                    .map(|(_, member)| member.as_str())
+                    .collect::<Vec<_>>()
+                    .join(","),
+            )),
+            // YEAR 以 1900 + 存储值的文本出现, 存储值 0 表示 0000 年
+            DbsyncType::Year => {
+                let text = String::from_utf8_lossy(&bytes);
+                let year: i64 = text.parse().map_err(|_| {
+                    Error::Type(format!("Invalid year {} in column {}", text, column.name))
+                })?;
+                Ok(DbsyncValue::Integer(if year == 1900 { 0 } else { year }))
+            }
+            t if t.is_binary() => Ok(DbsyncValue::Binary(bytes)),
+            _ => String::from_utf8(bytes)
+                .map(DbsyncValue::String)
+                .map_err(|e| Error::Type(e.to_string())),
+        },
+    }
+}
+
+#[async_trait]
+impl Source for MySQLBinlogSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let url = &self.config.url;
+        info!("Connecting to MySQL binlog source: {}", url);
+
+        let pool = MySqlPool::connect_with(connect_options(url, &self.config.time_zone)?)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect to MySQL source: {}", e)))?;
+
+        self.check_binlog_format(&pool).await?;
+
+        let database: Option<String> = sqlx::query_scalar("SELECT DATABASE()")
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+        self.database = database.ok_or_else(|| {
+            Error::Config("MySQL binlog source URL must include a database".into())
+        })?;
+
+        self.columns = self.get_columns(&pool).await?;
+        if self.columns.is_empty() {
+            return Err(Error::Config(format!(
+                "Table {}.{} does not exist",
+                self.database, self.config.table
+            )));
+        }
+
+        self.schema = Arc::new(BatchSchema::new(
+            self.columns
+                .iter()
+                .map(|column| {
+                    BatchField::new(&column.name, column.data_type.clone())
+                        .with_nullable(column.nullable)
+                })
+                .collect(),
+        ));
+
+        ctx.set_schema(
+            load_table_schema(&pool, &self.config.table, self.config.tinyint1_as_boolean).await?,
+        );
+
+        info!("Successfully connected to MySQL binlog source");
+        self.pool = Some(pool);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        if self.stream.is_none() && !self.finished {
+            self.start(None).await?;
+        }
+        Ok(self
+            .read_stream_batch(batch_size)
+            .await?
+            .map(|stream_batch| stream_batch.batch))
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(stream) = self.stream.take() {
+            info!("Closing MySQL binlog stream");
+            stream
+                .close()
+                .await
+                .map_err(|e| Error::Connection(e.to_string()))?;
+        }
+        if let Some(pool) = self.pool.take() {
+            pool.close().await;
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_streaming(&mut self) -> Option<&mut dyn StreamingSource> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl StreamingSource for MySQLBinlogSource {
+    async fn start(&mut self, position: Option<serde_json::Value>) -> Result<()> {
+        let position = match (position, &self.config.binlog_file) {
+            (Some(position), _) => serde_json::from_value(position)
+                .map_err(|e| Error::Config(format!("Invalid binlog checkpoint: {}", e)))?,
+            (None, Some(file)) => BinlogPosition {
+                file: file.clone(),
+                position: self.config.binlog_position.unwrap_or(4),
+                gtid: None,
+            },
+            (None, None) => self.current_position().await?,
+        };
+        info!(
+            "Starting binlog stream of {}.{} at {}:{}",
+            self.database, self.config.table, position.file, position.position
+        );
+
+        let opts = Opts::from_url(&self.config.url).map_err(|e| Error::Config(e.to_string()))?;
+        let conn = Conn::new(opts)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect to MySQL source: {}", e)))?;
+
+        let mut request = BinlogStreamRequest::new(self.config.server_id)
+            .with_filename(position.file.as_bytes())
+            .with_pos(position.position);
+        if self.config.stop_at_end {
+            request = request.with_non_blocking();
+        }
+        let stream = conn
+            .get_binlog_stream(request)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to start binlog stream: {}", e)))?;
+
+        self.stream = Some(stream);
+        self.finished = false;
+        self.file = position.file.clone();
+        self.gtid = position.gtid.clone();
+        self.committed = position;
+        self.rows.clear();
+        self.tx_rows.clear();
+        Ok(())
+    }
+
+    // 只在事务边界切分批次, 保证 position 之前的事务都已完整输出
+    async fn read_stream_batch(&mut self, batch_size: usize) -> Result<Option<StreamBatch>> {
+        let flush_interval = Duration::from_millis(self.config.flush_interval_ms);
+        let mut deadline = None;
+
+        while !self.finished && self.rows.len() < batch_size {
+            // 第一批已提交的行到达后开始计时
+            if deadline.is_none() && !self.rows.is_empty() {
+                deadline = Some(Instant::now() + flush_interval);
+            }
+
+            let stream = self
+                .stream
+                .as_mut()
+                .ok_or_else(|| Error::Connection("Binlog stream not started".into()))?;
+            let next = match deadline {
+                Some(deadline) => match tokio::time::timeout_at(deadline, stream.next()).await {
+                    Ok(next) => next,
+                    Err(_) => break,
+                },
+                None => stream.next().await,
+            };
+
+            match next {
+                Some(event) => self.handle_event(event.map_err(|e| Error::Read(e.to_string()))?)?,
+                None => {
+                    info!("Reached the end of the binlog");
+                    self.finished = true;
+                }
+            }
+        }
+
+        self.take_batch()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::SpatialType;
+
+    fn column(data_type: DbsyncType, unsigned_bits: Option<u32>) -> BinlogColumn {
+        BinlogColumn {
+            name: "c".to_string(),
+            data_type,
+            nullable: true,
+            unsigned_bits,
+        }
+    }
+
+    fn convert(value: MySqlValue, column: &BinlogColumn) -> DbsyncValue {
+        binlog_value(BinlogValue::Value(value), column).unwrap()
+    }
+
+    #[test]
+    fn test_binlog_value_unsigned() {
+        let int = column(DbsyncType::UnsignedInt, Some(32));
+        assert_eq!(
+            convert(MySqlValue::Int(-1), &int),
+            DbsyncValue::UInteger(u32::MAX as u64)
+        );
+        let bigint = column(DbsyncType::UnsignedBigInt, Some(64));
+        assert_eq!(
+            convert(MySqlValue::Int(-1), &bigint),
+            DbsyncValue::UInteger(u64::MAX)
+        );
+        let signed = column(DbsyncType::Int, None);
+        assert_eq!(
+            convert(MySqlValue::Int(-1), &signed),
+            DbsyncValue::Integer(-1)
+        );
+    }
+
+    #[test]
+    fn test_binlog_value_mysql_types() {
+        let bit = column(DbsyncType::Bit(12), None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(vec![0x0a, 0xbc]), &bit),
+            DbsyncValue::UInteger(0x0abc)
+        );
+
+        let year = column(DbsyncType::Year, None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(b"2024".to_vec()), &year),
+            DbsyncValue::Integer(2024)
+        );
+        assert_eq!(
+            convert(MySqlValue::Bytes(b"1900".to_vec()), &year),
+            DbsyncValue::Integer(0)
+        );
+
+        let members = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let enumeration = column(DbsyncType::Enum(members.clone()), None);
+        assert_eq!(
+            convert(MySqlValue::Int(2), &enumeration),
+            DbsyncValue::String("b".to_string())
+        );
+        assert_eq!(
+            convert(MySqlValue::Int(0), &enumeration),
+            DbsyncValue::String(String::new())
+        );
+
+        let set = column(DbsyncType::Set(members), None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(vec![0b101]), &set),
+            DbsyncValue::String("a,c".to_string())
+        );
+
+        let point = column(DbsyncType::Spatial(SpatialType::Point), None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(vec![0, 1, 2]), &point),
+            DbsyncValue::Binary(vec![0, 1, 2])
+        );
+        let text = column(DbsyncType::TinyText, None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(b"abc".to_vec()), &text),
+            DbsyncValue::String("abc".to_string())
+        );
+    }
+
+    #[test]
+    fn test_binlog_value_temporal() {
+        let timestamp = column(DbsyncType::Timestamp(6), None);
+        assert_eq!(
+            convert(MySqlValue::Bytes(b"1700000000.000123".to_vec()), &timestamp),
+            DbsyncValue::Timestamp(1_700_000_000_000_123)
+        );
+        assert_eq!(
+            convert(MySqlValue::Bytes(b"1700000000".to_vec()), &timestamp),
+            DbsyncValue::Timestamp(1_700_000_000_000_000)
+        );
+        assert_eq!(
+            convert(MySqlValue::Int(1), &timestamp),
+            DbsyncValue::Timestamp(1_000_000)
+        );
+        let datetime = column(DbsyncType::DateTime(6), None);
+        assert_eq!(
+            convert(MySqlValue::Date(1970, 1, 2, 0, 0, 1, 500_000), &datetime),
+            DbsyncValue::DateTime(86_401_500_000)
+        );
+        let date = column(DbsyncType::Date, None);
+        assert_eq!(
+            convert(MySqlValue::Date(1969, 12, 31, 0, 0, 0, 0), &date),
+            DbsyncValue::Date(-1)
+        );
+        assert!(binlog_value(
+            BinlogValue::Value(MySqlValue::Date(0, 0, 0, 0, 0, 0, 0)),
+            &datetime
+        )
+        .is_err());
+        let time = column(DbsyncType::Time(6), None);
+        assert_eq!(
+            convert(MySqlValue::Time(true, 1, 0, 0, 1, 7), &time),
+            DbsyncValue::Time(-86_401_000_007)
+        );
+    }
+
+    #[test]
+    fn test_position_round_trip() {
+        let position = BinlogPosition {
+            file: "mysql-bin.000003".to_string(),
+            position: 1024,
+            gtid: None,
+        };
+        let value = serde_json::to_value(&position).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({"file": "mysql-bin.000003", "position": 1024})
+        );
+        assert_eq!(
+            serde_json::from_value::<BinlogPosition>(value).unwrap(),
+            position
+        );
+    }
+}
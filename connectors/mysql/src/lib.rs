@@ -1,8 +1,12 @@
+mod binlog;
 mod config;
+mod schema;
 mod sink;
 mod source;
 mod type_converter;
 
-pub use config::{MySQLSinkConfig, MySQLSourceConfig};
+pub use binlog::{BinlogPosition, MySQLBinlogSource};
+pub use config::{MySQLBinlogConfig, MySQLSinkConfig, MySQLSourceConfig, WriteMode};
+pub use schema::MySQLDialect;
 pub use sink::MySQLSink;
 pub use source::MySQLSource;
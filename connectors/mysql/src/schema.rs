@@ -0,0 +1,421 @@
+use crate::type_converter::MySQLTypeMapper;
+use dbsync_core::{
+    ddl::{CommentStyle, Dialect},
+    error::{Error, Result},
+    schema::{ColumnDefault, ColumnSchema, IndexColumn, IndexSchema, TableSchema},
+    types::{DbsyncType, TypeMapper},
+};
+use sqlx::{
+    mysql::{MySqlPool, MySqlRow},
+    Row,
+};
+use std::collections::HashSet;
+
+// 带反引号的表名或列名, 名称中的反引号加倍转义
+pub(crate) fn quote_identifier(name: &str) -> String {
+    MySQLDialect.quote_identifier(name)
+}
+
+pub(crate) fn quote_identifiers(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// INFORMATION_SCHEMA.COLUMNS 中描述列类型的字段, 由 column_type 解析
+pub(crate) const COLUMN_TYPE_FIELDS: &str = "\
+    CAST(COLUMN_TYPE AS CHAR) AS COLUMN_TYPE, \
+    CAST(CHARACTER_MAXIMUM_LENGTH AS SIGNED) AS CHARACTER_MAXIMUM_LENGTH, \
+    CAST(NUMERIC_PRECISION AS SIGNED) AS NUMERIC_PRECISION, \
+    CAST(NUMERIC_SCALE AS SIGNED) AS NUMERIC_SCALE";
+
+// 需要查询 CAST(IS_NULLABLE AS CHAR) AS IS_NULLABLE
+pub(crate) fn is_nullable(row: &MySqlRow) -> bool {
+    row.get::<String, _>("IS_NULLABLE") == "YES"
+}
+
+// tinyint1_as_boolean 为 true 时 TINYINT(1) 列读作布尔值, 否则保留为 TINYINT
+pub(crate) fn column_type(row: &MySqlRow, tinyint1_as_boolean: bool) -> Result<DbsyncType> {
+    let column_type = row.get::<String, _>("COLUMN_TYPE");
+    if tinyint1_as_boolean && column_type.eq_ignore_ascii_case("tinyint(1)") {
+        return Ok(DbsyncType::Boolean);
+    }
+    resolve_column_type(
+        &column_type,
+        row.get("CHARACTER_MAXIMUM_LENGTH"),
+        row.get("NUMERIC_PRECISION"),
+        row.get("NUMERIC_SCALE"),
+    )
+}
+
+// 按 COLUMN_TYPE 解析类型和修饰 (UNSIGNED, ENUM 成员等),
+// 长度, 精度和小数位以对应字段为准, 保证目标表与源表一致
+fn resolve_column_type(
+    column_type: &str,
+    length: Option<i64>,
+    precision: Option<i64>,
+    scale: Option<i64>,
+) -> Result<DbsyncType> {
+    let param = |value: Option<i64>, field: &str| {
+        value
+            .and_then(|value| u32::try_from(value).ok())
+            .ok_or_else(|| Error::Type(format!("Invalid {} for type {}", field, column_type)))
+    };
+    // DECIMAL 最大精度 65, 小数位 30
+    let numeric = || -> Result<(u8, u8)> {
+        let precision = param(precision, "NUMERIC_PRECISION")?;
+        let scale = param(scale, "NUMERIC_SCALE")?;
+        match (u8::try_from(precision), u8::try_from(scale)) {
+            (Ok(precision), Ok(scale)) => Ok((precision, scale)),
+            _ => Err(Error::Type(format!(
+                "Invalid precision for type {}",
+                column_type
+            ))),
+        }
+    };
+    let length = || param(length, "CHARACTER_MAXIMUM_LENGTH");
+
+    Ok(match MySQLTypeMapper.to_dbsync_type(column_type)? {
+        DbsyncType::Decimal(_, _) => {
+            let (precision, scale) = numeric()?;
+            DbsyncType::Decimal(precision, scale)
+        }
+        DbsyncType::Numeric(_, _) => {
+            let (precision, scale) = numeric()?;
+            DbsyncType::Numeric(precision, scale)
+        }
+        DbsyncType::Char(_) => DbsyncType::Char(length()?),
+        DbsyncType::VarChar(_) => DbsyncType::VarChar(length()?),
+        DbsyncType::Binary(_) => DbsyncType::Binary(length()?),
+        DbsyncType::VarBinary(_) => DbsyncType::VarBinary(length()?),
+        other => other,
+    })
+}
+
+// 从 INFORMATION_SCHEMA 读取表结构
+pub(crate) async fn load_table_schema(
+    pool: &MySqlPool,
+    table: &str,
+    tinyint1_as_boolean: bool,
+) -> Result<TableSchema> {
+    let query = format!(
+        r#"
+        SELECT
+            CAST(COLUMN_NAME AS CHAR) AS COLUMN_NAME,
+            {},
+            CAST(IS_NULLABLE AS CHAR) AS IS_NULLABLE,
+            CAST(COLUMN_DEFAULT AS CHAR) AS COLUMN_DEFAULT,
+            CAST(EXTRA AS CHAR) AS EXTRA,
+            CAST(COLUMN_COMMENT AS CHAR) AS COLUMN_COMMENT
+        FROM INFORMATION_SCHEMA.COLUMNS
+        WHERE TABLE_SCHEMA = DATABASE()
+        AND TABLE_NAME = ?
+        ORDER BY ORDINAL_POSITION
+        "#,
+        COLUMN_TYPE_FIELDS
+    );
+    let rows = sqlx::query(&query)
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+
+    if rows.is_empty() {
+        return Err(Error::Read(format!("Table {} not found", table)));
+    }
+
+    let mut schema = TableSchema::new(table).with_dialect(MySQLDialect.name());
+    for row in &rows {
+        let extra: String = row.get("EXTRA");
+        let comment: String = row.get("COLUMN_COMMENT");
+
+        let mut column = ColumnSchema::new(
+            row.get::<String, _>("COLUMN_NAME"),
+            column_type(row, tinyint1_as_boolean)?,
+        )
+        .with_nullable(is_nullable(row))
+        .with_auto_increment(extra.to_lowercase().contains("auto_increment"));
+        column.default = column_default(row.get("COLUMN_DEFAULT"), &extra);
+        column.on_update = on_update(&extra);
+        if !comment.is_empty() {
+            column = column.with_comment(comment);
+        }
+        schema.columns.push(column);
+    }
+
+    // 函数索引没有 COLUMN_NAME, 无法按列重建, 整个跳过
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            CAST(INDEX_NAME AS CHAR) AS INDEX_NAME,
+            CAST(COLUMN_NAME AS CHAR) AS COLUMN_NAME,
+            CAST(NON_UNIQUE AS SIGNED) AS NON_UNIQUE,
+            CAST(SUB_PART AS SIGNED) AS SUB_PART,
+            CAST(COLLATION AS CHAR) AS COLLATION
+        FROM INFORMATION_SCHEMA.STATISTICS
+        WHERE TABLE_SCHEMA = DATABASE()
+        AND TABLE_NAME = ?
+        ORDER BY INDEX_NAME, SEQ_IN_INDEX
+        "#,
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))?;
+
+    let mut indexes: Vec<IndexSchema> = Vec::new();
+    let mut skipped = HashSet::new();
+    for row in &rows {
+        let name: String = row.get("INDEX_NAME");
+        let non_unique: i64 = row.get("NON_UNIQUE");
+        let Some(column) = row.get::<Option<String>, _>("COLUMN_NAME") else {
+            skipped.insert(name);
+            continue;
+        };
+        // SUB_PART 为前缀长度, COLLATION 为 A (升序) 或 D (降序)
+        let mut column = IndexColumn::new(column)
+            .with_descending(row.get::<Option<String>, _>("COLLATION").as_deref() == Some("D"));
+        if let Some(length) = row.get::<Option<i64>, _>("SUB_PART") {
+            column = column
+                .with_prefix_length(u32::try_from(length).map_err(|e| Error::Read(e.to_string()))?);
+        }
+        match indexes.last_mut() {
+            Some(index) if index.name == name => index.columns.push(column),
+            _ => indexes.push(IndexSchema::with_columns(
+                name,
+                vec![column],
+                non_unique == 0,
+            )),
+        }
+    }
+    for index in indexes {
+        if skipped.contains(&index.name) {
+            continue;
+        }
+        if index.name == "PRIMARY" {
+            schema.primary_key = index.column_names();
+        } else {
+            schema.indexes.push(index);
+        }
+    }
+
+    let comment: Option<String> = sqlx::query_scalar(
+        "SELECT CAST(TABLE_COMMENT AS CHAR) FROM INFORMATION_SCHEMA.TABLES \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ?",
+    )
+    .bind(table)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))?
+    .flatten();
+    schema.comment = comment.filter(|c| !c.is_empty());
+
+    Ok(schema)
+}
+
+// MySQL 8.0 对表达式默认值在 EXTRA 中标记 DEFAULT_GENERATED 且省略外层括号,
+// 5.7 只有 CURRENT_TIMESTAMP 一种表达式
+fn column_default(default: Option<String>, extra: &str) -> Option<ColumnDefault> {
+    let default = default?;
+    if default.to_uppercase().starts_with("CURRENT_TIMESTAMP") {
+        Some(ColumnDefault::Expression(default))
+    } else if extra.to_uppercase().contains("DEFAULT_GENERATED") {
+        Some(ColumnDefault::Expression(format!("({})", default)))
+    } else {
+        Some(ColumnDefault::Literal(default))
+    }
+}
+
+// EXTRA 中的 on update CURRENT_TIMESTAMP[(n)], 可能跟在 DEFAULT_GENERATED 之后
+fn on_update(extra: &str) -> Option<String> {
+    let start = extra.to_lowercase().find("on update ")? + "on update ".len();
+    Some(extra[start..].trim().to_string()).filter(|expr| !expr.is_empty())
+}
+
+// MySQL 方言: 反引号标识符, 索引和注释都写在 CREATE TABLE 内
+pub struct MySQLDialect;
+
+impl Dialect for MySQLDialect {
+    fn name(&self) -> &str {
+        "mysql"
+    }
+
+    fn type_mapper(&self) -> &dyn TypeMapper {
+        &MySQLTypeMapper
+    }
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("`{}`", name.replace('`', "``"))
+    }
+
+    // 默认的 sql_mode 下反斜杠也是转义字符
+    fn quote_literal(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+
+    fn auto_increment(&self) -> Option<&str> {
+        Some("AUTO_INCREMENT")
+    }
+
+    fn inline_indexes(&self) -> bool {
+        true
+    }
+
+    fn prefix_indexes(&self) -> bool {
+        true
+    }
+
+    fn on_update(&self) -> bool {
+        true
+    }
+
+    fn comment_style(&self) -> CommentStyle {
+        CommentStyle::Inline
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::{ddl::create_table_statements, DbsyncType};
+
+    #[test]
+    fn test_resolve_column_type() {
+        let resolve = |column_type, length, precision, scale| {
+            resolve_column_type(column_type, length, precision, scale).unwrap()
+        };
+        assert_eq!(
+            resolve("decimal(38,10)", None, Some(38), Some(10)),
+            DbsyncType::Decimal(38, 10)
+        );
+        assert_eq!(
+            resolve("decimal(65,30) unsigned", None, Some(65), Some(30)),
+            DbsyncType::Decimal(65, 30)
+        );
+        assert_eq!(
+            resolve("varchar(1000)", Some(1000), None, None),
+            DbsyncType::VarChar(1000)
+        );
+        assert_eq!(resolve("char(3)", Some(3), None, None), DbsyncType::Char(3));
+        assert_eq!(
+            resolve("binary(16)", Some(16), None, None),
+            DbsyncType::Binary(16)
+        );
+        assert_eq!(
+            resolve("varbinary(2048)", Some(2048), None, None),
+            DbsyncType::VarBinary(2048)
+        );
+        assert_eq!(
+            resolve("tinyint(1)", None, Some(3), Some(0)),
+            DbsyncType::TinyInt
+        );
+        assert_eq!(
+            resolve("tinyint", None, Some(3), Some(0)),
+            DbsyncType::TinyInt
+        );
+        assert_eq!(
+            resolve("int(10) unsigned", None, Some(10), Some(0)),
+            DbsyncType::UnsignedInt
+        );
+        assert_eq!(resolve("bit(5)", None, Some(5), None), DbsyncType::Bit(5));
+        assert_eq!(
+            resolve("enum('a','it''s')", Some(4), None, None),
+            DbsyncType::Enum(vec!["a".to_string(), "it's".to_string()])
+        );
+        assert_eq!(resolve("text", Some(65535), None, None), DbsyncType::Text);
+        assert!(resolve_column_type("varchar(10)", None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_column_default() {
+        assert_eq!(column_default(None, ""), None);
+        assert_eq!(
+            column_default(Some("abc".into()), ""),
+            Some(ColumnDefault::Literal("abc".into()))
+        );
+        assert_eq!(
+            column_default(Some("CURRENT_TIMESTAMP(3)".into()), "DEFAULT_GENERATED"),
+            Some(ColumnDefault::Expression("CURRENT_TIMESTAMP(3)".into()))
+        );
+        assert_eq!(
+            column_default(Some("CURRENT_TIMESTAMP".into()), ""),
+            Some(ColumnDefault::Expression("CURRENT_TIMESTAMP".into()))
+        );
+        assert_eq!(
+            column_default(Some("uuid()".into()), "DEFAULT_GENERATED"),
+            Some(ColumnDefault::Expression("(uuid())".into()))
+        );
+    }
+
+    #[test]
+    fn test_on_update() {
+        assert_eq!(on_update(""), None);
+        assert_eq!(on_update("auto_increment"), None);
+        assert_eq!(
+            on_update("on update CURRENT_TIMESTAMP"),
+            Some("CURRENT_TIMESTAMP".into())
+        );
+        assert_eq!(
+            on_update("DEFAULT_GENERATED on update CURRENT_TIMESTAMP(3)"),
+            Some("CURRENT_TIMESTAMP(3)".into())
+        );
+    }
+
+    #[test]
+    fn test_mysql_create_table_statements() {
+        // 列名与表名相同时不应被替换
+        let schema = TableSchema::new("users")
+            .with_column(
+                ColumnSchema::new("id", DbsyncType::BigInt)
+                    .with_nullable(false)
+                    .with_auto_increment(true),
+            )
+            .with_column(
+                ColumnSchema::new("users", DbsyncType::VarChar(255))
+                    .with_default(ColumnDefault::Literal("it's".into()))
+                    .with_comment("名称"),
+            )
+            .with_column(
+                ColumnSchema::new("created_at", DbsyncType::Timestamp(3))
+                    .with_default(ColumnDefault::Expression("CURRENT_TIMESTAMP(3)".into()))
+                    .with_on_update("CURRENT_TIMESTAMP(3)"),
+            )
+            .with_column(
+                ColumnSchema::new("uid", DbsyncType::VarChar(36))
+                    .with_default(ColumnDefault::Expression("(uuid())".into())),
+            )
+            .with_primary_key(vec!["id".into()])
+            .with_index(IndexSchema::new(
+                "idx_users",
+                vec!["users".into(), "created_at".into()],
+                true,
+            ))
+            .with_index(IndexSchema::with_columns(
+                "idx_prefix",
+                vec![
+                    IndexColumn::new("users").with_prefix_length(10),
+                    IndexColumn::new("created_at").with_descending(true),
+                ],
+                false,
+            ))
+            .with_comment("用户表")
+            .with_dialect("mysql");
+
+        assert_eq!(
+            create_table_statements(&MySQLDialect, "target", &schema).unwrap(),
+            vec![
+                "CREATE TABLE `target` (\n  \
+             `id` BIGINT NOT NULL AUTO_INCREMENT,\n  \
+             `users` VARCHAR(255) NULL DEFAULT 'it''s' COMMENT '名称',\n  \
+             `created_at` TIMESTAMP(3) NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),\n  \
+             `uid` VARCHAR(36) NULL DEFAULT (uuid()),\n  \
+             PRIMARY KEY (`id`),\n  \
+             UNIQUE KEY `idx_users` (`users`, `created_at`),\n  \
+             KEY `idx_prefix` (`users`(10), `created_at` DESC)\n\
+             ) COMMENT='用户表'"
+            ]
+        );
+    }
+}
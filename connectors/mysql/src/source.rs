@@ -1,46 +1,121 @@
-use crate::config::MySQLSourceConfig;
-use crate::type_converter::{MySQLTypeMapper, MySQLValueConverter};
+use crate::config::{connect_options, parse_time_zone, MySQLSourceConfig};
+use crate::schema::{
+    column_type, is_nullable, load_table_schema, quote_identifier, COLUMN_TYPE_FIELDS,
+};
+use crate::type_converter::MySQLValueConverter;
 use async_trait::async_trait;
 use dbsync_core::connector::Context;
 use dbsync_core::{
-    connector::{ConnectorConfig, DataBatch, Record, ShardedSource, Source},
+    connector::{
+        BatchField, BatchSchema, ConnectorConfig, DataBatch, IncrementalSource, KeyRange, KeyValue,
+        RangeBatch, Record, ShardedSource, Source,
+    },
     error::{Error, Result},
-    types::{DbsyncType, TypeConverter, TypeMapper},
+    schema::TableSchema,
+    types::{DbsyncValue, TypeConverter},
 };
-use sqlx::{mysql::MySqlPool, Column, Row};
+use sqlx::mysql::{MySql, MySqlArguments, MySqlPool, MySqlRow};
+use sqlx::query::Query;
+use sqlx::{Column, Row};
 use std::any::Any;
-use std::collections::HashMap;
-use tracing::info;
+use std::sync::Arc;
+use tracing::{info, warn};
 
 #[derive(Clone)]
 pub struct MySQLSource {
     config: MySQLSourceConfig,
     pool: Option<MySqlPool>,
-    type_mapper: MySQLTypeMapper,
     value_converter: MySQLValueConverter,
+    // 主键列, init 时从 INFORMATION_SCHEMA 读取
+    primary_key: Vec<String>,
+    // 输出的列, 按列顺序排列, init 时读取一次, 所有批次共享
+    schema: Option<Arc<BatchSchema>>,
+    // read_batch 的 keyset 游标, 即上一次返回的最后一行的主键
+    cursor: Option<KeyValue>,
+    // 增量同步的水位区间 (low, high], 设置后所有读取只包含区间内的行
+    watermark: Option<(Option<DbsyncValue>, DbsyncValue)>,
 }
 
 impl MySQLSource {
     pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = MySQLSourceConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        let time_zone = parse_time_zone(&config.time_zone)?;
         Ok(Self {
-            config: MySQLSourceConfig::from_json(serde_json::Value::Object(
-                serde_json::Map::from_iter(config.properties),
-            ))?,
+            config,
             pool: None,
-            type_mapper: MySQLTypeMapper,
-            value_converter: MySQLValueConverter,
+            value_converter: MySQLValueConverter::new(time_zone),
+            primary_key: Vec::new(),
+            schema: None,
+            cursor: None,
+            watermark: None,
         })
     }
 
-    async fn get_column_types(&self, pool: &MySqlPool) -> Result<HashMap<String, DbsyncType>> {
-        let query = r#"
-            SELECT 
+    // 水位过滤条件及其参数, 未设置水位区间时为空
+    fn watermark_filter(&self) -> (Vec<String>, KeyValue) {
+        let (Some(column), Some((low, high))) = (&self.config.incremental_column, &self.watermark)
+        else {
+            return (Vec::new(), KeyValue(Vec::new()));
+        };
+
+        let column = quote_identifier(column);
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(low) = low {
+            conditions.push(format!("{} > ?", column));
+            values.push(low.clone());
+        }
+        conditions.push(format!("{} <= ?", column));
+        values.push(high.clone());
+        (conditions, KeyValue(values))
+    }
+
+    fn schema(&self) -> Result<&Arc<BatchSchema>> {
+        self.schema
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    async fn get_columns(&self, pool: &MySqlPool) -> Result<Vec<BatchField>> {
+        let query = format!(
+            r#"
+            SELECT
                 COLUMN_NAME,
-                CAST(DATA_TYPE AS CHAR) as COLUMN_TYPE
-            FROM INFORMATION_SCHEMA.COLUMNS 
+                CAST(IS_NULLABLE AS CHAR) AS IS_NULLABLE,
+                {}
+            FROM INFORMATION_SCHEMA.COLUMNS
             WHERE TABLE_SCHEMA = DATABASE()
             AND TABLE_NAME = ?
             ORDER BY ORDINAL_POSITION
+            "#,
+            COLUMN_TYPE_FIELDS
+        );
+
+        let rows = sqlx::query(&query)
+            .bind(&self.config.table)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        rows.iter()
+            .map(|row| {
+                let column_name: String = row.get("COLUMN_NAME");
+                let data_type = column_type(row, self.config.tinyint1_as_boolean)?;
+                Ok(BatchField::new(column_name, data_type).with_nullable(is_nullable(row)))
+            })
+            .collect()
+    }
+
+    async fn get_primary_key_columns(&self, pool: &MySqlPool) -> Result<Vec<String>> {
+        let query = r#"
+            SELECT COLUMN_NAME
+            FROM INFORMATION_SCHEMA.KEY_COLUMN_USAGE
+            WHERE TABLE_SCHEMA = DATABASE()
+            AND TABLE_NAME = ?
+            AND CONSTRAINT_NAME = 'PRIMARY'
+            ORDER BY ORDINAL_POSITION
         "#;
 
         let rows = sqlx::query(query)
@@ -49,27 +124,208 @@ impl MySQLSource {
             .await
             .map_err(|e| Error::Read(e.to_string()))?;
 
-        let mut column_types = HashMap::new();
-        for row in rows {
-            let column_name: String = row.get("COLUMN_NAME");
-            let column_type: String = row.get("COLUMN_TYPE");
-            let dbsync_type = self.type_mapper.to_dbsync_type(&column_type)?;
-            column_types.insert(column_name, dbsync_type);
+        Ok(rows.iter().map(|row| row.get("COLUMN_NAME")).collect())
+    }
+
+    fn primary_key(&self) -> Result<&[String]> {
+        if self.primary_key.is_empty() {
+            return Err(Error::Config(format!(
+                "Table {} has no primary key, keyset reads require one",
+                self.config.table
+            )));
+        }
+        Ok(&self.primary_key)
+    }
+
+    // 按主键顺序读取 after 之后 (不含) 且不超过 end 的下一批数据
+    async fn read_page(
+        &self,
+        after: Option<&KeyValue>,
+        end: Option<&KeyValue>,
+        limit: usize,
+    ) -> Result<Option<RangeBatch>> {
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))?;
+        let key = self.primary_key()?;
+        let schema = self.schema()?;
+
+        let (mut conditions, watermark) = self.watermark_filter();
+        if after.is_some() {
+            conditions.push(key_predicate(key, ">"));
+        }
+        if end.is_some() {
+            conditions.push(key_predicate(key, "<="));
+        }
+        let query = format!(
+            "SELECT * FROM {} {} ORDER BY {} LIMIT ?",
+            quote_identifier(&self.config.table),
+            where_clause(&conditions),
+            key_order(key, "ASC")
+        );
+
+        let mut query_builder = bind_key(sqlx::query(&query), &watermark, &self.value_converter)?;
+        for bound in [after, end].into_iter().flatten() {
+            query_builder = bind_key(query_builder, bound, &self.value_converter)?;
+        }
+        let rows = query_builder
+            .bind(limit as u64)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        let Some(first) = rows.first() else {
+            return Ok(None);
+        };
+        // 整页共享 init 时读取的 schema, SELECT * 的列顺序与它一致, 除非表结构已改变
+        if !first
+            .columns()
+            .iter()
+            .map(|col| col.name())
+            .eq(schema.names())
+        {
+            return Err(Error::Read(format!(
+                "Columns of table {} changed while reading",
+                self.config.table
+            )));
         }
+        let schema = schema.clone();
+
+        let records = rows
+            .iter()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .zip(&schema.fields)
+                    .map(|(col, field)| {
+                        self.value_converter
+                            .to_dbsync_value(col, row, &field.data_type)
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map(Record::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
 
-        Ok(column_types)
+        let last = &records[records.len() - 1];
+        let last_key = KeyValue(
+            key.iter()
+                .map(|column| {
+                    schema
+                        .index_of(column)
+                        .map(|index| last.values[index].clone())
+                        .ok_or_else(|| Error::Read(format!("Missing key column: {}", column)))
+                })
+                .collect::<Result<_>>()?,
+        );
+
+        Ok(Some(RangeBatch {
+            batch: DataBatch::new(schema, records),
+            last_key,
+        }))
     }
 
-    async fn get_create_table_sql(&self, pool: &MySqlPool) -> Result<String> {
-        let query = "SHOW CREATE TABLE ".to_string() + &self.config.table;
-        let row = sqlx::query(&query)
-            .fetch_one(pool)
+    // 按主键排序, 跳过 after (不含) 之后的 offset 行, 读取下一行的主键
+    async fn get_key_at(
+        &self,
+        order: &str,
+        after: Option<&KeyValue>,
+        offset: i64,
+    ) -> Result<Option<KeyValue>> {
+        let pool = self
+            .pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))?;
+        let key = self.primary_key()?;
+
+        let (mut conditions, watermark) = self.watermark_filter();
+        if after.is_some() {
+            conditions.push(key_predicate(key, if order == "ASC" { ">" } else { "<" }));
+        }
+        let query = format!(
+            "SELECT {} FROM {} {} ORDER BY {} LIMIT 1 OFFSET ?",
+            key_columns(key),
+            quote_identifier(&self.config.table),
+            where_clause(&conditions),
+            key_order(key, order)
+        );
+        let mut query_builder = bind_key(sqlx::query(&query), &watermark, &self.value_converter)?;
+        if let Some(after) = after {
+            query_builder = bind_key(query_builder, after, &self.value_converter)?;
+        }
+        let row = query_builder
+            .bind(offset)
+            .fetch_optional(pool)
             .await
             .map_err(|e| Error::Read(e.to_string()))?;
 
-        let create_table: String = row.get(1);
-        Ok(create_table.replace(&self.config.table, "target_table"))
+        match row {
+            Some(row) => Ok(Some(self.row_to_key(&row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn row_to_key(&self, row: &MySqlRow) -> Result<KeyValue> {
+        let schema = self.schema()?;
+        let values = row
+            .columns()
+            .iter()
+            .map(|col| {
+                let field = schema
+                    .index_of(col.name())
+                    .map(|index| &schema.fields[index])
+                    .ok_or_else(|| Error::Type(format!("Unknown column: {}", col.name())))?;
+                self.value_converter
+                    .to_dbsync_value(col, row, &field.data_type)
+            })
+            .collect::<Result<_>>()?;
+        Ok(KeyValue(values))
+    }
+}
+
+fn key_columns(key: &[String]) -> String {
+    key.iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn key_order(key: &[String], order: &str) -> String {
+    key.iter()
+        .map(|column| format!("{} {}", quote_identifier(column), order))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        return String::new();
+    }
+    format!("WHERE {}", conditions.join(" AND "))
+}
+
+// 主键比较条件, 复合主键使用行构造器比较, 如 (`a`, `b`) > (?, ?)
+fn key_predicate(key: &[String], op: &str) -> String {
+    if key.len() == 1 {
+        return format!("{} {} ?", quote_identifier(&key[0]), op);
+    }
+    let placeholders = vec!["?"; key.len()].join(", ");
+    format!("({}) {} ({})", key_columns(key), op, placeholders)
+}
+
+// 按主键值的原生类型绑定参数, 避免字符串比较带来的精度和排序问题
+fn bind_key<'q>(
+    mut query: Query<'q, MySql, MySqlArguments>,
+    key: &KeyValue,
+    converter: &MySQLValueConverter,
+) -> Result<Query<'q, MySql, MySqlArguments>> {
+    for value in &key.0 {
+        if *value == DbsyncValue::Null {
+            return Err(Error::Type("Primary key value cannot be NULL".into()));
+        }
+        query = converter.bind(query, value)?;
     }
+    Ok(query)
 }
 
 #[async_trait]
@@ -78,13 +334,24 @@ impl Source for MySQLSource {
         let url = &self.config.url;
         info!("Connecting to MySQL source: {}", url);
 
-        let pool = MySqlPool::connect(url)
+        let pool = MySqlPool::connect_with(connect_options(url, &self.config.time_zone)?)
             .await
             .map_err(|e| Error::Connection(format!("Failed to connect to MySQL source: {}", e)))?;
 
         // 获取表结构并放入 context
-        let schema = self.get_create_table_sql(&pool).await?;
-        ctx.set_schema(schema);
+        ctx.set_schema(
+            load_table_schema(&pool, &self.config.table, self.config.tinyint1_as_boolean).await?,
+        );
+
+        self.primary_key = self.get_primary_key_columns(&pool).await?;
+        if self.primary_key.is_empty() {
+            warn!(
+                "Table {} has no primary key, sharded reads are unavailable",
+                self.config.table
+            );
+        }
+
+        self.schema = Some(Arc::new(BatchSchema::new(self.get_columns(&pool).await?)));
 
         info!("Successfully connected to MySQL source");
         self.pool = Some(pool);
@@ -92,43 +359,16 @@ impl Source for MySQLSource {
     }
 
     async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
-        let pool = self
-            .pool
-            .as_ref()
-            .ok_or_else(|| Error::Connection("Not connected".into()))?;
-
-        let column_types = self.get_column_types(pool).await?;
-        let query = format!("SELECT * FROM {} LIMIT {}", self.config.table, batch_size);
-
-        let rows = sqlx::query(&query)
-            .fetch_all(pool)
-            .await
-            .map_err(|e| Error::Read(e.to_string()))?;
-
-        if rows.is_empty() {
-            return Ok(None);
+        match self
+            .read_page(self.cursor.as_ref(), None, batch_size)
+            .await?
+        {
+            Some(RangeBatch { batch, last_key }) => {
+                self.cursor = Some(last_key);
+                Ok(Some(batch))
+            }
+            None => Ok(None),
         }
-
-        let records = rows
-            .into_iter()
-            .map(|row| {
-                let mut fields = HashMap::new();
-                for col in row.columns() {
-                    let name = col.name().to_string();
-                    let field_type = column_types
-                        .get(&name)
-                        .ok_or_else(|| Error::Type(format!("Unknown column: {}", name)))?;
-
-                    let dbsync_value =
-                        self.value_converter
-                            .to_dbsync_value(col, &row, &field_type)?;
-                    fields.insert(name, (dbsync_value, field_type.clone()));
-                }
-                Ok(Record { fields })
-            })
-            .collect::<Result<Vec<_>>>()?;
-
-        Ok(Some(DataBatch { records }))
     }
 
     async fn close(&mut self) -> Result<()> {
@@ -150,99 +390,166 @@ impl Source for MySQLSource {
     fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
         Some(self)
     }
+
+    fn as_incremental(&mut self) -> Option<&mut dyn IncrementalSource> {
+        if self.config.incremental_column.is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
 }
 
 #[async_trait]
-impl ShardedSource for MySQLSource {
-    async fn get_total_records(&self) -> Result<i64> {
-        let pool = self
-            .pool
-            .as_ref()
-            .ok_or_else(|| Error::Connection("Not connected".into()))?;
-        let row = sqlx::query(&format!(
-            "SELECT COUNT(*) as count FROM {}",
-            self.config.table
-        ))
-        .fetch_one(pool)
-        .await
-        .map_err(|e| Error::Read(e.to_string()))?;
-        Ok(row.get::<i64, _>("count"))
+impl IncrementalSource for MySQLSource {
+    fn watermark_column(&self) -> &str {
+        self.config
+            .incremental_column
+            .as_deref()
+            .unwrap_or_default()
     }
 
-    async fn get_id_range(&self) -> Result<(i64, i64)> {
+    async fn get_max_watermark(&self) -> Result<Option<DbsyncValue>> {
         let pool = self
             .pool
             .as_ref()
             .ok_or_else(|| Error::Connection("Not connected".into()))?;
-        let row = sqlx::query(&format!(
-            "SELECT MIN(id) as min_id, MAX(id) as max_id FROM {}",
-            self.config.table
-        ))
-        .fetch_one(pool)
-        .await
-        .map_err(|e| Error::Read(e.to_string()))?;
-        Ok((row.get("min_id"), row.get("max_id")))
+        let column = quote_identifier(self.watermark_column());
+
+        let query = format!(
+            "SELECT {0} FROM {1} WHERE {0} IS NOT NULL ORDER BY {0} DESC LIMIT 1",
+            column,
+            quote_identifier(&self.config.table)
+        );
+        let row = sqlx::query(&query)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(self.row_to_key(&row)?.0.pop()),
+            None => Ok(None),
+        }
+    }
+
+    fn set_watermark_range(&mut self, low: Option<DbsyncValue>, high: DbsyncValue) {
+        self.watermark = Some((low, high));
     }
+}
 
-    async fn read_batch_range(&mut self, start_id: i64, end_id: i64) -> Result<Option<DataBatch>> {
+#[async_trait]
+impl ShardedSource for MySQLSource {
+    async fn get_total_records(&self) -> Result<i64> {
         let pool = self
             .pool
             .as_ref()
             .ok_or_else(|| Error::Connection("Not connected".into()))?;
-
-        let column_types = self.get_column_types(pool).await?;
+        let (conditions, watermark) = self.watermark_filter();
         let query = format!(
-            "SELECT * FROM {} WHERE id >= {} AND id <= {} LIMIT 1000",
-            self.config.table, start_id, end_id
+            "SELECT COUNT(*) as count FROM {} {}",
+            quote_identifier(&self.config.table),
+            where_clause(&conditions)
         );
-
-        let rows = sqlx::query(&query)
-            .fetch_all(pool)
+        let row = bind_key(sqlx::query(&query), &watermark, &self.value_converter)?
+            .fetch_one(pool)
             .await
             .map_err(|e| Error::Read(e.to_string()))?;
+        Ok(row.get::<i64, _>("count"))
+    }
 
-        if rows.is_empty() {
-            return Ok(None);
+    async fn get_primary_key(&self) -> Result<Vec<String>> {
+        Ok(self.primary_key()?.to_vec())
+    }
+
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+        let min = self.get_key_at("ASC", None, 0).await?;
+        let max = self.get_key_at("DESC", None, 0).await?;
+        Ok(min.zip(max))
+    }
+
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        let Some((min, max)) = self.get_id_range().await? else {
+            return Ok(Vec::new());
+        };
+        if let Some(ranges) = KeyRange::split_integer(&min, &max, chunks) {
+            return Ok(ranges);
         }
 
-        let records = rows
-            .into_iter()
-            .map(|row| {
-                let mut fields = HashMap::new();
-                for col in row.columns() {
-                    let name = col.name().to_string();
-                    let field_type = column_types
-                        .get(&name)
-                        .ok_or_else(|| Error::Type(format!("Unknown column: {}", name)))?;
-
-                    let dbsync_value =
-                        self.value_converter
-                            .to_dbsync_value(col, &row, &field_type)?;
-                    fields.insert(name, (dbsync_value, field_type.clone()));
-                }
-                Ok(Record { fields })
-            })
-            .collect::<Result<Vec<_>>>()?;
+        // 字符串和复合主键无法按数值切分, 按行号采样区间终点.
+        // 每次从上一个终点开始只跳过两个终点之间的行, 整个切分只扫描一遍主键索引
+        let total = self.get_total_records().await?;
+        let chunks = chunks.max(1) as i64;
+        let mut ends: Vec<KeyValue> = Vec::new();
+        let mut previous = -1;
+        for i in 1..chunks {
+            let offset = total * i / chunks - 1;
+            if offset <= previous {
+                continue;
+            }
+            let Some(end) = self
+                .get_key_at("ASC", ends.last(), offset - previous - 1)
+                .await?
+            else {
+                break;
+            };
+            if end == max {
+                break;
+            }
+            ends.push(end);
+            previous = offset;
+        }
+        ends.push(max);
+
+        Ok(KeyRange::from_boundaries(ends))
+    }
 
-        Ok(Some(DataBatch { records }))
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>> {
+        self.read_page(after, Some(end), self.config.batch_size)
+            .await
     }
 
-    async fn get_schema(&self) -> Result<String> {
+    async fn get_schema(&self) -> Result<TableSchema> {
         let pool = self
             .pool
             .as_ref()
             .ok_or_else(|| Error::Connection("Not connected".into()))?;
 
-        // 获取表的创建语句
-        let query = format!("SHOW CREATE TABLE {}", self.config.table);
-        let row = sqlx::query(&query)
-            .fetch_one(pool)
-            .await
-            .map_err(|e| Error::Read(e.to_string()))?;
+        load_table_schema(pool, &self.config.table, self.config.tinyint1_as_boolean).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(columns: &[&str]) -> Vec<String> {
+        columns.iter().map(|c| c.to_string()).collect()
+    }
 
-        let create_table: String = row.get(1); // 第二列是 Create Table 语句
+    #[test]
+    fn test_key_predicate_quotes_columns() {
+        assert_eq!(key_predicate(&key(&["order"]), ">"), "`order` > ?");
+        assert_eq!(
+            key_predicate(&key(&["order", "group"]), "<="),
+            "(`order`, `group`) <= (?, ?)"
+        );
+        // 列名中的反引号加倍转义, 不会提前结束标识符
+        assert_eq!(
+            key_predicate(&key(&["a` = 1 OR `b"]), ">"),
+            "`a`` = 1 OR ``b` > ?"
+        );
+    }
 
-        // 替换表名为 target_table
-        Ok(create_table.replace(&self.config.table, "target_table"))
+    #[test]
+    fn test_key_order_quotes_columns() {
+        assert_eq!(
+            key_order(&key(&["order", "group"]), "DESC"),
+            "`order` DESC, `group` DESC"
+        );
+        assert_eq!(key_columns(&key(&["order", "x`y"])), "`order`, `x``y`");
     }
 }
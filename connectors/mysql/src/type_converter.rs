@@ -1,68 +1,413 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeDelta, TimeZone};
 use dbsync_core::{
     error::{Error, Result},
-    types::{DbsyncType, DbsyncValue, TypeConverter, TypeMapper},
+    types::{DbsyncType, DbsyncValue, SpatialType, TypeConverter, TypeMapper, MICROS_PER_SECOND},
 };
-use hex;
-use sqlx::mysql::{MySqlColumn, MySqlRow};
-use sqlx::Column;
-use sqlx::Row;
+use sqlx::mysql::types::MySqlTime;
+use sqlx::mysql::{MySql, MySqlArguments, MySqlColumn, MySqlRow};
+use sqlx::query::Query;
+use sqlx::{Column, Row, ValueRef};
+
+// DECIMAL 的最大精度和最大小数位数
+const MAX_DECIMAL_PRECISION: i64 = 65;
+const MAX_DECIMAL_SCALE: i64 = 30;
 
 #[derive(Clone)]
 pub struct MySQLTypeMapper;
 
 impl TypeMapper for MySQLTypeMapper {
+    // 接受 DATA_TYPE 这样的类型名, 也接受 COLUMN_TYPE 这样带参数和修饰的完整类型,
+    // 如 DECIMAL(10,2), INT UNSIGNED, ENUM('a','b')
     fn to_dbsync_type(&self, source_type: &str) -> Result<DbsyncType> {
-        match source_type.to_uppercase().as_str() {
-            "TINYINT" => Ok(DbsyncType::TinyInt),
-            "SMALLINT" => Ok(DbsyncType::SmallInt),
-            "MEDIUMINT" | "INT" => Ok(DbsyncType::Int),
-            "BIGINT" => Ok(DbsyncType::BigInt),
-            "FLOAT" => Ok(DbsyncType::Float),
-            "DOUBLE" => Ok(DbsyncType::Double),
-            "DECIMAL" | "NUMERIC" => Ok(DbsyncType::Decimal(20, 6)),
-            "CHAR" => Ok(DbsyncType::Char(255)),
-            "VARCHAR" => Ok(DbsyncType::VarChar(255)),
-            "TEXT" | "MEDIUMTEXT" | "LONGTEXT" => Ok(DbsyncType::Text),
-            "DATE" => Ok(DbsyncType::Date),
-            "TIME" => Ok(DbsyncType::Time),
-            "DATETIME" => Ok(DbsyncType::DateTime),
-            "TIMESTAMP" => Ok(DbsyncType::Timestamp),
-            "BOOLEAN" | "TINYINT(1)" => Ok(DbsyncType::Boolean),
-            "BINARY" | "VARBINARY" => Ok(DbsyncType::Binary(255)),
-            "BLOB" => Ok(DbsyncType::Blob),
-            "JSON" => Ok(DbsyncType::Json),
-            _ => Err(Error::Type(format!("Unknown MySQL type: {}", source_type))),
-        }
+        let (name, params, unsigned) = parse_column_type(source_type)?;
+        let param = |index: usize, default: u32| -> Result<u32> {
+            params.get(index).map_or(Ok(default), |p| {
+                p.parse()
+                    .map_err(|_| Error::Type(format!("Invalid MySQL type: {}", source_type)))
+            })
+        };
+        let narrow = |value: u32| -> Result<u8> {
+            u8::try_from(value)
+                .map_err(|_| Error::Type(format!("Invalid MySQL type: {}", source_type)))
+        };
+        // 秒的小数位数, 0..=6
+        let fsp = || -> Result<u8> {
+            match param(0, 0)? {
+                fsp @ 0..=6 => Ok(fsp as u8),
+                _ => Err(Error::Type(format!("Invalid MySQL type: {}", source_type))),
+            }
+        };
+
+        let dbsync_type = match name.as_str() {
+            // BOOLEAN 在 MySQL 中存为 TINYINT(1), 但 TINYINT(1) 列可以存放 -128..=127,
+            // 只有按配置显式开启时才把 TINYINT(1) 列读作布尔值
+            "BOOL" | "BOOLEAN" => DbsyncType::Boolean,
+            "TINYINT" if unsigned => DbsyncType::UnsignedTinyInt,
+            "TINYINT" => DbsyncType::TinyInt,
+            "SMALLINT" if unsigned => DbsyncType::UnsignedSmallInt,
+            "SMALLINT" => DbsyncType::SmallInt,
+            "MEDIUMINT" if unsigned => DbsyncType::UnsignedMediumInt,
+            "MEDIUMINT" => DbsyncType::MediumInt,
+            "INT" | "INTEGER" if unsigned => DbsyncType::UnsignedInt,
+            "INT" | "INTEGER" => DbsyncType::Int,
+            "BIGINT" if unsigned => DbsyncType::UnsignedBigInt,
+            "BIGINT" => DbsyncType::BigInt,
+            "BIT" => match param(0, 1)? {
+                bits @ 1..=64 => DbsyncType::Bit(bits as u8),
+                _ => return Err(Error::Type(format!("Invalid MySQL type: {}", source_type))),
+            },
+            "FLOAT" => DbsyncType::Float,
+            "DOUBLE" | "REAL" => DbsyncType::Double,
+            // 未指定时 MySQL 的默认精度为 DECIMAL(10,0)
+            "DECIMAL" | "NUMERIC" => {
+                DbsyncType::Decimal(narrow(param(0, 10)?)?, narrow(param(1, 0)?)?)
+            }
+            "CHAR" => DbsyncType::Char(param(0, 1)?),
+            "VARCHAR" => DbsyncType::VarChar(param(0, 255)?),
+            "TINYTEXT" => DbsyncType::TinyText,
+            "TEXT" => DbsyncType::Text,
+            "MEDIUMTEXT" => DbsyncType::MediumText,
+            "LONGTEXT" => DbsyncType::LongText,
+            "ENUM" => DbsyncType::Enum(params),
+            "SET" => DbsyncType::Set(params),
+            "DATE" => DbsyncType::Date,
+            "TIME" => DbsyncType::Time(fsp()?),
+            "DATETIME" => DbsyncType::DateTime(fsp()?),
+            "TIMESTAMP" => DbsyncType::Timestamp(fsp()?),
+            "YEAR" => DbsyncType::Year,
+            "BINARY" => DbsyncType::Binary(param(0, 1)?),
+            "VARBINARY" => DbsyncType::VarBinary(param(0, 255)?),
+            "TINYBLOB" => DbsyncType::TinyBlob,
+            "BLOB" => DbsyncType::Blob,
+            "MEDIUMBLOB" => DbsyncType::MediumBlob,
+            "LONGBLOB" => DbsyncType::LongBlob,
+            "JSON" => DbsyncType::Json,
+            "GEOMETRY" => DbsyncType::Spatial(SpatialType::Geometry),
+            "POINT" => DbsyncType::Spatial(SpatialType::Point),
+            "LINESTRING" => DbsyncType::Spatial(SpatialType::LineString),
+            "POLYGON" => DbsyncType::Spatial(SpatialType::Polygon),
+            "MULTIPOINT" => DbsyncType::Spatial(SpatialType::MultiPoint),
+            "MULTILINESTRING" => DbsyncType::Spatial(SpatialType::MultiLineString),
+            "MULTIPOLYGON" => DbsyncType::Spatial(SpatialType::MultiPolygon),
+            // MySQL 8.0 的 DATA_TYPE 为 geomcollection
+            "GEOMETRYCOLLECTION" | "GEOMCOLLECTION" => {
+                DbsyncType::Spatial(SpatialType::GeometryCollection)
+            }
+            _ => return Err(Error::Type(format!("Unknown MySQL type: {}", source_type))),
+        };
+        Ok(dbsync_type)
     }
 
     fn to_target_type(&self, dbsync_type: &DbsyncType) -> Result<String> {
         match dbsync_type {
             DbsyncType::TinyInt => Ok("TINYINT".to_string()),
             DbsyncType::SmallInt => Ok("SMALLINT".to_string()),
+            DbsyncType::MediumInt => Ok("MEDIUMINT".to_string()),
             DbsyncType::Int => Ok("INT".to_string()),
             DbsyncType::BigInt => Ok("BIGINT".to_string()),
+            DbsyncType::UnsignedTinyInt => Ok("TINYINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedSmallInt => Ok("SMALLINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedMediumInt => Ok("MEDIUMINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedInt => Ok("INT UNSIGNED".to_string()),
+            DbsyncType::UnsignedBigInt => Ok("BIGINT UNSIGNED".to_string()),
+            DbsyncType::Bit(n) => Ok(format!("BIT({})", n)),
             DbsyncType::Float => Ok("FLOAT".to_string()),
             DbsyncType::Double => Ok("DOUBLE".to_string()),
             DbsyncType::Decimal(p, s) => Ok(format!("DECIMAL({},{})", p, s)),
+            // MySQL 没有不限位数的 DECIMAL, 使用最大精度, 写入时检查值能否放下
+            t if t.is_unconstrained_numeric() => Ok(format!(
+                "DECIMAL({},{})",
+                MAX_DECIMAL_PRECISION, MAX_DECIMAL_SCALE
+            )),
             DbsyncType::Numeric(p, s) => Ok(format!("NUMERIC({},{})", p, s)),
             DbsyncType::Char(n) => Ok(format!("CHAR({})", n)),
             DbsyncType::VarChar(n) => Ok(format!("VARCHAR({})", n)),
+            DbsyncType::TinyText => Ok("TINYTEXT".to_string()),
             DbsyncType::Text => Ok("TEXT".to_string()),
+            DbsyncType::MediumText => Ok("MEDIUMTEXT".to_string()),
+            DbsyncType::LongText => Ok("LONGTEXT".to_string()),
+            DbsyncType::Enum(values) => Ok(format!("ENUM({})", quote_members(values))),
+            DbsyncType::Set(values) => Ok(format!("SET({})", quote_members(values))),
             DbsyncType::Date => Ok("DATE".to_string()),
-            DbsyncType::Time => Ok("TIME".to_string()),
-            DbsyncType::DateTime => Ok("DATETIME".to_string()),
-            DbsyncType::Timestamp => Ok("TIMESTAMP".to_string()),
-            DbsyncType::Boolean => Ok("TINYINT(1)".to_string()),
-            DbsyncType::Binary(n) => Ok(format!("VARBINARY({})", n)),
+            DbsyncType::Time(fsp) => Ok(with_fsp("TIME", *fsp)),
+            DbsyncType::DateTime(fsp) => Ok(with_fsp("DATETIME", *fsp)),
+            DbsyncType::Timestamp(fsp) => Ok(with_fsp("TIMESTAMP", *fsp)),
+            DbsyncType::Year => Ok("YEAR".to_string()),
+            DbsyncType::Boolean => Ok("BOOLEAN".to_string()),
+            DbsyncType::Binary(n) => Ok(format!("BINARY({})", n)),
+            DbsyncType::VarBinary(n) => Ok(format!("VARBINARY({})", n)),
+            DbsyncType::TinyBlob => Ok("TINYBLOB".to_string()),
             DbsyncType::Blob => Ok("BLOB".to_string()),
+            DbsyncType::MediumBlob => Ok("MEDIUMBLOB".to_string()),
+            DbsyncType::LongBlob => Ok("LONGBLOB".to_string()),
             DbsyncType::Json => Ok("JSON".to_string()),
+            DbsyncType::Spatial(spatial) => Ok(match spatial {
+                SpatialType::Geometry => "GEOMETRY",
+                SpatialType::Point => "POINT",
+                SpatialType::LineString => "LINESTRING",
+                SpatialType::Polygon => "POLYGON",
+                SpatialType::MultiPoint => "MULTIPOINT",
+                SpatialType::MultiLineString => "MULTILINESTRING",
+                SpatialType::MultiPolygon => "MULTIPOLYGON",
+                SpatialType::GeometryCollection => "GEOMETRYCOLLECTION",
+            }
+            .to_string()),
+            // MySQL 没有 UUID 和数组类型, 分别以文本和 JSON 保存
+            DbsyncType::Uuid => Ok("CHAR(36)".to_string()),
+            DbsyncType::Array(_) => Ok("JSON".to_string()),
             DbsyncType::Null => Ok("NULL".to_string()),
         }
     }
 }
 
+// 把列类型拆成大写的类型名, 括号内的参数和是否 UNSIGNED.
+// ENUM / SET 的成员去掉引号并还原转义, 如 enum('a','it''s') → ["a", "it's"]
+// 读作布尔值的 TINYINT(1) 列只接受 0 和 1, 其他值报错而不是写成 true
+pub(crate) fn int_to_boolean(value: i64, column: &str) -> Result<bool> {
+    match value {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::Type(format!(
+            "Column {} has value {}, which is not a boolean; disable tinyint1_as_boolean to read it as TINYINT",
+            column, value
+        ))),
+    }
+}
+
+fn parse_column_type(source_type: &str) -> Result<(String, Vec<String>, bool)> {
+    let invalid = || Error::Type(format!("Invalid MySQL type: {}", source_type));
+    let source_type = source_type.trim();
+    let (name, rest) = match source_type.find('(') {
+        Some(open) => (&source_type[..open], &source_type[open + 1..]),
+        None => match source_type.split_once(' ') {
+            Some((name, modifiers)) => (name, modifiers),
+            None => (source_type, ""),
+        },
+    };
+
+    let mut params = Vec::new();
+    let mut modifiers = rest;
+    if source_type.contains('(') {
+        let mut current = String::new();
+        let mut quoted = false;
+        let mut chars = rest.char_indices().peekable();
+        let mut end = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\'' if quoted && chars.peek().map(|(_, c)| *c) == Some('\'') => {
+                    chars.next();
+                    current.push('\'');
+                }
+                '\'' => quoted = !quoted,
+                '\\' if quoted => current.push(chars.next().ok_or_else(invalid)?.1),
+                ',' if !quoted => params.push(std::mem::take(&mut current)),
+                ')' if !quoted => {
+                    end = Some(i);
+                    break;
+                }
+                c if quoted || !c.is_whitespace() => current.push(c),
+                _ => {}
+            }
+        }
+        let end = end.ok_or_else(invalid)?;
+        params.push(current);
+        modifiers = &rest[end + 1..];
+    }
+
+    let unsigned = modifiers
+        .split_whitespace()
+        .any(|m| m.eq_ignore_ascii_case("unsigned"));
+    Ok((name.trim().to_uppercase(), params, unsigned))
+}
+
+// ENUM / SET 成员列表, 与 MySQLDialect 的字符串字面量转义一致
+fn quote_members(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{}'", v.replace('\\', "\\\\").replace('\'', "''")))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+// 小数位数为 0 时省略括号, 与 COLUMN_TYPE 的写法一致
+fn with_fsp(name: &str, fsp: u8) -> String {
+    if fsp == 0 {
+        name.to_string()
+    } else {
+        format!("{}({})", name, fsp)
+    }
+}
+
+pub(crate) fn date_to_days(date: NaiveDate) -> i32 {
+    (date - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32
+}
+
+pub(crate) fn days_to_date(days: i32) -> Result<NaiveDate> {
+    DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(TimeDelta::days(days.into()))
+        .ok_or_else(|| Error::Type(format!("Date out of range: {} days", days)))
+}
+
+// 墙上时间按 UTC 解释得到的微秒数
+pub(crate) fn datetime_to_micros(datetime: NaiveDateTime) -> i64 {
+    datetime.and_utc().timestamp_micros()
+}
+
+pub(crate) fn micros_to_datetime(micros: i64) -> Result<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros)
+        .map(|dt| dt.naive_utc())
+        .ok_or_else(|| Error::Type(format!("Datetime out of range: {} us", micros)))
+}
+
+// MySQL TIME 的文本形式, 小时可超过 24, 如 -838:59:59.000000
+pub(crate) fn format_time(micros: i64) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.unsigned_abs();
+    let seconds = micros / MICROS_PER_SECOND as u64;
+    format!(
+        "{}{:02}:{:02}:{:02}.{:06}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        micros % MICROS_PER_SECOND as u64
+    )
+}
+
+// 值能否不经舍入写入 DECIMAL(65,30) 的列
+fn fits_max_decimal(value: &str) -> Result<bool> {
+    let decimal = value
+        .parse::<BigDecimal>()
+        .map_err(|e| Error::Type(format!("Invalid decimal {}: {}", value, e)))?
+        .normalized();
+    let (_, scale) = decimal.as_bigint_and_exponent();
+    let integer_digits = decimal.digits() as i64 - scale;
+    Ok(scale <= MAX_DECIMAL_SCALE && integer_digits <= MAX_DECIMAL_PRECISION - MAX_DECIMAL_SCALE)
+}
+
+// 值能否写入该类型的列, NULL 可以写入任何列
+fn accepts(value: &DbsyncValue, target_type: &DbsyncType) -> bool {
+    match value {
+        DbsyncValue::Null => true,
+        DbsyncValue::Integer(_) => {
+            target_type.is_signed_integer()
+                || target_type.is_unsigned_integer()
+                || *target_type == DbsyncType::Year
+        }
+        DbsyncValue::UInteger(_) => {
+            target_type.is_signed_integer() || target_type.is_unsigned_integer()
+        }
+        DbsyncValue::Float(_) => matches!(target_type, DbsyncType::Float | DbsyncType::Double),
+        DbsyncValue::Decimal(_) => matches!(
+            target_type,
+            DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _)
+        ),
+        // JSON 列接受 JSON 文本, 由服务器解析
+        DbsyncValue::String(_) => {
+            target_type.is_text() || matches!(target_type, DbsyncType::Json | DbsyncType::Uuid)
+        }
+        DbsyncValue::Date(_) => *target_type == DbsyncType::Date,
+        DbsyncValue::Time(_) => matches!(target_type, DbsyncType::Time(_)),
+        DbsyncValue::DateTime(_) => matches!(target_type, DbsyncType::DateTime(_)),
+        DbsyncValue::Timestamp(_) => matches!(target_type, DbsyncType::Timestamp(_)),
+        DbsyncValue::Boolean(_) => *target_type == DbsyncType::Boolean,
+        DbsyncValue::Binary(_) => target_type.is_binary(),
+        DbsyncValue::Json(_) => matches!(target_type, DbsyncType::Json | DbsyncType::Array(_)),
+    }
+}
+
 #[derive(Clone)]
-pub struct MySQLValueConverter;
+pub struct MySQLValueConverter {
+    // 会话时区, TIMESTAMP 在该时区的墙上时间与 UTC 时刻之间换算
+    time_zone: FixedOffset,
+}
+
+impl MySQLValueConverter {
+    pub fn new(time_zone: FixedOffset) -> Self {
+        Self { time_zone }
+    }
+
+    // 会话时区下的墙上时间转换为 UTC 微秒
+    pub(crate) fn timestamp_to_micros(&self, local: NaiveDateTime) -> Result<i64> {
+        self.time_zone
+            .from_local_datetime(&local)
+            .single()
+            .map(|dt| dt.timestamp_micros())
+            .ok_or_else(|| Error::Type(format!("Invalid timestamp: {}", local)))
+    }
+
+    // UTC 微秒转换为会话时区下的墙上时间
+    pub(crate) fn micros_to_timestamp(&self, micros: i64) -> Result<NaiveDateTime> {
+        DateTime::from_timestamp_micros(micros)
+            .map(|dt| dt.with_timezone(&self.time_zone).naive_local())
+            .ok_or_else(|| Error::Type(format!("Timestamp out of range: {} us", micros)))
+    }
+
+    // 按值的原生类型绑定参数, 服务器不再需要从文本解析,
+    // 二进制数据按字节发送, 大数值的 DECIMAL 不丢失精度
+    pub(crate) fn bind<'q>(
+        &self,
+        query: Query<'q, MySql, MySqlArguments>,
+        value: &DbsyncValue,
+    ) -> Result<Query<'q, MySql, MySqlArguments>> {
+        Ok(match value {
+            DbsyncValue::Null => query.bind(None::<String>),
+            DbsyncValue::Integer(i) => query.bind(*i),
+            DbsyncValue::UInteger(u) => query.bind(*u),
+            DbsyncValue::Float(f) => query.bind(*f),
+            DbsyncValue::Decimal(d) => query.bind(
+                d.parse::<BigDecimal>()
+                    .map_err(|e| Error::Type(format!("Invalid decimal {}: {}", d, e)))?,
+            ),
+            DbsyncValue::String(s) => query.bind(s.clone()),
+            DbsyncValue::Date(days) => query.bind(days_to_date(*days)?),
+            DbsyncValue::Time(micros) => query.bind(
+                MySqlTime::try_from(TimeDelta::microseconds(*micros))
+                    .map_err(|e| Error::Type(e.to_string()))?,
+            ),
+            DbsyncValue::DateTime(micros) => query.bind(micros_to_datetime(*micros)?),
+            DbsyncValue::Timestamp(micros) => query.bind(self.micros_to_timestamp(*micros)?),
+            DbsyncValue::Boolean(b) => query.bind(*b),
+            DbsyncValue::Binary(b) => query.bind(b.clone()),
+            // 以 JSON 文本发送, 由服务器解析为 JSON 文档
+            DbsyncValue::Json(json) => query.bind(json.to_string()),
+        })
+    }
+
+    // 检查值能否写入 target_type 类型的列, 再按原生类型绑定
+    pub(crate) fn bind_as<'q>(
+        &self,
+        query: Query<'q, MySql, MySqlArguments>,
+        value: &DbsyncValue,
+        target_type: &DbsyncType,
+    ) -> Result<Query<'q, MySql, MySqlArguments>> {
+        if !accepts(value, target_type) {
+            return Err(Error::Type(format!(
+                "Type mismatch: value {:?} cannot be converted to target type {:?}",
+                value, target_type
+            )));
+        }
+        if let DbsyncValue::Decimal(d) = value {
+            if target_type.is_unconstrained_numeric() && !fits_max_decimal(d)? {
+                return Err(Error::Type(format!(
+                    "Decimal {} does not fit in DECIMAL({},{})",
+                    d, MAX_DECIMAL_PRECISION, MAX_DECIMAL_SCALE
+                )));
+            }
+        }
+        self.bind(query, value)
+    }
+}
+
+impl Default for MySQLValueConverter {
+    fn default() -> Self {
+        Self::new(FixedOffset::east_opt(0).unwrap())
+    }
+}
 
 impl TypeConverter for MySQLValueConverter {
     type Column = MySqlColumn;
@@ -74,52 +419,118 @@ impl TypeConverter for MySQLValueConverter {
         row: &Self::Row,
         dbsync_type: &DbsyncType,
     ) -> Result<DbsyncValue> {
+        // 任何类型的列都可能为 NULL, 先于按类型解码判断
+        let raw = row
+            .try_get_raw(column.ordinal())
+            .map_err(|e| Error::Type(e.to_string()))?;
+        if raw.is_null() {
+            return Ok(DbsyncValue::Null);
+        }
+
         match dbsync_type {
-            DbsyncType::TinyInt | DbsyncType::SmallInt | DbsyncType::Int | DbsyncType::BigInt => {
-                row.try_get::<i64, _>(column.ordinal())
-                    .map(DbsyncValue::Integer)
-                    .map_err(|e| Error::Type(e.to_string()))
-            }
+            DbsyncType::TinyInt
+            | DbsyncType::SmallInt
+            | DbsyncType::MediumInt
+            | DbsyncType::Int
+            | DbsyncType::BigInt => row
+                .try_get::<i64, _>(column.ordinal())
+                .map(DbsyncValue::Integer)
+                .map_err(|e| Error::Type(e.to_string())),
+
+            // BIT 列按大端字节解码为 u64
+            DbsyncType::UnsignedTinyInt
+            | DbsyncType::UnsignedSmallInt
+            | DbsyncType::UnsignedMediumInt
+            | DbsyncType::UnsignedInt
+            | DbsyncType::UnsignedBigInt
+            | DbsyncType::Bit(_) => row
+                .try_get_unchecked::<u64, _>(column.ordinal())
+                .map(DbsyncValue::UInteger)
+                .map_err(|e| Error::Type(e.to_string())),
+
+            DbsyncType::Year => row
+                .try_get_unchecked::<u16, _>(column.ordinal())
+                .map(|year| DbsyncValue::Integer(year.into()))
+                .map_err(|e| Error::Type(e.to_string())),
 
             DbsyncType::Float | DbsyncType::Double => row
                 .try_get::<f64, _>(column.ordinal())
                 .map(DbsyncValue::Float)
                 .map_err(|e| Error::Type(e.to_string())),
 
+            // 服务器以文本传输 DECIMAL, 直接保留原文, 不受 rust 侧十进制类型的精度限制
             DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _) => row
-                .try_get::<rust_decimal::Decimal, _>(column.ordinal())
-                .map(|d| DbsyncValue::Decimal(d.to_string()))
+                .try_get_unchecked::<String, _>(column.ordinal())
+                .map(DbsyncValue::Decimal)
                 .map_err(|e| Error::Type(e.to_string())),
 
-            DbsyncType::Char(_) | DbsyncType::VarChar(_) | DbsyncType::Text => row
-                .try_get::<String, _>(column.ordinal())
+            // 结果集中 ENUM / SET 列的类型为 STRING, 按文本读取
+            DbsyncType::Char(_)
+            | DbsyncType::VarChar(_)
+            | DbsyncType::TinyText
+            | DbsyncType::Text
+            | DbsyncType::MediumText
+            | DbsyncType::LongText
+            | DbsyncType::Enum(_)
+            | DbsyncType::Set(_)
+            | DbsyncType::Uuid => row
+                .try_get_unchecked::<String, _>(column.ordinal())
                 .map(DbsyncValue::String)
                 .map_err(|e| Error::Type(e.to_string())),
 
-            DbsyncType::Date | DbsyncType::Time | DbsyncType::DateTime | DbsyncType::Timestamp => {
-                row.try_get::<chrono::NaiveDateTime, _>(column.ordinal())
-                    .or_else(|_| {
-                        row.try_get::<chrono::DateTime<chrono::Utc>, _>(column.ordinal())
-                            .map(|dt| dt.naive_utc())
-                    })
-                    .map(|dt| DbsyncValue::DateTime(dt.timestamp()))
-                    .map_err(|e| Error::Type(e.to_string()))
+            DbsyncType::Date => row
+                .try_get_unchecked::<NaiveDate, _>(column.ordinal())
+                .map(|date| DbsyncValue::Date(date_to_days(date)))
+                .map_err(|e| Error::Type(e.to_string())),
+
+            // TIME 表示时长, 可为负且超过 24 小时, 不能用 NaiveTime 读取
+            DbsyncType::Time(_) => row
+                .try_get_unchecked::<TimeDelta, _>(column.ordinal())
+                .map_err(|e| Error::Type(e.to_string()))?
+                .num_microseconds()
+                .map(DbsyncValue::Time)
+                .ok_or_else(|| Error::Type("TIME value out of range".to_string())),
+
+            DbsyncType::DateTime(_) => row
+                .try_get_unchecked::<NaiveDateTime, _>(column.ordinal())
+                .map(|dt| DbsyncValue::DateTime(datetime_to_micros(dt)))
+                .map_err(|e| Error::Type(e.to_string())),
+
+            // 服务器按会话时区返回 TIMESTAMP 的墙上时间
+            DbsyncType::Timestamp(_) => {
+                let local = row
+                    .try_get_unchecked::<NaiveDateTime, _>(column.ordinal())
+                    .map_err(|e| Error::Type(e.to_string()))?;
+                self.timestamp_to_micros(local).map(DbsyncValue::Timestamp)
             }
 
+            // 按整数读取, 0 和 1 以外的值无法表示为布尔值
             DbsyncType::Boolean => row
-                .try_get::<bool, _>(column.ordinal())
-                .map(DbsyncValue::Boolean)
-                .map_err(|e| Error::Type(e.to_string())),
+                .try_get_unchecked::<i8, _>(column.ordinal())
+                .map_err(|e| Error::Type(e.to_string()))
+                .and_then(|value| int_to_boolean(value.into(), column.name()))
+                .map(DbsyncValue::Boolean),
 
-            DbsyncType::Binary(_) | DbsyncType::Blob => row
-                .try_get::<Vec<u8>, _>(column.ordinal())
+            // 空间类型读取的是 MySQL 内部格式: 4 字节小端 SRID + WKB
+            DbsyncType::Binary(_)
+            | DbsyncType::VarBinary(_)
+            | DbsyncType::TinyBlob
+            | DbsyncType::Blob
+            | DbsyncType::MediumBlob
+            | DbsyncType::LongBlob
+            | DbsyncType::Spatial(_) => row
+                .try_get_unchecked::<Vec<u8>, _>(column.ordinal())
                 .map(DbsyncValue::Binary)
                 .map_err(|e| Error::Type(e.to_string())),
 
-            DbsyncType::Json => row
-                .try_get::<String, _>(column.ordinal())
-                .map(DbsyncValue::String)
-                .map_err(|e| Error::Type(e.to_string())),
+            DbsyncType::Json | DbsyncType::Array(_) => {
+                let text = row
+                    .try_get_unchecked::<String, _>(column.ordinal())
+                    .map_err(|e| Error::Type(e.to_string()))?;
+                serde_json::from_str(&text)
+                    .map(DbsyncValue::Json)
+                    .map_err(|e| Error::Type(format!("Invalid JSON {}: {}", text, e)))
+            }
 
             DbsyncType::Null => Ok(DbsyncValue::Null),
         }
@@ -127,13 +538,20 @@ impl TypeConverter for MySQLValueConverter {
 
     fn from_dbsync_value(&self, value: &DbsyncValue, target_type: &DbsyncType) -> Result<String> {
         match (value, target_type) {
-            (DbsyncValue::Null, _) => Ok("NULL".to_string()),
+            // NULL 没有字符串形式, 需要绑定为 SQL NULL
+            (DbsyncValue::Null, _) => Err(Error::Type(
+                "NULL cannot be converted to a string, bind it as SQL NULL".to_string(),
+            )),
 
             // 整数类型
-            (
-                DbsyncValue::Integer(i),
-                DbsyncType::TinyInt | DbsyncType::SmallInt | DbsyncType::Int | DbsyncType::BigInt,
-            ) => Ok(i.to_string()),
+            (DbsyncValue::Integer(i), t)
+                if t.is_signed_integer() || t.is_unsigned_integer() || *t == DbsyncType::Year =>
+            {
+                Ok(i.to_string())
+            }
+            (DbsyncValue::UInteger(u), t) if t.is_signed_integer() || t.is_unsigned_integer() => {
+                Ok(u.to_string())
+            }
 
             // 浮点类型
             (DbsyncValue::Float(f), DbsyncType::Float | DbsyncType::Double) => Ok(f.to_string()),
@@ -144,26 +562,22 @@ impl TypeConverter for MySQLValueConverter {
             }
 
             // 字符串类型
-            (
-                DbsyncValue::String(s),
-                DbsyncType::Char(_) | DbsyncType::VarChar(_) | DbsyncType::Text,
-            ) => Ok(format!("{}", s)),
-
-            (DbsyncValue::DateTime(ts), DbsyncType::DateTime) => {
-                // 将 Unix 时间戳转换为 MySQL 格式的日期时间字符串
-                let dt = chrono::NaiveDateTime::from_timestamp_opt(*ts, 0)
-                    .ok_or_else(|| Error::Type("Invalid timestamp".to_string()))?;
-                Ok(format!("{}", dt.format("%Y-%m-%d %H:%M:%S")))
-            }
+            (DbsyncValue::String(s), t) if t.is_text() || *t == DbsyncType::Uuid => Ok(s.clone()),
 
-            (
-                DbsyncValue::DateTime(ts),
-                DbsyncType::DateTime | DbsyncType::Timestamp | DbsyncType::Date | DbsyncType::Time,
-            ) => {
-                let dt = chrono::NaiveDateTime::from_timestamp_opt(*ts, 0)
-                    .ok_or_else(|| Error::Type("Invalid timestamp".to_string()))?;
-                Ok(format!("{}", dt.format("%Y-%m-%d %H:%M:%S")))
+            // 时间类型, 始终写出 6 位小数, 由服务器按列的精度舍入
+            (DbsyncValue::Date(days), DbsyncType::Date) => {
+                Ok(days_to_date(*days)?.format("%Y-%m-%d").to_string())
+            }
+            (DbsyncValue::Time(micros), DbsyncType::Time(_)) => Ok(format_time(*micros)),
+            (DbsyncValue::DateTime(micros), DbsyncType::DateTime(_)) => {
+                Ok(micros_to_datetime(*micros)?
+                    .format(DATETIME_FORMAT)
+                    .to_string())
             }
+            (DbsyncValue::Timestamp(micros), DbsyncType::Timestamp(_)) => Ok(self
+                .micros_to_timestamp(*micros)?
+                .format(DATETIME_FORMAT)
+                .to_string()),
 
             // 布尔类型
             (DbsyncValue::Boolean(b), DbsyncType::Boolean) => {
@@ -171,12 +585,13 @@ impl TypeConverter for MySQLValueConverter {
             }
 
             // 二进制类型
-            (DbsyncValue::Binary(b), DbsyncType::Binary(_) | DbsyncType::Blob) => {
-                Ok(format!("0x{}", hex::encode(b)))
-            }
+            (DbsyncValue::Binary(b), t) if t.is_binary() => Ok(format!("0x{}", hex::encode(b))),
 
             // JSON 类型
-            (DbsyncValue::String(s), DbsyncType::Json) => Ok(format!("'{}'", s)),
+            (DbsyncValue::Json(json), DbsyncType::Json | DbsyncType::Array(_)) => {
+                Ok(json.to_string())
+            }
+            (DbsyncValue::String(s), DbsyncType::Json) => Ok(s.clone()),
 
             // 类型不匹配的情况
             (v, t) => Err(Error::Type(format!(
@@ -186,3 +601,301 @@ impl TypeConverter for MySQLValueConverter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_types() -> Vec<DbsyncType> {
+        vec![
+            DbsyncType::TinyInt,
+            DbsyncType::SmallInt,
+            DbsyncType::MediumInt,
+            DbsyncType::Int,
+            DbsyncType::BigInt,
+            DbsyncType::UnsignedTinyInt,
+            DbsyncType::UnsignedSmallInt,
+            DbsyncType::UnsignedMediumInt,
+            DbsyncType::UnsignedInt,
+            DbsyncType::UnsignedBigInt,
+            DbsyncType::Bit(1),
+            DbsyncType::Bit(64),
+            DbsyncType::Float,
+            DbsyncType::Double,
+            DbsyncType::Decimal(65, 30),
+            DbsyncType::Char(3),
+            DbsyncType::VarChar(1000),
+            DbsyncType::TinyText,
+            DbsyncType::Text,
+            DbsyncType::MediumText,
+            DbsyncType::LongText,
+            DbsyncType::Enum(vec!["a".into(), "it's".into(), "c,d".into()]),
+            DbsyncType::Set(vec!["x".into(), "back\\slash".into()]),
+            DbsyncType::Date,
+            DbsyncType::Time(0),
+            DbsyncType::Time(3),
+            DbsyncType::DateTime(0),
+            DbsyncType::DateTime(6),
+            DbsyncType::Timestamp(0),
+            DbsyncType::Timestamp(6),
+            DbsyncType::Year,
+            DbsyncType::Boolean,
+            DbsyncType::Binary(16),
+            DbsyncType::VarBinary(2048),
+            DbsyncType::TinyBlob,
+            DbsyncType::Blob,
+            DbsyncType::MediumBlob,
+            DbsyncType::LongBlob,
+            DbsyncType::Json,
+            DbsyncType::Spatial(SpatialType::Geometry),
+            DbsyncType::Spatial(SpatialType::Point),
+            DbsyncType::Spatial(SpatialType::LineString),
+            DbsyncType::Spatial(SpatialType::Polygon),
+            DbsyncType::Spatial(SpatialType::MultiPoint),
+            DbsyncType::Spatial(SpatialType::MultiLineString),
+            DbsyncType::Spatial(SpatialType::MultiPolygon),
+            DbsyncType::Spatial(SpatialType::GeometryCollection),
+        ]
+    }
+
+    // 生成的列类型再解析回来应得到同一个 DbsyncType
+    #[test]
+    fn test_type_round_trip() {
+        let mapper = MySQLTypeMapper;
+        for dbsync_type in all_types() {
+            let target = mapper.to_target_type(&dbsync_type).unwrap();
+            assert_eq!(
+                mapper.to_dbsync_type(&target).unwrap(),
+                dbsync_type,
+                "{}",
+                target
+            );
+        }
+    }
+
+    // MySQL 8.0 INFORMATION_SCHEMA.COLUMNS.COLUMN_TYPE 的写法
+    #[test]
+    fn test_parse_information_schema_types() {
+        let mapper = MySQLTypeMapper;
+        let cases = [
+            ("bigint unsigned", DbsyncType::UnsignedBigInt),
+            ("int(10) unsigned zerofill", DbsyncType::UnsignedInt),
+            ("mediumint", DbsyncType::MediumInt),
+            ("tinyint(1)", DbsyncType::TinyInt),
+            ("boolean", DbsyncType::Boolean),
+            ("tinyint(1) unsigned", DbsyncType::UnsignedTinyInt),
+            ("bit(7)", DbsyncType::Bit(7)),
+            ("year", DbsyncType::Year),
+            ("double(10,2)", DbsyncType::Double),
+            ("datetime(6)", DbsyncType::DateTime(6)),
+            ("timestamp", DbsyncType::Timestamp(0)),
+            ("time(3)", DbsyncType::Time(3)),
+            ("decimal", DbsyncType::Decimal(10, 0)),
+            (
+                "enum('small','medium')",
+                DbsyncType::Enum(vec!["small".into(), "medium".into()]),
+            ),
+            (
+                "set('a b','it''s','x\\\\y')",
+                DbsyncType::Set(vec!["a b".into(), "it's".into(), "x\\y".into()]),
+            ),
+            (
+                "geomcollection",
+                DbsyncType::Spatial(SpatialType::GeometryCollection),
+            ),
+            ("varbinary(16)", DbsyncType::VarBinary(16)),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(
+                mapper.to_dbsync_type(source).unwrap(),
+                expected,
+                "{}",
+                source
+            );
+        }
+
+        assert!(mapper.to_dbsync_type("vector(3)").is_err());
+        assert!(mapper.to_dbsync_type("enum('a'").is_err());
+        assert!(mapper.to_dbsync_type("bit(100)").is_err());
+        assert!(mapper.to_dbsync_type("datetime(7)").is_err());
+    }
+
+    #[test]
+    fn test_temporal_values() {
+        let converter = MySQLValueConverter::default();
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(days_to_date(date_to_days(date)).unwrap(), date);
+        assert_eq!(
+            date_to_days(NaiveDate::from_ymd_opt(1969, 12, 31).unwrap()),
+            -1
+        );
+        assert_eq!(
+            converter
+                .from_dbsync_value(&DbsyncValue::Date(date_to_days(date)), &DbsyncType::Date)
+                .unwrap(),
+            "2024-02-29"
+        );
+
+        let time = -((838 * 3600 + 59 * 60 + 59) * MICROS_PER_SECOND + 5);
+        assert_eq!(format_time(time), "-838:59:59.000005");
+        assert_eq!(format_time(MICROS_PER_SECOND / 2), "00:00:00.500000");
+
+        let datetime = date.and_hms_micro_opt(23, 59, 59, 123_456).unwrap();
+        let micros = datetime_to_micros(datetime);
+        assert_eq!(micros_to_datetime(micros).unwrap(), datetime);
+        assert_eq!(
+            converter
+                .from_dbsync_value(&DbsyncValue::DateTime(micros), &DbsyncType::DateTime(6))
+                .unwrap(),
+            "2024-02-29 23:59:59.123456"
+        );
+
+        // 值和列类型必须一致, DATETIME 不带时区, 不能当作 TIMESTAMP 写入
+        assert!(converter
+            .from_dbsync_value(&DbsyncValue::DateTime(micros), &DbsyncType::Timestamp(6))
+            .is_err());
+    }
+
+    #[test]
+    fn test_int_to_boolean() {
+        assert!(!int_to_boolean(0, "flag").unwrap());
+        assert!(int_to_boolean(1, "flag").unwrap());
+        for value in [2, 127, -1, -128] {
+            assert!(
+                matches!(int_to_boolean(value, "flag"), Err(Error::Type(msg)) if msg.contains("flag")),
+                "{}",
+                value
+            );
+        }
+    }
+
+    // NULL 由 sink 绑定为 SQL NULL, 不能变成字符串 "NULL"
+    #[test]
+    fn test_null_has_no_string_form() {
+        let converter = MySQLValueConverter::default();
+        for dbsync_type in all_types() {
+            assert!(converter
+                .from_dbsync_value(&DbsyncValue::Null, &dbsync_type)
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_bind_native_values() {
+        let converter = MySQLValueConverter::default();
+        let cases = [
+            (DbsyncValue::Integer(-1), DbsyncType::MediumInt),
+            (DbsyncValue::Integer(2024), DbsyncType::Year),
+            (DbsyncValue::UInteger(u64::MAX), DbsyncType::UnsignedBigInt),
+            (DbsyncValue::UInteger(0b101), DbsyncType::Bit(3)),
+            (DbsyncValue::Float(1.5), DbsyncType::Float),
+            (
+                DbsyncValue::Decimal("1234567890123456789012345678.0123456789".into()),
+                DbsyncType::Decimal(38, 10),
+            ),
+            (DbsyncValue::String("a,c".into()), DbsyncType::Set(vec![])),
+            (DbsyncValue::String("{\"a\": 1}".into()), DbsyncType::Json),
+            (
+                DbsyncValue::Json(serde_json::json!({"a": [1, "it's"]})),
+                DbsyncType::Json,
+            ),
+            (DbsyncValue::Date(-1), DbsyncType::Date),
+            (DbsyncValue::Time(-1), DbsyncType::Time(6)),
+            (DbsyncValue::DateTime(0), DbsyncType::DateTime(0)),
+            (DbsyncValue::Timestamp(1), DbsyncType::Timestamp(6)),
+            (DbsyncValue::Boolean(true), DbsyncType::Boolean),
+            (DbsyncValue::Binary(vec![0, 0xff]), DbsyncType::Binary(2)),
+            (
+                DbsyncValue::Binary(vec![0; 25]),
+                DbsyncType::Spatial(SpatialType::Point),
+            ),
+            (DbsyncValue::Null, DbsyncType::LongBlob),
+        ];
+        for (value, target_type) in cases {
+            assert!(
+                converter
+                    .bind_as(sqlx::query("SELECT ?"), &value, &target_type)
+                    .is_ok(),
+                "{:?} as {:?}",
+                value,
+                target_type
+            );
+        }
+
+        let mismatches = [
+            (DbsyncValue::String("0x00".into()), DbsyncType::Blob),
+            (DbsyncValue::Binary(vec![1]), DbsyncType::Json),
+            (DbsyncValue::Json(serde_json::json!("a")), DbsyncType::Text),
+            (DbsyncValue::Float(1.0), DbsyncType::Decimal(10, 2)),
+            (DbsyncValue::DateTime(0), DbsyncType::Date),
+        ];
+        for (value, target_type) in mismatches {
+            assert!(converter
+                .bind_as(sqlx::query("SELECT ?"), &value, &target_type)
+                .is_err());
+        }
+        assert!(converter
+            .bind(sqlx::query("SELECT ?"), &DbsyncValue::Decimal("abc".into()))
+            .is_err());
+    }
+
+    // 不限位数的 NUMERIC 建为 DECIMAL(65,30), 放不下的值报错而不是被舍入
+    #[test]
+    fn test_unconstrained_numeric() {
+        let mapper = MySQLTypeMapper;
+        assert_eq!(
+            mapper.to_target_type(&DbsyncType::Numeric(0, 0)).unwrap(),
+            "DECIMAL(65,30)"
+        );
+
+        let converter = MySQLValueConverter::default();
+        let bind = |d: &str| {
+            converter.bind_as(
+                sqlx::query("SELECT ?"),
+                &DbsyncValue::Decimal(d.into()),
+                &DbsyncType::Numeric(0, 0),
+            )
+        };
+        let integer = "9".repeat(35);
+        let fraction = "0.".to_string() + &"9".repeat(30);
+        for d in [
+            integer.as_str(),
+            fraction.as_str(),
+            "-1.50000000000000000000000000000000000",
+            "1E+34",
+            "0",
+        ] {
+            assert!(bind(d).is_ok(), "{}", d);
+        }
+        let integer = "1".repeat(36);
+        let fraction = "0.".to_string() + &"0".repeat(30) + "1";
+        for d in [integer.as_str(), fraction.as_str(), "1E+35", "abc"] {
+            assert!(matches!(bind(d), Err(Error::Type(_))), "{}", d);
+        }
+    }
+
+    // TIMESTAMP 按会话时区的墙上时间读写
+    #[test]
+    fn test_timestamp_time_zone() {
+        let converter = MySQLValueConverter::new(FixedOffset::east_opt(8 * 3600).unwrap());
+        let local = NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_micro_opt(8, 0, 0, 1)
+            .unwrap();
+        let micros = converter.timestamp_to_micros(local).unwrap();
+        assert_eq!(micros, 1_704_067_200 * MICROS_PER_SECOND + 1);
+        assert_eq!(converter.micros_to_timestamp(micros).unwrap(), local);
+        assert_eq!(
+            converter
+                .from_dbsync_value(&DbsyncValue::Timestamp(micros), &DbsyncType::Timestamp(6))
+                .unwrap(),
+            "2024-01-01 08:00:00.000001"
+        );
+        assert_eq!(
+            MySQLValueConverter::default()
+                .from_dbsync_value(&DbsyncValue::Timestamp(micros), &DbsyncType::Timestamp(6))
+                .unwrap(),
+            "2024-01-01 00:00:00.000001"
+        );
+    }
+}
@@ -0,0 +1,12 @@
+mod config;
+mod replication;
+mod schema;
+mod sink;
+mod source;
+mod type_converter;
+
+pub use config::{PostgresReplicationConfig, PostgresSinkConfig, PostgresSourceConfig, WriteMode};
+pub use replication::{Lsn, PostgresReplicationSource, ReplicationPosition};
+pub use schema::PostgresDialect;
+pub use sink::PostgresSink;
+pub use source::PostgresSource;
@@ -0,0 +1,1050 @@
+use crate::config::{connect_options, PostgresReplicationConfig};
+use crate::schema::{load_table_schema, quote_identifier};
+use crate::type_converter::PostgresValueConverter;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchField, BatchSchema, ConnectorConfig, Context, DataBatch, Operation, Record, Source,
+        StreamBatch, StreamingSource,
+    },
+    error::{Error, Result},
+    types::{DbsyncValue, TypeConverter},
+};
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::Row;
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::{info, warn};
+
+// WAL 中的位置, 文本形式为高 32 位和低 32 位的十六进制, 如 0/16B3748
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Lsn(pub u64);
+
+impl FromStr for Lsn {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        let invalid = || Error::Read(format!("Invalid LSN: {}", text));
+        let (high, low) = text.split_once('/').ok_or_else(invalid)?;
+        let high = u32::from_str_radix(high, 16).map_err(|_| invalid())?;
+        let low = u32::from_str_radix(low, 16).map_err(|_| invalid())?;
+        Ok(Lsn((u64::from(high) << 32) | u64::from(low)))
+    }
+}
+
+impl TryFrom<String> for Lsn {
+    type Error = Error;
+
+    fn try_from(text: String) -> Result<Self> {
+        text.parse()
+    }
+}
+
+impl From<Lsn> for String {
+    fn from(lsn: Lsn) -> Self {
+        lsn.to_string()
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 & 0xffff_ffff)
+    }
+}
+
+// 复制槽中的读取位置, 作为 checkpoint 的 position 保存
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReplicationPosition {
+    // 最后一个已输出事务的提交记录结束位置, 复制槽确认到这里后不会再解码该事务
+    pub lsn: Lsn,
+}
+
+// pgoutput 元组中的一列
+#[derive(Clone, Debug, PartialEq)]
+enum TupleValue {
+    Null,
+    // 未修改的 TOAST 值, 消息中不包含
+    Unchanged,
+    Text(String),
+}
+
+// 解析 pgoutput 第 1 版协议中用到的消息
+#[derive(Debug, PartialEq)]
+enum Message {
+    Begin,
+    Commit {
+        end_lsn: Lsn,
+    },
+    Relation {
+        id: u32,
+        columns: Vec<String>,
+    },
+    Insert {
+        relation: u32,
+        new: Vec<TupleValue>,
+    },
+    // old 的第二项表示是否为完整的行 (REPLICA IDENTITY FULL), 否则只有主键列
+    Update {
+        relation: u32,
+        old: Option<(Vec<TupleValue>, bool)>,
+        new: Vec<TupleValue>,
+    },
+    Delete {
+        relation: u32,
+        old: Vec<TupleValue>,
+    },
+    Truncate {
+        relations: Vec<u32>,
+    },
+    Other,
+}
+
+// 按顺序读取消息中的字段, 整数均为大端
+struct MessageReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> MessageReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(Error::Read("Truncated pgoutput message".into()));
+        }
+        let (head, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn i16(&mut self) -> Result<i16> {
+        Ok(i16::from_be_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_be_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_be_bytes(self.take(8)?.try_into().unwrap()))
+    }
+
+    fn text(&mut self, len: usize) -> Result<String> {
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| Error::Read("pgoutput message is not valid UTF-8".into()))
+    }
+
+    // 以 \0 结尾的字符串
+    fn cstring(&mut self) -> Result<String> {
+        let len = self
+            .data
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| Error::Read("Truncated pgoutput message".into()))?;
+        let text = self.text(len)?;
+        self.take(1)?;
+        Ok(text)
+    }
+
+    fn tuple(&mut self) -> Result<Vec<TupleValue>> {
+        let count = self.i16()?;
+        (0..count)
+            .map(|_| match self.u8()? {
+                b'n' => Ok(TupleValue::Null),
+                b'u' => Ok(TupleValue::Unchanged),
+                b't' => {
+                    let len = self.u32()? as usize;
+                    Ok(TupleValue::Text(self.text(len)?))
+                }
+                kind => Err(Error::Read(format!(
+                    "Unsupported pgoutput tuple value kind {:?}",
+                    kind as char
+                ))),
+            })
+            .collect()
+    }
+}
+
+fn parse_message(data: &[u8]) -> Result<Message> {
+    let mut reader = MessageReader { data };
+    let message = match reader.u8()? {
+        b'B' => Message::Begin,
+        b'C' => {
+            reader.u8()?; // flags
+            reader.u64()?; // 提交记录的起始位置
+            Message::Commit {
+                end_lsn: Lsn(reader.u64()?),
+            }
+        }
+        b'R' => {
+            let id = reader.u32()?;
+            reader.cstring()?; // namespace
+            reader.cstring()?; // 表名
+            reader.u8()?; // replica identity
+            let count = reader.i16()?;
+            let columns = (0..count)
+                .map(|_| {
+                    reader.u8()?; // flags
+                    let name = reader.cstring()?;
+                    reader.u32()?; // 类型 oid
+                    reader.u32()?; // typmod
+                    Ok(name)
+                })
+                .collect::<Result<_>>()?;
+            Message::Relation { id, columns }
+        }
+        b'I' => {
+            let relation = reader.u32()?;
+            reader.u8()?; // 'N'
+            Message::Insert {
+                relation,
+                new: reader.tuple()?,
+            }
+        }
+        b'U' => {
+            let relation = reader.u32()?;
+            let old = match reader.u8()? {
+                kind @ (b'K' | b'O') => {
+                    let old = reader.tuple()?;
+                    reader.u8()?; // 'N'
+                    Some((old, kind == b'O'))
+                }
+                _ => None,
+            };
+            Message::Update {
+                relation,
+                old,
+                new: reader.tuple()?,
+            }
+        }
+        b'D' => {
+            let relation = reader.u32()?;
+            reader.u8()?; // 'K' 或 'O'
+            Message::Delete {
+                relation,
+                old: reader.tuple()?,
+            }
+        }
+        b'T' => {
+            let count = reader.u32()?;
+            reader.u8()?; // options
+            Message::Truncate {
+                relations: (0..count).map(|_| reader.u32()).collect::<Result<_>>()?,
+            }
+        }
+        _ => Message::Other,
+    };
+    Ok(message)
+}
+
+// 源表的一行变更, 值为 pgoutput 输出的文本, None 表示 NULL
+#[derive(Clone, Debug, PartialEq)]
+struct Change {
+    op: Operation,
+    values: Vec<Option<String>>,
+    before: Option<Vec<Option<String>>>,
+    // 未修改且消息中没有值的 TOAST 列的下标, 输出时不包含这些列
+    unchanged: Vec<usize>,
+    // 所在事务的提交结束位置
+    lsn: Lsn,
+}
+
+// 行类型的文本形式, 如 ("1","it's",), 空字段为 NULL
+fn row_literal(values: &[Option<String>]) -> String {
+    let fields = values
+        .iter()
+        .map(|value| match value {
+            Some(text) => format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\"")),
+            None => String::new(),
+        })
+        .collect::<Vec<_>>();
+    format!("({})", fields.join(","))
+}
+
+// pg_replication_slots 中复制槽的插件, 数据库, 是否属于当前数据库和已确认的位置
+type SlotRow = (Option<String>, Option<String>, bool, Option<String>);
+
+#[derive(Clone)]
+pub struct PostgresReplicationSource {
+    config: PostgresReplicationConfig,
+    pool: Option<PgPool>,
+    value_converter: PostgresValueConverter,
+    // 源表的 oid, 即 pgoutput 消息中的 relation id
+    relation: u32,
+    // 输出批次共享的 schema, 与表的列一一对应
+    schema: Arc<BatchSchema>,
+    started: bool,
+    // 已读完所有已提交的变更 (stop_at_end)
+    finished: bool,
+    // 最后一个已读出的事务的结束位置, 重新解码时跳过此前的事务
+    committed: Lsn,
+    // 最后一个已输出批次的位置
+    emitted: Lsn,
+    // 复制槽已确认到的位置
+    acknowledged: Lsn,
+    // 每次解码的消息数上限, 复制槽确认后从批次大小重新开始
+    peek_limit: usize,
+    // 已提交但尚未输出的变更
+    rows: Vec<Change>,
+    // 当前事务中的变更, 事务提交后才能输出
+    tx_rows: Vec<Change>,
+}
+
+impl PostgresReplicationSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        Ok(Self {
+            config: PostgresReplicationConfig::from_json(serde_json::Value::Object(
+                serde_json::Map::from_iter(config.properties),
+            ))?,
+            pool: None,
+            value_converter: PostgresValueConverter::new(),
+            relation: 0,
+            schema: Arc::default(),
+            started: false,
+            finished: false,
+            committed: Lsn::default(),
+            emitted: Lsn::default(),
+            acknowledged: Lsn::default(),
+            peek_limit: 0,
+            rows: Vec::new(),
+            tx_rows: Vec::new(),
+        })
+    }
+
+    fn pool(&self) -> Result<&PgPool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    async fn check_wal_level(&self, pool: &PgPool) -> Result<()> {
+        let wal_level: String = sqlx::query_scalar("SELECT current_setting('wal_level')")
+            .fetch_one(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+        if wal_level != "logical" {
+            return Err(Error::Config(format!(
+                "Logical replication requires wal_level=logical, server has {}",
+                wal_level
+            )));
+        }
+        Ok(())
+    }
+
+    // 发布不存在时为源表创建, 已存在时必须包含源表
+    async fn ensure_publication(&self) -> Result<()> {
+        let pool = self.pool()?;
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM pg_publication WHERE pubname = $1)")
+                .bind(&self.config.publication)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| Error::Read(e.to_string()))?;
+        if !exists {
+            info!(
+                "Creating publication {} for table {}",
+                self.config.publication, self.config.table
+            );
+            sqlx::query(&format!(
+                "CREATE PUBLICATION {} FOR TABLE {}",
+                quote_identifier(&self.config.publication),
+                quote_identifier(&self.config.table)
+            ))
+            .execute(pool)
+            .await
+            .map_err(|e| Error::Config(format!("Failed to create publication: {}", e)))?;
+            return Ok(());
+        }
+
+        let includes: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM pg_publication_tables \
+             WHERE pubname = $1 AND schemaname = $2 AND tablename = $3)",
+        )
+        .bind(&self.config.publication)
+        .bind(&self.config.schema)
+        .bind(&self.config.table)
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+        if !includes {
+            return Err(Error::Config(format!(
+                "Publication {} does not include table {}.{}",
+                self.config.publication, self.config.schema, self.config.table
+            )));
+        }
+        Ok(())
+    }
+
+    // 复制槽不存在时创建, 返回它已确认的位置
+    async fn ensure_slot(&self) -> Result<Lsn> {
+        let pool = self.pool()?;
+        let slot: Option<SlotRow> = sqlx::query_as(
+            "SELECT plugin::text, database::text, database = current_database(), \
+             confirmed_flush_lsn::text FROM pg_replication_slots WHERE slot_name = $1",
+        )
+        .bind(&self.config.slot_name)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+
+        match slot {
+            None => {
+                let lsn: String = sqlx::query_scalar(
+                    "SELECT lsn::text FROM pg_create_logical_replication_slot($1, 'pgoutput')",
+                )
+                .bind(&self.config.slot_name)
+                .fetch_one(pool)
+                .await
+                .map_err(|e| {
+                    Error::Config(format!("Failed to create replication slot: {}", e))
+                })?;
+                info!(
+                    "Created replication slot {} at {}",
+                    self.config.slot_name, lsn
+                );
+                lsn.parse()
+            }
+            Some((Some(plugin), _, true, Some(lsn))) if plugin == "pgoutput" => lsn.parse(),
+            Some((plugin, database, _, _)) => Err(Error::Config(format!(
+                "Replication slot {} must be a pgoutput slot of the current database, it uses plugin {} in database {}",
+                self.config.slot_name,
+                plugin.as_deref().unwrap_or("none"),
+                database.as_deref().unwrap_or("none")
+            ))),
+        }
+    }
+
+    // 推进复制槽, 此前的 WAL 不再为本任务保留
+    async fn advance_slot(&self, lsn: Lsn) -> Result<()> {
+        sqlx::query("SELECT pg_replication_slot_advance($1, $2::pg_lsn)")
+            .bind(&self.config.slot_name)
+            .bind(lsn.to_string())
+            .execute(self.pool()?)
+            .await
+            .map_err(|e| Error::Read(format!("Failed to advance replication slot: {}", e)))?;
+        Ok(())
+    }
+
+    // 不消费复制槽, 从已确认的位置起解码至多 peek_limit 条消息, 返回是否已读到末尾.
+    // 未确认的事务每次都会重新解码, 其数量受流水线的容量限制
+    async fn poll(&mut self) -> Result<bool> {
+        let messages: Vec<Vec<u8>> = sqlx::query_scalar(
+            "SELECT data FROM pg_logical_slot_peek_binary_changes($1, NULL, $2, \
+             'proto_version', '1', 'publication_names', $3)",
+        )
+        .bind(&self.config.slot_name)
+        .bind(i32::try_from(self.peek_limit).unwrap_or(i32::MAX))
+        .bind(quote_identifier(&self.config.publication))
+        .fetch_all(self.pool()?)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+
+        self.tx_rows.clear();
+        let reached_end = messages.len() < self.peek_limit;
+        for data in messages {
+            self.handle_message(parse_message(&data)?)?;
+        }
+        Ok(reached_end)
+    }
+
+    fn handle_message(&mut self, message: Message) -> Result<()> {
+        match message {
+            Message::Begin => self.tx_rows.clear(),
+            Message::Commit { end_lsn } => self.commit(end_lsn),
+            Message::Relation { id, columns }
+                if id == self.relation
+                    && !columns.iter().map(String::as_str).eq(self.schema.names()) =>
+            {
+                return Err(Error::Read(format!(
+                    "Table {} has columns {:?} in the replication stream but {:?} when the job started, restart the job after schema changes",
+                    self.config.table,
+                    columns,
+                    self.schema.names().collect::<Vec<_>>()
+                )));
+            }
+            Message::Insert { relation, new } if relation == self.relation => {
+                let (values, unchanged) = self.tuple_values(new, None)?;
+                self.push(Operation::Insert, values, None, unchanged);
+            }
+            Message::Update { relation, old, new } if relation == self.relation => {
+                let full = old.as_ref().filter(|(_, full)| *full).map(|(old, _)| old);
+                let (values, unchanged) = self.tuple_values(new, full)?;
+                let before = old
+                    .map(|(old, _)| self.tuple_values(old, None).map(|(before, _)| before))
+                    .transpose()?;
+                self.push(Operation::Update, values, before, unchanged);
+            }
+            Message::Delete { relation, old } if relation == self.relation => {
+                let (values, unchanged) = self.tuple_values(old, None)?;
+                self.push(Operation::Delete, values, None, unchanged);
+            }
+            Message::Truncate { relations } if relations.contains(&self.relation) => {
+                warn!(
+                    "Ignoring TRUNCATE of table {} in the replication stream",
+                    self.config.table
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn push(
+        &mut self,
+        op: Operation,
+        values: Vec<Option<String>>,
+        before: Option<Vec<Option<String>>>,
+        unchanged: Vec<usize>,
+    ) {
+        self.tx_rows.push(Change {
+            op,
+            values,
+            before,
+            unchanged,
+            lsn: Lsn::default(),
+        });
+    }
+
+    // 元组的文本值和其中未修改的 TOAST 列的下标. 未修改的 TOAST 值优先从完整的旧行
+    // (REPLICA IDENTITY FULL) 中取得, 取不到时该列在值中为 NULL, 输出时去掉该列
+    fn tuple_values(
+        &self,
+        tuple: Vec<TupleValue>,
+        old: Option<&Vec<TupleValue>>,
+    ) -> Result<(Vec<Option<String>>, Vec<usize>)> {
+        if tuple.len() != self.schema.len() {
+            return Err(Error::Read(format!(
+                "Table {} has {} columns in the replication stream but {} when the job started, restart the job after schema changes",
+                self.config.table,
+                tuple.len(),
+                self.schema.len()
+            )));
+        }
+
+        let mut unchanged = Vec::new();
+        let values = tuple
+            .into_iter()
+            .enumerate()
+            .map(|(index, value)| match value {
+                TupleValue::Null => None,
+                TupleValue::Text(text) => Some(text),
+                TupleValue::Unchanged => match old.and_then(|old| old.get(index)) {
+                    Some(TupleValue::Text(text)) => Some(text.clone()),
+                    Some(TupleValue::Null) => None,
+                    _ => {
+                        unchanged.push(index);
+                        None
+                    }
+                },
+            })
+            .collect();
+        Ok((values, unchanged))
+    }
+
+    // 事务提交, 重新解码时遇到已读出的事务直接丢弃
+    fn commit(&mut self, end_lsn: Lsn) {
+        if end_lsn <= self.committed {
+            self.tx_rows.clear();
+            return;
+        }
+        for change in &mut self.tx_rows {
+            change.lsn = end_lsn;
+        }
+        self.rows.append(&mut self.tx_rows);
+        self.committed = end_lsn;
+    }
+
+    // 输出的变更都已确认, 之后读出的事务又都不涉及源表时直接推进复制槽
+    async fn release_skipped(&mut self) -> Result<()> {
+        if self.rows.is_empty()
+            && self.emitted == self.acknowledged
+            && self.committed > self.acknowledged
+        {
+            self.advance_slot(self.committed).await?;
+            self.acknowledged = self.committed;
+            self.emitted = self.committed;
+        }
+        Ok(())
+    }
+
+    // 把文本转换为表的行类型后按列读取, 与全量读取使用同样的类型转换.
+    // 所有变更的 unchanged 相同, 输出的值去掉这些列
+    async fn decode_changes(
+        &self,
+        changes: Vec<Change>,
+        unchanged: &[usize],
+    ) -> Result<Vec<Record>> {
+        let mut literals = Vec::new();
+        for change in &changes {
+            literals.push(row_literal(&change.values));
+            if let Some(before) = &change.before {
+                literals.push(row_literal(before));
+            }
+        }
+
+        let query = format!(
+            "SELECT (r).* FROM (SELECT CAST(t AS {}) AS r, n \
+             FROM unnest($1::text[]) WITH ORDINALITY AS u(t, n)) s ORDER BY n",
+            quote_identifier(&self.config.table)
+        );
+        let rows = sqlx::query(&query)
+            .bind(literals)
+            .fetch_all(self.pool()?)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        let mut rows = rows.iter();
+        let mut next_row = || {
+            rows.next()
+                .ok_or_else(|| Error::Read("Decoded fewer rows than changes".into()))
+                .and_then(|row| self.row_values(row))
+                .map(|values| without_columns(values, unchanged))
+        };
+        let mut records = Vec::with_capacity(changes.len());
+        for change in changes {
+            let mut record =
+                Record::new(next_row()?)
+                    .with_op(change.op)
+                    .with_position(serde_json::to_value(ReplicationPosition {
+                        lsn: change.lsn,
+                    })?);
+            if change.before.is_some() {
+                record = record.with_before(next_row()?);
+            }
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    fn row_values(&self, row: &PgRow) -> Result<Vec<DbsyncValue>> {
+        row.columns()
+            .iter()
+            .zip(&self.schema.fields)
+            .map(|(col, field)| {
+                self.value_converter
+                    .to_dbsync_value(col, row, &field.data_type)
+            })
+            .collect()
+    }
+
+    // 取出开头连续的 unchanged 相同的变更, 它们共用一个 schema. 返回这些变更和批次的位置:
+    // 批次在事务中间结束时, 位置停在此前最后一个完整输出的事务, 恢复时重新输出未输出完的事务
+    fn take_changes(&mut self) -> Option<(Vec<Change>, Lsn)> {
+        let first = self.rows.first()?;
+        let count = self
+            .rows
+            .iter()
+            .take_while(|change| change.unchanged == first.unchanged)
+            .count();
+        let changes = self.rows.drain(..count).collect::<Vec<_>>();
+        let last = changes[changes.len() - 1].lsn;
+        let position = match self.rows.first() {
+            Some(next) if next.lsn == last => changes
+                .iter()
+                .map(|change| change.lsn)
+                .filter(|lsn| *lsn < last)
+                .max()
+                .unwrap_or(self.emitted),
+            Some(_) => last,
+            None => self.committed,
+        };
+        Some((changes, position))
+    }
+
+    // 输出已读出的变更, 没有时返回 None. 有未修改的 TOAST 列的更新单独成批,
+    // 批次的 schema 不含这些列, sink 只更新其余的列
+    async fn take_batch(&mut self) -> Result<Option<StreamBatch>> {
+        let Some((changes, position)) = self.take_changes() else {
+            return Ok(None);
+        };
+        let unchanged = changes[0].unchanged.clone();
+        let records = self.decode_changes(changes, &unchanged).await?;
+        let schema = if unchanged.is_empty() {
+            self.schema.clone()
+        } else {
+            Arc::new(BatchSchema::new(without_columns(
+                self.schema.fields.clone(),
+                &unchanged,
+            )))
+        };
+        self.emitted = position;
+        Ok(Some(StreamBatch {
+            batch: DataBatch::new(schema, records),
+            position: serde_json::to_value(ReplicationPosition { lsn: position })?,
+        }))
+    }
+}
+
+// 去掉下标在 columns 中的列
+fn without_columns<T>(values: Vec<T>, columns: &[usize]) -> Vec<T> {
+    values
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !columns.contains(index))
+        .map(|(_, value)| value)
+        .collect()
+}
+
+#[async_trait]
+impl Source for PostgresReplicationSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let url = &self.config.url;
+        info!("Connecting to PostgreSQL replication source: {}", url);
+
+        let pool = PgPool::connect_with(connect_options(url, &self.config.schema)?)
+            .await
+            .map_err(|e| {
+                Error::Connection(format!("Failed to connect to PostgreSQL source: {}", e))
+            })?;
+
+        self.check_wal_level(&pool).await?;
+
+        let table_schema = load_table_schema(&pool, &self.config.table).await?;
+        let relation: i64 = sqlx::query_scalar("SELECT $1::regclass::oid::int8")
+            .bind(quote_identifier(&self.config.table))
+            .fetch_one(&pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+        self.relation = relation as u32;
+        self.schema = Arc::new(BatchSchema::new(
+            table_schema
+                .columns
+                .iter()
+                .map(|column| {
+                    BatchField::new(&column.name, column.data_type.clone())
+                        .with_nullable(column.nullable)
+                })
+                .collect(),
+        ));
+        ctx.set_schema(table_schema);
+
+        info!("Successfully connected to PostgreSQL replication source");
+        self.pool = Some(pool);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        if !self.started && !self.finished {
+            self.start(None).await?;
+        }
+        Ok(self
+            .read_stream_batch(batch_size)
+            .await?
+            .map(|stream_batch| stream_batch.batch))
+    }
+
+    // 复制槽保留在服务器上, 下次从已确认的位置继续
+    async fn close(&mut self) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            info!("Closing PostgreSQL replication source");
+            pool.close().await;
+        }
+        self.started = false;
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_streaming(&mut self) -> Option<&mut dyn StreamingSource> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl StreamingSource for PostgresReplicationSource {
+    // 从复制槽已确认的位置开始; checkpoint 的位置更靠后时先把复制槽推进到该位置
+    async fn start(&mut self, position: Option<serde_json::Value>) -> Result<()> {
+        self.ensure_publication().await?;
+        let mut start = self.ensure_slot().await?;
+        if let Some(position) = position {
+            let position: ReplicationPosition = serde_json::from_value(position)
+                .map_err(|e| Error::Config(format!("Invalid replication checkpoint: {}", e)))?;
+            if position.lsn > start {
+                self.advance_slot(position.lsn).await?;
+                start = position.lsn;
+            }
+        }
+        info!(
+            "Starting logical replication of {}.{} from slot {} at {}",
+            self.config.schema, self.config.table, self.config.slot_name, start
+        );
+
+        self.started = true;
+        self.finished = false;
+        self.committed = start;
+        self.emitted = start;
+        self.acknowledged = start;
+        self.peek_limit = 0;
+        self.rows.clear();
+        self.tx_rows.clear();
+        Ok(())
+    }
+
+    // 只在事务边界切分批次, 保证 position 之前的事务都已完整输出
+    async fn read_stream_batch(&mut self, batch_size: usize) -> Result<Option<StreamBatch>> {
+        let flush_interval = Duration::from_millis(self.config.flush_interval_ms);
+        let poll_interval = Duration::from_millis(self.config.poll_interval_ms);
+        self.peek_limit = self.peek_limit.max(batch_size.max(1));
+        let mut deadline = None;
+
+        while !self.finished && self.rows.len() < batch_size {
+            // 第一批已提交的变更到达后开始计时
+            if deadline.is_none() && !self.rows.is_empty() {
+                deadline = Some(Instant::now() + flush_interval);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                break;
+            }
+
+            let committed = self.committed;
+            let reached_end = self.poll().await?;
+            if self.committed > committed {
+                continue;
+            }
+            if !reached_end {
+                // 解码到的都是已读出的事务, 扩大解码范围
+                self.peek_limit = self.peek_limit.saturating_mul(2);
+                continue;
+            }
+
+            self.release_skipped().await?;
+            if self.config.stop_at_end {
+                info!("Read all committed changes of {}", self.config.table);
+                self.finished = true;
+                break;
+            }
+            let wake = Instant::now() + poll_interval;
+            match deadline {
+                Some(deadline) if deadline <= wake => tokio::time::sleep_until(deadline).await,
+                _ => tokio::time::sleep_until(wake).await,
+            }
+        }
+
+        self.take_batch().await
+    }
+
+    async fn acknowledge(&mut self, position: serde_json::Value) -> Result<()> {
+        let position: ReplicationPosition = serde_json::from_value(position)
+            .map_err(|e| Error::Read(format!("Invalid replication position: {}", e)))?;
+        if position.lsn > self.acknowledged {
+            self.advance_slot(position.lsn).await?;
+            self.acknowledged = position.lsn;
+            // 已确认的事务不再被解码, 解码范围回到批次大小
+            self.peek_limit = 0;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::types::DbsyncType;
+    use serde_json::json;
+
+    fn tuple(values: &[TupleValue]) -> Vec<u8> {
+        let mut data = (values.len() as i16).to_be_bytes().to_vec();
+        for value in values {
+            match value {
+                TupleValue::Null => data.push(b'n'),
+                TupleValue::Unchanged => data.push(b'u'),
+                TupleValue::Text(text) => {
+                    data.push(b't');
+                    data.extend((text.len() as u32).to_be_bytes());
+                    data.extend(text.as_bytes());
+                }
+            }
+        }
+        data
+    }
+
+    fn text(text: &str) -> TupleValue {
+        TupleValue::Text(text.to_string())
+    }
+
+    #[test]
+    fn test_lsn_round_trip() {
+        let lsn: Lsn = "16/B374D848".parse().unwrap();
+        assert_eq!(lsn, Lsn(0x16_B374_D848));
+        assert_eq!(lsn.to_string(), "16/B374D848");
+        assert!("0/1A722C8".parse::<Lsn>().unwrap() < lsn);
+        assert!("16B374D848".parse::<Lsn>().is_err());
+
+        let position = ReplicationPosition { lsn };
+        let value = serde_json::to_value(&position).unwrap();
+        assert_eq!(value, json!({"lsn": "16/B374D848"}));
+        assert_eq!(
+            serde_json::from_value::<ReplicationPosition>(value).unwrap(),
+            position
+        );
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let mut insert = vec![b'I'];
+        insert.extend(16542u32.to_be_bytes());
+        insert.push(b'N');
+        insert.extend(tuple(&[text("1"), TupleValue::Null]));
+        assert_eq!(
+            parse_message(&insert).unwrap(),
+            Message::Insert {
+                relation: 16542,
+                new: vec![text("1"), TupleValue::Null],
+            }
+        );
+
+        // 修改主键的更新带有旧的主键
+        let mut update = vec![b'U'];
+        update.extend(7u32.to_be_bytes());
+        update.push(b'K');
+        update.extend(tuple(&[text("1"), TupleValue::Null]));
+        update.push(b'N');
+        update.extend(tuple(&[text("2"), TupleValue::Unchanged]));
+        assert_eq!(
+            parse_message(&update).unwrap(),
+            Message::Update {
+                relation: 7,
+                old: Some((vec![text("1"), TupleValue::Null], false)),
+                new: vec![text("2"), TupleValue::Unchanged],
+            }
+        );
+
+        // 主键未修改时没有旧行
+        let mut update = vec![b'U'];
+        update.extend(7u32.to_be_bytes());
+        update.push(b'N');
+        update.extend(tuple(&[text("2"), TupleValue::Null]));
+        assert_eq!(
+            parse_message(&update).unwrap(),
+            Message::Update {
+                relation: 7,
+                old: None,
+                new: vec![text("2"), TupleValue::Null],
+            }
+        );
+
+        let mut commit = vec![b'C', 0];
+        commit.extend(0x1A7_23C8u64.to_be_bytes());
+        commit.extend(0x1A7_23F8u64.to_be_bytes());
+        commit.extend(0i64.to_be_bytes());
+        assert_eq!(
+            parse_message(&commit).unwrap(),
+            Message::Commit {
+                end_lsn: Lsn(0x1A7_23F8)
+            }
+        );
+
+        let mut relation = vec![b'R'];
+        relation.extend(7u32.to_be_bytes());
+        relation.extend(b"public\0users\0d");
+        relation.extend(1i16.to_be_bytes());
+        relation.push(1);
+        relation.extend(b"id\0");
+        relation.extend(20u32.to_be_bytes());
+        relation.extend((-1i32).to_be_bytes());
+        assert_eq!(
+            parse_message(&relation).unwrap(),
+            Message::Relation {
+                id: 7,
+                columns: vec!["id".to_string()],
+            }
+        );
+
+        assert!(parse_message(&insert[..insert.len() - 1]).is_err());
+        assert_eq!(parse_message(b"O").unwrap(), Message::Other);
+    }
+
+    // 没有完整旧行时未修改的 TOAST 列被标记, 标记不同的变更分批输出
+    #[test]
+    fn test_unchanged_columns() {
+        let mut source = PostgresReplicationSource::new(ConnectorConfig {
+            name: "replication".to_string(),
+            connector_type: "postgres-cdc".to_string(),
+            properties: [
+                ("url".to_string(), json!("postgres://localhost/db")),
+                ("table".to_string(), json!("users")),
+            ]
+            .into(),
+        })
+        .unwrap();
+        source.schema = Arc::new(BatchSchema::new(vec![
+            BatchField::new("id", DbsyncType::BigInt),
+            BatchField::new("name", DbsyncType::Text),
+            BatchField::new("doc", DbsyncType::Text),
+        ]));
+
+        let new = vec![text("1"), text("b"), TupleValue::Unchanged];
+        assert_eq!(
+            source.tuple_values(new.clone(), None).unwrap(),
+            (vec![Some("1".into()), Some("b".into()), None], vec![2])
+        );
+        let old = vec![text("1"), text("a"), text("large")];
+        assert_eq!(
+            source.tuple_values(new, Some(&old)).unwrap(),
+            (
+                vec![Some("1".into()), Some("b".into()), Some("large".into())],
+                vec![]
+            )
+        );
+        assert!(source.tuple_values(vec![text("1")], None).is_err());
+
+        // 第一个事务含两种变更, 第二个事务只有一种
+        source.push(Operation::Insert, vec![], None, vec![]);
+        source.push(Operation::Update, vec![], None, vec![2]);
+        source.commit(Lsn(10));
+        source.push(Operation::Update, vec![], None, vec![2]);
+        source.commit(Lsn(20));
+        source.emitted = Lsn(5);
+        assert_eq!(
+            source.take_changes(),
+            Some((vec![change_at(Operation::Insert, vec![], 10)], Lsn(5)))
+        );
+        assert_eq!(
+            source.take_changes(),
+            Some((
+                vec![
+                    change_at(Operation::Update, vec![2], 10),
+                    change_at(Operation::Update, vec![2], 20),
+                ],
+                Lsn(20)
+            ))
+        );
+        assert_eq!(source.take_changes(), None);
+    }
+
+    fn change_at(op: Operation, unchanged: Vec<usize>, lsn: u64) -> Change {
+        Change {
+            op,
+            values: vec![],
+            before: None,
+            unchanged,
+            lsn: Lsn(lsn),
+        }
+    }
+
+    #[test]
+    fn test_row_literal() {
+        assert_eq!(
+            row_literal(&[
+                Some("1".into()),
+                None,
+                Some("".into()),
+                Some("say \"hi\" \\ {a,b}".into()),
+            ]),
+            r#"("1",,"","say \"hi\" \\ {a,b}")"#
+        );
+    }
+}
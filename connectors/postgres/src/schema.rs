@@ -0,0 +1,309 @@
+use crate::type_converter::PostgresTypeMapper;
+use dbsync_core::{
+    ddl::{CommentStyle, Dialect},
+    error::{Error, Result},
+    schema::{ColumnDefault, ColumnSchema, IndexColumn, IndexSchema, TableSchema},
+    types::{DbsyncType, TypeMapper},
+};
+use sqlx::{
+    postgres::{PgPool, PgRow},
+    Row,
+};
+
+// 带引号的表名或列名, 保留大小写; 表名按 search_path 解析
+pub(crate) fn quote_identifier(name: &str) -> String {
+    PostgresDialect.quote_identifier(name)
+}
+
+// pg_attribute 中的列, 类型为 format_type() 的输出, 由 PostgresTypeMapper 解析
+const COLUMNS_QUERY: &str = r#"
+    SELECT
+        a.attname::text AS column_name,
+        format_type(a.atttypid, a.atttypmod) AS column_type,
+        NOT a.attnotnull AS is_nullable,
+        pg_get_expr(d.adbin, d.adrelid) AS column_default,
+        a.attidentity <> '' AS is_identity,
+        col_description(a.attrelid, a.attnum) AS column_comment
+    FROM pg_attribute a
+    LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+    WHERE a.attrelid = $1::regclass
+    AND a.attnum > 0
+    AND NOT a.attisdropped
+    ORDER BY a.attnum
+"#;
+
+async fn fetch_columns(pool: &PgPool, table: &str) -> Result<Vec<PgRow>> {
+    sqlx::query(COLUMNS_QUERY)
+        .bind(quote_identifier(table))
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))
+}
+
+// 表的列, 按列顺序排列
+pub(crate) async fn load_columns(pool: &PgPool, table: &str) -> Result<Vec<ColumnSchema>> {
+    let mut columns = Vec::new();
+    for row in fetch_columns(pool, table).await? {
+        let name: String = row.get("column_name");
+        let data_type = PostgresTypeMapper
+            .to_dbsync_type(&row.get::<String, _>("column_type"))
+            .map_err(|e| Error::Type(format!("Column {}: {}", name, e)))?;
+
+        let (default, serial) = column_default(row.get("column_default"));
+        let mut column = ColumnSchema::new(name, data_type)
+            .with_nullable(row.get("is_nullable"))
+            .with_auto_increment(serial || row.get::<bool, _>("is_identity"));
+        column.default = default;
+        if let Some(comment) = row.get::<Option<String>, _>("column_comment") {
+            column = column.with_comment(comment);
+        }
+        columns.push(column);
+    }
+    Ok(columns)
+}
+
+// 目标表各列的类型, sink 按它绑定参数; 无法映射的类型 (如扩展类型) 不在结果中
+pub(crate) async fn target_column_types(
+    pool: &PgPool,
+    table: &str,
+) -> Result<Vec<(String, DbsyncType)>> {
+    Ok(fetch_columns(pool, table)
+        .await?
+        .iter()
+        .filter_map(|row| {
+            let data_type = PostgresTypeMapper
+                .to_dbsync_type(&row.get::<String, _>("column_type"))
+                .ok()?;
+            Some((row.get("column_name"), data_type))
+        })
+        .collect())
+}
+
+pub(crate) async fn primary_key_columns(pool: &PgPool, table: &str) -> Result<Vec<String>> {
+    Ok(load_indexes(pool, table)
+        .await?
+        .into_iter()
+        .find(|(_, primary)| *primary)
+        .map(|(index, _)| index.column_names())
+        .unwrap_or_default())
+}
+
+// 表上的索引及是否为主键. 表达式索引和部分索引无法按列重建, 整个跳过;
+// INCLUDE 的列不属于索引键, 不计入
+async fn load_indexes(pool: &PgPool, table: &str) -> Result<Vec<(IndexSchema, bool)>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT
+            i.relname::text AS index_name,
+            ix.indisprimary AS is_primary,
+            ix.indisunique AS is_unique,
+            a.attname::text AS column_name
+        FROM pg_index ix
+        JOIN pg_class i ON i.oid = ix.indexrelid
+        CROSS JOIN LATERAL unnest(ix.indkey::int2[]) WITH ORDINALITY AS k(attnum, ordinal)
+        LEFT JOIN pg_attribute a ON a.attrelid = ix.indrelid AND a.attnum = k.attnum
+        WHERE ix.indrelid = $1::regclass
+        AND ix.indpred IS NULL
+        AND k.ordinal <= ix.indnkeyatts
+        ORDER BY i.relname, k.ordinal
+        "#,
+    )
+    .bind(quote_identifier(table))
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))?;
+
+    let mut indexes: Vec<(IndexSchema, bool)> = Vec::new();
+    let mut skipped = Vec::new();
+    for row in &rows {
+        let name: String = row.get("index_name");
+        let Some(column) = row.get::<Option<String>, _>("column_name") else {
+            skipped.push(name);
+            continue;
+        };
+        match indexes.last_mut() {
+            Some((index, _)) if index.name == name => index.columns.push(IndexColumn::new(column)),
+            _ => indexes.push((
+                IndexSchema::new(name, vec![column], row.get("is_unique")),
+                row.get("is_primary"),
+            )),
+        }
+    }
+    indexes.retain(|(index, _)| !skipped.contains(&index.name));
+    Ok(indexes)
+}
+
+// 从系统表读取表结构
+pub(crate) async fn load_table_schema(pool: &PgPool, table: &str) -> Result<TableSchema> {
+    let exists: bool = sqlx::query_scalar("SELECT to_regclass($1) IS NOT NULL")
+        .bind(quote_identifier(table))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+    if !exists {
+        return Err(Error::Read(format!("Table {} not found", table)));
+    }
+
+    let mut schema = TableSchema::new(table).with_dialect(PostgresDialect.name());
+    schema.columns = load_columns(pool, table).await?;
+    for (index, primary) in load_indexes(pool, table).await? {
+        if primary {
+            schema.primary_key = index.column_names();
+        } else {
+            schema.indexes.push(index);
+        }
+    }
+
+    schema.comment = sqlx::query_scalar("SELECT obj_description($1::regclass, 'pg_class')")
+        .bind(quote_identifier(table))
+        .fetch_one(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))?;
+
+    Ok(schema)
+}
+
+// pg_get_expr 输出的默认值, 第二项表示是否为 serial 列 (默认值为 nextval).
+// 字符串字面量带有类型转换, 如 'it''s'::character varying
+fn column_default(default: Option<String>) -> (Option<ColumnDefault>, bool) {
+    let Some(default) = default else {
+        return (None, false);
+    };
+    if default.starts_with("nextval(") {
+        return (None, true);
+    }
+    if let Some(literal) = string_literal(&default) {
+        return (Some(ColumnDefault::Literal(literal)), false);
+    }
+    if default.parse::<f64>().is_ok() || default == "true" || default == "false" {
+        return (Some(ColumnDefault::Literal(default)), false);
+    }
+    (Some(ColumnDefault::Expression(default)), false)
+}
+
+// 'text'::type 或 'text' 形式的字面量, 返回去掉引号和类型转换后的文本
+fn string_literal(default: &str) -> Option<String> {
+    let mut chars = default.strip_prefix('\'')?.chars().peekable();
+    let mut text = String::new();
+    while let Some(c) = chars.next() {
+        if c == '\'' {
+            if chars.peek() == Some(&'\'') {
+                chars.next();
+                text.push('\'');
+                continue;
+            }
+            // 之后只能是类型转换, 如 ::character varying 或 ::integer[]
+            let rest = chars.collect::<String>();
+            let cast = rest.strip_prefix("::").unwrap_or(&rest);
+            return cast
+                .chars()
+                .all(|c| c.is_alphanumeric() || " _[].\"".contains(c))
+                .then_some(text);
+        }
+        text.push(c);
+    }
+    None
+}
+
+// PostgreSQL 方言: 双引号标识符, 索引和注释都是单独的语句
+pub struct PostgresDialect;
+
+impl Dialect for PostgresDialect {
+    fn name(&self) -> &str {
+        "postgres"
+    }
+
+    fn type_mapper(&self) -> &dyn TypeMapper {
+        &PostgresTypeMapper
+    }
+
+    // 自增列使用标识列, 写入时仍可指定值
+    fn auto_increment(&self) -> Option<&str> {
+        Some("GENERATED BY DEFAULT AS IDENTITY")
+    }
+
+    fn comment_style(&self) -> CommentStyle {
+        CommentStyle::Statement
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::ddl::create_table_statements;
+
+    #[test]
+    fn test_column_default() {
+        assert_eq!(column_default(None), (None, false));
+        assert_eq!(
+            column_default(Some("nextval('users_id_seq'::regclass)".into())),
+            (None, true)
+        );
+        assert_eq!(
+            column_default(Some("'it''s'::character varying".into())),
+            (Some(ColumnDefault::Literal("it's".into())), false)
+        );
+        assert_eq!(
+            column_default(Some("'-1'::integer".into())),
+            (Some(ColumnDefault::Literal("-1".into())), false)
+        );
+        assert_eq!(
+            column_default(Some("0.5".into())),
+            (Some(ColumnDefault::Literal("0.5".into())), false)
+        );
+        assert_eq!(
+            column_default(Some("now()".into())),
+            (Some(ColumnDefault::Expression("now()".into())), false)
+        );
+        assert_eq!(
+            column_default(Some("('a'::text || 'b'::text)".into())),
+            (
+                Some(ColumnDefault::Expression("('a'::text || 'b'::text)".into())),
+                false
+            )
+        );
+        assert_eq!(string_literal("'a'::text || 'b'::text"), None);
+    }
+
+    #[test]
+    fn test_postgres_create_table_statements() {
+        let schema = TableSchema::new("users")
+            .with_column(
+                ColumnSchema::new("id", DbsyncType::UnsignedBigInt)
+                    .with_nullable(false)
+                    .with_auto_increment(true),
+            )
+            .with_column(
+                ColumnSchema::new("Name", DbsyncType::VarChar(255))
+                    .with_default(ColumnDefault::Literal("it's".into()))
+                    .with_comment("名称"),
+            )
+            .with_column(ColumnSchema::new(
+                "tags",
+                DbsyncType::Array(Box::new(DbsyncType::Text)),
+            ))
+            .with_column(
+                ColumnSchema::new("created_at", DbsyncType::Timestamp(3))
+                    .with_default(ColumnDefault::Expression("CURRENT_TIMESTAMP(3)".into())),
+            )
+            .with_primary_key(vec!["id".into()])
+            .with_index(IndexSchema::new("idx_name", vec!["Name".into()], true))
+            .with_comment("用户表");
+
+        assert_eq!(
+            create_table_statements(&PostgresDialect, "target", &schema).unwrap(),
+            vec![
+                "CREATE TABLE \"target\" (\n  \
+                 \"id\" BIGINT NOT NULL GENERATED BY DEFAULT AS IDENTITY,\n  \
+                 \"Name\" VARCHAR(255) NULL DEFAULT 'it''s',\n  \
+                 \"tags\" TEXT[] NULL,\n  \
+                 \"created_at\" TIMESTAMPTZ(3) NULL DEFAULT CURRENT_TIMESTAMP(3),\n  \
+                 PRIMARY KEY (\"id\")\n\
+                 )",
+                "CREATE UNIQUE INDEX \"target_idx_name\" ON \"target\" (\"Name\")",
+                "COMMENT ON TABLE \"target\" IS '用户表'",
+                "COMMENT ON COLUMN \"target\".\"Name\" IS '名称'",
+            ]
+        );
+    }
+}
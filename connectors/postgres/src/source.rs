@@ -0,0 +1,530 @@
+use crate::config::{connect_options, PostgresSourceConfig};
+use crate::schema::{load_columns, load_table_schema, primary_key_columns, quote_identifier};
+use crate::type_converter::{PgQuery, PostgresValueConverter};
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchField, BatchSchema, ConnectorConfig, Context, DataBatch, IncrementalSource, KeyRange,
+        KeyValue, RangeBatch, Record, ShardedSource, Source,
+    },
+    error::{Error, Result},
+    schema::TableSchema,
+    types::{DbsyncType, DbsyncValue, TypeConverter},
+};
+use sqlx::postgres::{PgPool, PgRow};
+use sqlx::{Column, Row};
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+
+#[derive(Clone)]
+pub struct PostgresSource {
+    config: PostgresSourceConfig,
+    pool: Option<PgPool>,
+    value_converter: PostgresValueConverter,
+    // 主键列, init 时从 pg_index 读取
+    primary_key: Vec<String>,
+    // read_batch 的 keyset 游标, 即上一次返回的最后一行的主键
+    cursor: Option<KeyValue>,
+    // 增量同步的水位区间 (low, high], 设置后所有读取只包含区间内的行
+    watermark: Option<(Option<DbsyncValue>, DbsyncValue)>,
+}
+
+// 按出现顺序编号的查询参数, 与语句中的 $1, $2 ... 一一对应,
+// 每个参数按所比较的列的类型绑定
+#[derive(Default)]
+struct Params {
+    values: Vec<(DbsyncValue, DbsyncType)>,
+}
+
+impl Params {
+    // 加入一个参数, 返回它的占位符
+    fn push(&mut self, value: DbsyncValue, data_type: DbsyncType) -> String {
+        self.values.push((value, data_type));
+        format!("${}", self.values.len())
+    }
+
+    fn bind<'q>(
+        &self,
+        mut query: PgQuery<'q>,
+        converter: &PostgresValueConverter,
+    ) -> Result<PgQuery<'q>> {
+        for (value, data_type) in &self.values {
+            query = converter.bind_as(query, value, data_type)?;
+        }
+        Ok(query)
+    }
+}
+
+impl PostgresSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = PostgresSourceConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            config,
+            pool: None,
+            value_converter: PostgresValueConverter::new(),
+            primary_key: Vec::new(),
+            cursor: None,
+            watermark: None,
+        })
+    }
+
+    fn pool(&self) -> Result<&PgPool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    async fn get_columns(&self, pool: &PgPool) -> Result<HashMap<String, BatchField>> {
+        Ok(load_columns(pool, &self.config.table)
+            .await?
+            .into_iter()
+            .map(|column| {
+                let field =
+                    BatchField::new(&column.name, column.data_type).with_nullable(column.nullable);
+                (column.name, field)
+            })
+            .collect())
+    }
+
+    fn column_type(columns: &HashMap<String, BatchField>, name: &str) -> Result<DbsyncType> {
+        columns
+            .get(name)
+            .map(|field| field.data_type.clone())
+            .ok_or_else(|| Error::Type(format!("Unknown column: {}", name)))
+    }
+
+    // 水位过滤条件, 未设置水位区间时为空
+    fn watermark_filter(
+        &self,
+        columns: &HashMap<String, BatchField>,
+        params: &mut Params,
+    ) -> Result<Vec<String>> {
+        let (Some(column), Some((low, high))) = (&self.config.incremental_column, &self.watermark)
+        else {
+            return Ok(Vec::new());
+        };
+
+        let data_type = Self::column_type(columns, column)?;
+        let quoted = quote_identifier(column);
+        let mut conditions = Vec::new();
+        if let Some(low) = low {
+            let placeholder = params.push(low.clone(), data_type.clone());
+            conditions.push(format!("{} > {}", quoted, placeholder));
+        }
+        let placeholder = params.push(high.clone(), data_type);
+        conditions.push(format!("{} <= {}", quoted, placeholder));
+        Ok(conditions)
+    }
+
+    // 主键比较条件, 复合主键使用行构造器比较, 如 ("a", "b") > ($1, $2)
+    fn key_predicate(
+        &self,
+        columns: &HashMap<String, BatchField>,
+        params: &mut Params,
+        op: &str,
+        key: &KeyValue,
+    ) -> Result<String> {
+        let primary_key = self.primary_key()?;
+        if key.0.len() != primary_key.len() {
+            return Err(Error::Read(format!(
+                "Key {:?} does not match primary key {:?}",
+                key.0, primary_key
+            )));
+        }
+        let mut placeholders = Vec::new();
+        for (column, value) in primary_key.iter().zip(&key.0) {
+            if *value == DbsyncValue::Null {
+                return Err(Error::Type("Primary key value cannot be NULL".into()));
+            }
+            placeholders.push(params.push(value.clone(), Self::column_type(columns, column)?));
+        }
+        Ok(format!(
+            "({}) {} ({})",
+            key_columns(primary_key),
+            op,
+            placeholders.join(", ")
+        ))
+    }
+
+    fn primary_key(&self) -> Result<&[String]> {
+        if self.primary_key.is_empty() {
+            return Err(Error::Config(format!(
+                "Table {} has no primary key, keyset reads require one",
+                self.config.table
+            )));
+        }
+        Ok(&self.primary_key)
+    }
+
+    // 按主键顺序读取 after 之后 (不含) 且不超过 end 的下一批数据
+    async fn read_page(
+        &self,
+        after: Option<&KeyValue>,
+        end: Option<&KeyValue>,
+        limit: usize,
+    ) -> Result<Option<RangeBatch>> {
+        let pool = self.pool()?;
+        let key = self.primary_key()?;
+        let columns = self.get_columns(pool).await?;
+
+        let mut params = Params::default();
+        let mut conditions = self.watermark_filter(&columns, &mut params)?;
+        if let Some(after) = after {
+            conditions.push(self.key_predicate(&columns, &mut params, ">", after)?);
+        }
+        if let Some(end) = end {
+            conditions.push(self.key_predicate(&columns, &mut params, "<=", end)?);
+        }
+        let limit = params.push(DbsyncValue::Integer(limit as i64), DbsyncType::BigInt);
+        let query = format!(
+            "SELECT * FROM {} {} ORDER BY {} LIMIT {}",
+            quote_identifier(&self.config.table),
+            where_clause(&conditions),
+            key_order(key, "ASC"),
+            limit
+        );
+
+        let rows = params
+            .bind(sqlx::query(&query), &self.value_converter)?
+            .fetch_all(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        let Some(first) = rows.first() else {
+            return Ok(None);
+        };
+        // 整页共享一份 schema, 列顺序与 SELECT * 的结果一致
+        let schema = Arc::new(BatchSchema::new(
+            first
+                .columns()
+                .iter()
+                .map(|col| {
+                    columns
+                        .get(col.name())
+                        .cloned()
+                        .ok_or_else(|| Error::Type(format!("Unknown column: {}", col.name())))
+                })
+                .collect::<Result<_>>()?,
+        ));
+
+        let records = rows
+            .iter()
+            .map(|row| {
+                row.columns()
+                    .iter()
+                    .zip(&schema.fields)
+                    .map(|(col, field)| {
+                        self.value_converter
+                            .to_dbsync_value(col, row, &field.data_type)
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map(Record::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let last = &records[records.len() - 1];
+        let last_key = KeyValue(
+            key.iter()
+                .map(|column| {
+                    schema
+                        .index_of(column)
+                        .map(|index| last.values[index].clone())
+                        .ok_or_else(|| Error::Read(format!("Missing key column: {}", column)))
+                })
+                .collect::<Result<_>>()?,
+        );
+
+        Ok(Some(RangeBatch {
+            batch: DataBatch::new(schema, records),
+            last_key,
+        }))
+    }
+
+    // 按主键排序, 跳过 after (不含) 之后的 offset 行, 读取下一行的主键
+    async fn get_key_at(
+        &self,
+        order: &str,
+        after: Option<&KeyValue>,
+        offset: i64,
+    ) -> Result<Option<KeyValue>> {
+        let pool = self.pool()?;
+        let key = self.primary_key()?;
+        let columns = self.get_columns(pool).await?;
+
+        let mut params = Params::default();
+        let mut conditions = self.watermark_filter(&columns, &mut params)?;
+        if let Some(after) = after {
+            let op = if order == "ASC" { ">" } else { "<" };
+            conditions.push(self.key_predicate(&columns, &mut params, op, after)?);
+        }
+        let offset = params.push(DbsyncValue::Integer(offset), DbsyncType::BigInt);
+        let query = format!(
+            "SELECT {} FROM {} {} ORDER BY {} LIMIT 1 OFFSET {}",
+            key_columns(key),
+            quote_identifier(&self.config.table),
+            where_clause(&conditions),
+            key_order(key, order),
+            offset
+        );
+        let row = params
+            .bind(sqlx::query(&query), &self.value_converter)?
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(Some(self.row_to_key(&columns, &row)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn row_to_key(&self, columns: &HashMap<String, BatchField>, row: &PgRow) -> Result<KeyValue> {
+        let values = row
+            .columns()
+            .iter()
+            .map(|col| {
+                let data_type = Self::column_type(columns, col.name())?;
+                self.value_converter.to_dbsync_value(col, row, &data_type)
+            })
+            .collect::<Result<_>>()?;
+        Ok(KeyValue(values))
+    }
+}
+
+fn key_columns(key: &[String]) -> String {
+    key.iter()
+        .map(|column| quote_identifier(column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn key_order(key: &[String], order: &str) -> String {
+    key.iter()
+        .map(|column| format!("{} {}", quote_identifier(column), order))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn where_clause(conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        return String::new();
+    }
+    format!("WHERE {}", conditions.join(" AND "))
+}
+
+#[async_trait]
+impl Source for PostgresSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let url = &self.config.url;
+        info!("Connecting to PostgreSQL source: {}", url);
+
+        let pool = PgPool::connect_with(connect_options(url, &self.config.schema)?)
+            .await
+            .map_err(|e| {
+                Error::Connection(format!("Failed to connect to PostgreSQL source: {}", e))
+            })?;
+
+        // 获取表结构并放入 context
+        ctx.set_schema(load_table_schema(&pool, &self.config.table).await?);
+
+        self.primary_key = primary_key_columns(&pool, &self.config.table).await?;
+        if self.primary_key.is_empty() {
+            warn!(
+                "Table {} has no primary key, sharded reads are unavailable",
+                self.config.table
+            );
+        }
+
+        info!("Successfully connected to PostgreSQL source");
+        self.pool = Some(pool);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        match self
+            .read_page(self.cursor.as_ref(), None, batch_size)
+            .await?
+        {
+            Some(RangeBatch { batch, last_key }) => {
+                self.cursor = Some(last_key);
+                Ok(Some(batch))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            info!("Closing PostgreSQL connection");
+            pool.close().await;
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
+        Some(self)
+    }
+
+    fn as_incremental(&mut self) -> Option<&mut dyn IncrementalSource> {
+        if self.config.incremental_column.is_some() {
+            Some(self)
+        } else {
+            None
+        }
+    }
+}
+
+#[async_trait]
+impl IncrementalSource for PostgresSource {
+    fn watermark_column(&self) -> &str {
+        self.config
+            .incremental_column
+            .as_deref()
+            .unwrap_or_default()
+    }
+
+    async fn get_max_watermark(&self) -> Result<Option<DbsyncValue>> {
+        let pool = self.pool()?;
+        let columns = self.get_columns(pool).await?;
+        let column = quote_identifier(self.watermark_column());
+
+        let query = format!(
+            "SELECT {0} FROM {1} WHERE {0} IS NOT NULL ORDER BY {0} DESC LIMIT 1",
+            column,
+            quote_identifier(&self.config.table)
+        );
+        let row = sqlx::query(&query)
+            .fetch_optional(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        match row {
+            Some(row) => Ok(self.row_to_key(&columns, &row)?.0.pop()),
+            None => Ok(None),
+        }
+    }
+
+    fn set_watermark_range(&mut self, low: Option<DbsyncValue>, high: DbsyncValue) {
+        self.watermark = Some((low, high));
+    }
+}
+
+#[async_trait]
+impl ShardedSource for PostgresSource {
+    async fn get_total_records(&self) -> Result<i64> {
+        let pool = self.pool()?;
+        let columns = self.get_columns(pool).await?;
+        let mut params = Params::default();
+        let conditions = self.watermark_filter(&columns, &mut params)?;
+        let query = format!(
+            "SELECT COUNT(*) FROM {} {}",
+            quote_identifier(&self.config.table),
+            where_clause(&conditions)
+        );
+        let row = params
+            .bind(sqlx::query(&query), &self.value_converter)?
+            .fetch_one(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+        Ok(row.get::<i64, _>(0))
+    }
+
+    async fn get_primary_key(&self) -> Result<Vec<String>> {
+        Ok(self.primary_key()?.to_vec())
+    }
+
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+        let min = self.get_key_at("ASC", None, 0).await?;
+        let max = self.get_key_at("DESC", None, 0).await?;
+        Ok(min.zip(max))
+    }
+
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        let Some((min, max)) = self.get_id_range().await? else {
+            return Ok(Vec::new());
+        };
+        if let Some(ranges) = KeyRange::split_integer(&min, &max, chunks) {
+            return Ok(ranges);
+        }
+
+        // 字符串, uuid 和复合主键无法按数值切分, 按行号采样区间终点.
+        // 每次从上一个终点开始只跳过两个终点之间的行, 整个切分只扫描一遍主键索引
+        let total = self.get_total_records().await?;
+        let chunks = chunks.max(1) as i64;
+        let mut ends: Vec<KeyValue> = Vec::new();
+        let mut previous = -1;
+        for i in 1..chunks {
+            let offset = total * i / chunks - 1;
+            if offset <= previous {
+                continue;
+            }
+            let Some(end) = self
+                .get_key_at("ASC", ends.last(), offset - previous - 1)
+                .await?
+            else {
+                break;
+            };
+            if end == max {
+                break;
+            }
+            ends.push(end);
+            previous = offset;
+        }
+        ends.push(max);
+
+        Ok(KeyRange::from_boundaries(ends))
+    }
+
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>> {
+        self.read_page(after, Some(end), self.config.batch_size)
+            .await
+    }
+
+    async fn get_schema(&self) -> Result<TableSchema> {
+        load_table_schema(self.pool()?, &self.config.table).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_params_are_numbered_in_order() {
+        let mut params = Params::default();
+        assert_eq!(params.push(DbsyncValue::Integer(1), DbsyncType::Int), "$1");
+        assert_eq!(
+            params.push(DbsyncValue::String("a".into()), DbsyncType::Text),
+            "$2"
+        );
+        assert!(params
+            .bind(sqlx::query("SELECT $1, $2"), &PostgresValueConverter::new())
+            .is_ok());
+
+        assert_eq!(
+            key_order(&["a".into(), "B".into()], "DESC"),
+            "\"a\" DESC, \"B\" DESC"
+        );
+        assert_eq!(where_clause(&[]), "");
+        assert_eq!(
+            where_clause(&["\"a\" > $1".into(), "\"a\" <= $2".into()]),
+            "WHERE \"a\" > $1 AND \"a\" <= $2"
+        );
+    }
+}
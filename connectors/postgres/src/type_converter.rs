@@ -0,0 +1,874 @@
+use bigdecimal::BigDecimal;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, Utc};
+use dbsync_core::{
+    error::{Error, Result},
+    types::{DbsyncType, DbsyncValue, TypeConverter, TypeMapper, MICROS_PER_DAY},
+};
+use serde_json::Value;
+use sqlx::postgres::{PgArguments, PgColumn, PgHasArrayType, PgRow, Postgres};
+use sqlx::query::Query;
+use sqlx::{Column, Decode, Encode, Row, Type, ValueRef};
+use uuid::Uuid;
+
+pub(crate) type PgQuery<'q> = Query<'q, Postgres, PgArguments>;
+
+#[derive(Clone)]
+pub struct PostgresTypeMapper;
+
+impl TypeMapper for PostgresTypeMapper {
+    // 接受 format_type() 的输出, 如 character varying(255), timestamp(3) with time zone,
+    // integer[], 也接受 int4, timestamptz 这样的别名
+    fn to_dbsync_type(&self, source_type: &str) -> Result<DbsyncType> {
+        let invalid = || Error::Type(format!("Invalid PostgreSQL type: {}", source_type));
+        let (name, params, array) = parse_type(source_type).ok_or_else(invalid)?;
+        let param = |index: usize| -> Result<Option<u32>> {
+            params
+                .get(index)
+                .map(|p| p.parse().map_err(|_| invalid()))
+                .transpose()
+        };
+        let narrow = |value: u32| u8::try_from(value).map_err(|_| invalid());
+        // 秒的小数位数, 未指定时为 6
+        let precision = || -> Result<u8> {
+            match param(0)?.unwrap_or(6) {
+                p @ 0..=6 => Ok(p as u8),
+                _ => Err(invalid()),
+            }
+        };
+
+        let dbsync_type = match name.as_str() {
+            "smallint" | "int2" | "smallserial" | "serial2" => DbsyncType::SmallInt,
+            "integer" | "int" | "int4" | "serial" | "serial4" => DbsyncType::Int,
+            "bigint" | "int8" | "bigserial" | "serial8" => DbsyncType::BigInt,
+            "real" | "float4" => DbsyncType::Float,
+            "double precision" | "float8" => DbsyncType::Double,
+            // 未指定精度的 numeric 不限制位数, 用 Numeric(0, 0) 表示
+            "numeric" | "decimal" => match param(0)? {
+                Some(precision) => {
+                    DbsyncType::Numeric(narrow(precision)?, narrow(param(1)?.unwrap_or(0))?)
+                }
+                None => DbsyncType::Numeric(0, 0),
+            },
+            "character varying" | "varchar" => match param(0)? {
+                Some(length) => DbsyncType::VarChar(length),
+                None => DbsyncType::Text,
+            },
+            "character" | "char" => DbsyncType::Char(param(0)?.unwrap_or(1)),
+            // 不带长度的 bpchar 不限制长度
+            "bpchar" => match param(0)? {
+                Some(length) => DbsyncType::Char(length),
+                None => DbsyncType::Text,
+            },
+            "text" | "name" | "citext" => DbsyncType::Text,
+            "bytea" => DbsyncType::LongBlob,
+            "boolean" | "bool" => DbsyncType::Boolean,
+            "date" => DbsyncType::Date,
+            "time" | "time without time zone" => DbsyncType::Time(precision()?),
+            "timestamp" | "timestamp without time zone" => DbsyncType::DateTime(precision()?),
+            "timestamptz" | "timestamp with time zone" => DbsyncType::Timestamp(precision()?),
+            "json" | "jsonb" => DbsyncType::Json,
+            "uuid" => DbsyncType::Uuid,
+            _ => {
+                return Err(Error::Type(format!(
+                    "Unknown PostgreSQL type: {}",
+                    source_type
+                )))
+            }
+        };
+        Ok(if array {
+            DbsyncType::Array(Box::new(dbsync_type))
+        } else {
+            dbsync_type
+        })
+    }
+
+    // PostgreSQL 没有无符号整数, 按能容纳取值范围的有符号类型保存;
+    // UNSIGNED BIGINT 和 BIT 仍用 BIGINT, 以便作为自增主键, 超出范围的值写入时报错
+    fn to_target_type(&self, dbsync_type: &DbsyncType) -> Result<String> {
+        match dbsync_type {
+            DbsyncType::TinyInt
+            | DbsyncType::SmallInt
+            | DbsyncType::UnsignedTinyInt
+            | DbsyncType::Year => Ok("SMALLINT".to_string()),
+            DbsyncType::MediumInt
+            | DbsyncType::Int
+            | DbsyncType::UnsignedSmallInt
+            | DbsyncType::UnsignedMediumInt => Ok("INTEGER".to_string()),
+            DbsyncType::BigInt
+            | DbsyncType::UnsignedInt
+            | DbsyncType::UnsignedBigInt
+            | DbsyncType::Bit(_) => Ok("BIGINT".to_string()),
+            DbsyncType::Float => Ok("REAL".to_string()),
+            DbsyncType::Double => Ok("DOUBLE PRECISION".to_string()),
+            t if t.is_unconstrained_numeric() => Ok("NUMERIC".to_string()),
+            DbsyncType::Decimal(p, s) | DbsyncType::Numeric(p, s) => {
+                Ok(format!("NUMERIC({},{})", p, s))
+            }
+            DbsyncType::Char(n) => Ok(format!("CHAR({})", n)),
+            DbsyncType::VarChar(n) => Ok(format!("VARCHAR({})", n)),
+            // ENUM / SET 没有对应的内置类型, 以成员文本保存
+            DbsyncType::TinyText
+            | DbsyncType::Text
+            | DbsyncType::MediumText
+            | DbsyncType::LongText
+            | DbsyncType::Enum(_)
+            | DbsyncType::Set(_) => Ok("TEXT".to_string()),
+            DbsyncType::Date => Ok("DATE".to_string()),
+            DbsyncType::Time(p) => Ok(format!("TIME({})", p)),
+            DbsyncType::DateTime(p) => Ok(format!("TIMESTAMP({})", p)),
+            DbsyncType::Timestamp(p) => Ok(format!("TIMESTAMPTZ({})", p)),
+            DbsyncType::Boolean => Ok("BOOLEAN".to_string()),
+            DbsyncType::Binary(_)
+            | DbsyncType::VarBinary(_)
+            | DbsyncType::TinyBlob
+            | DbsyncType::Blob
+            | DbsyncType::MediumBlob
+            | DbsyncType::LongBlob
+            | DbsyncType::Spatial(_) => Ok("BYTEA".to_string()),
+            DbsyncType::Json => Ok("JSONB".to_string()),
+            DbsyncType::Uuid => Ok("UUID".to_string()),
+            DbsyncType::Array(element) => match element.as_ref() {
+                DbsyncType::Array(_) | DbsyncType::Null => Err(Error::Type(format!(
+                    "Unsupported array element type: {:?}",
+                    element
+                ))),
+                element => Ok(format!("{}[]", self.to_target_type(element)?)),
+            },
+            DbsyncType::Null => Err(Error::Type(
+                "PostgreSQL has no column type for NULL".to_string(),
+            )),
+        }
+    }
+}
+
+// 把类型拆成小写的类型名, 括号内的参数和是否为数组,
+// 参数可以在类型名中间, 如 timestamp(3) with time zone → ("timestamp with time zone", ["3"])
+fn parse_type(source_type: &str) -> Option<(String, Vec<String>, bool)> {
+    let mut source_type = source_type.trim().to_lowercase();
+    let array = source_type.ends_with("[]");
+    while let Some(element) = source_type.strip_suffix("[]") {
+        source_type = element.trim_end().to_string();
+    }
+
+    let (name, params) = match source_type.find('(') {
+        Some(open) => {
+            let close = open + source_type[open..].find(')')?;
+            let params = source_type[open + 1..close]
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .collect();
+            (
+                format!("{} {}", &source_type[..open], &source_type[close + 1..]),
+                params,
+            )
+        }
+        None => (source_type.clone(), Vec::new()),
+    };
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some((name, params, array))
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIME_FORMAT: &str = "%H:%M:%S%.6f";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.6f";
+
+pub(crate) fn date_to_days(date: NaiveDate) -> i32 {
+    (date - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32
+}
+
+pub(crate) fn days_to_date(days: i32) -> Option<NaiveDate> {
+    DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(TimeDelta::days(days.into()))
+}
+
+// PostgreSQL TIME 只能表示一天之内的时刻, 负数和超过 24 小时的 MySQL TIME 无法写入
+pub(crate) fn micros_to_time(micros: i64) -> Option<NaiveTime> {
+    if !(0..MICROS_PER_DAY).contains(&micros) {
+        return None;
+    }
+    Some(
+        NaiveTime::MIN
+            .overflowing_add_signed(TimeDelta::microseconds(micros))
+            .0,
+    )
+}
+
+pub(crate) fn time_to_micros(time: NaiveTime) -> i64 {
+    (time - NaiveTime::MIN)
+        .num_microseconds()
+        .unwrap_or_default()
+}
+
+// 墙上时间按 UTC 解释得到的微秒数
+pub(crate) fn micros_to_datetime(micros: i64) -> Option<NaiveDateTime> {
+    DateTime::from_timestamp_micros(micros).map(|dt| dt.naive_utc())
+}
+
+fn integer(value: &DbsyncValue) -> Option<i128> {
+    match value {
+        DbsyncValue::Integer(i) => Some((*i).into()),
+        DbsyncValue::UInteger(u) => Some((*u).into()),
+        _ => None,
+    }
+}
+
+fn mismatch(value: &DbsyncValue, target_type: &DbsyncType) -> Error {
+    Error::Type(format!(
+        "Type mismatch: value {:?} cannot be converted to target type {:?}",
+        value, target_type
+    ))
+}
+
+// 按 T 绑定参数, NULL 也带上 T 的类型, 否则服务器会把它当作 text 而拒绝写入 uuid 等列
+fn bind_typed<'q, T>(
+    query: PgQuery<'q>,
+    value: &DbsyncValue,
+    target_type: &DbsyncType,
+    convert: impl FnOnce(&DbsyncValue) -> Option<T>,
+) -> Result<PgQuery<'q>>
+where
+    T: 'q + Encode<'q, Postgres> + Type<Postgres>,
+{
+    match value {
+        DbsyncValue::Null => Ok(query.bind(None::<T>)),
+        value => convert(value)
+            .map(|v| query.bind(v))
+            .ok_or_else(|| mismatch(value, target_type)),
+    }
+}
+
+// 数组的值为 JSON 数组, 逐个元素转换为 T, null 元素绑定为 NULL
+fn bind_array<'q, T>(
+    query: PgQuery<'q>,
+    value: &DbsyncValue,
+    target_type: &DbsyncType,
+    convert: impl Fn(&Value) -> Option<T>,
+) -> Result<PgQuery<'q>>
+where
+    T: 'q + Type<Postgres> + PgHasArrayType,
+    Vec<Option<T>>: Encode<'q, Postgres> + Type<Postgres>,
+{
+    bind_typed(query, value, target_type, |value| match value {
+        DbsyncValue::Json(Value::Array(items)) => items
+            .iter()
+            .map(|item| match item {
+                Value::Null => Some(None),
+                item => convert(item).map(Some),
+            })
+            .collect::<Option<Vec<_>>>(),
+        _ => None,
+    })
+}
+
+fn json_string(value: &Value) -> Option<String> {
+    value.as_str().map(str::to_string)
+}
+
+// 数组元素的 JSON 形式: 数值和布尔为 JSON 原生值, numeric 为文本以免丢失精度,
+// 日期时间为 ISO 8601 文本, 时间戳带 Z 后缀
+fn decode_array<'r, T>(
+    row: &'r PgRow,
+    column: &PgColumn,
+    to_json: impl Fn(T) -> Option<Value>,
+) -> Result<DbsyncValue>
+where
+    T: Type<Postgres> + PgHasArrayType,
+    Vec<Option<T>>: Decode<'r, Postgres> + Type<Postgres>,
+{
+    let items = row
+        .try_get::<Vec<Option<T>>, _>(column.ordinal())
+        .map_err(|e| Error::Type(e.to_string()))?;
+    items
+        .into_iter()
+        .map(|item| match item {
+            None => Some(Value::Null),
+            Some(item) => to_json(item),
+        })
+        .collect::<Option<Vec<_>>>()
+        .map(|items| DbsyncValue::Json(Value::Array(items)))
+        .ok_or_else(|| {
+            Error::Type(format!(
+                "Array in column {} has elements that cannot be represented in JSON",
+                column.name()
+            ))
+        })
+}
+
+fn get<'r, T>(row: &'r PgRow, column: &PgColumn) -> Result<T>
+where
+    T: Decode<'r, Postgres> + Type<Postgres>,
+{
+    row.try_get(column.ordinal())
+        .map_err(|e| Error::Type(e.to_string()))
+}
+
+// PostgreSQL 数组字面量, 如 {1,NULL,"a \"b\""}
+fn array_literal(items: &[Value]) -> String {
+    let items = items
+        .iter()
+        .map(|item| match item {
+            Value::Null => "NULL".to_string(),
+            Value::Array(items) => array_literal(items),
+            Value::String(s) => quote_element(s),
+            Value::Object(_) => quote_element(&item.to_string()),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>();
+    format!("{{{}}}", items.join(","))
+}
+
+fn quote_element(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[derive(Clone, Default)]
+pub struct PostgresValueConverter;
+
+impl PostgresValueConverter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    // 按目标列对应的 PostgreSQL 类型绑定参数. 扩展协议中参数带有类型,
+    // 服务器不会把 text 隐式转换为 uuid, jsonb 等类型, 所以必须按列类型选择 rust 类型
+    pub(crate) fn bind_as<'q>(
+        &self,
+        query: PgQuery<'q>,
+        value: &DbsyncValue,
+        target_type: &DbsyncType,
+    ) -> Result<PgQuery<'q>> {
+        match target_type {
+            DbsyncType::TinyInt
+            | DbsyncType::SmallInt
+            | DbsyncType::UnsignedTinyInt
+            | DbsyncType::Year => bind_typed(query, value, target_type, |v| {
+                integer(v).and_then(|i| i16::try_from(i).ok())
+            }),
+            DbsyncType::MediumInt
+            | DbsyncType::Int
+            | DbsyncType::UnsignedSmallInt
+            | DbsyncType::UnsignedMediumInt => bind_typed(query, value, target_type, |v| {
+                integer(v).and_then(|i| i32::try_from(i).ok())
+            }),
+            DbsyncType::BigInt
+            | DbsyncType::UnsignedInt
+            | DbsyncType::UnsignedBigInt
+            | DbsyncType::Bit(_) => bind_typed(query, value, target_type, |v| {
+                integer(v).and_then(|i| i64::try_from(i).ok())
+            }),
+            DbsyncType::Float => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Float(f) => Some(*f as f32),
+                _ => None,
+            }),
+            DbsyncType::Double => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Float(f) => Some(*f),
+                _ => None,
+            }),
+            DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _) => {
+                bind_typed(query, value, target_type, |v| match v {
+                    DbsyncValue::Decimal(d) => d.parse::<BigDecimal>().ok(),
+                    _ => None,
+                })
+            }
+            t if t.is_text() => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::String(s) => Some(s.clone()),
+                _ => None,
+            }),
+            // MySQL 中 UUID 常以 CHAR(36) 或 BINARY(16) 保存
+            DbsyncType::Uuid => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::String(s) => Uuid::parse_str(s).ok(),
+                DbsyncValue::Binary(b) => Uuid::from_slice(b).ok(),
+                _ => None,
+            }),
+            DbsyncType::Date => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Date(days) => days_to_date(*days),
+                _ => None,
+            }),
+            DbsyncType::Time(_) => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Time(micros) => micros_to_time(*micros),
+                _ => None,
+            }),
+            DbsyncType::DateTime(_) => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::DateTime(micros) => micros_to_datetime(*micros),
+                _ => None,
+            }),
+            DbsyncType::Timestamp(_) => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Timestamp(micros) => DateTime::<Utc>::from_timestamp_micros(*micros),
+                _ => None,
+            }),
+            DbsyncType::Boolean => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Boolean(b) => Some(*b),
+                _ => None,
+            }),
+            t if t.is_binary() => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Binary(b) => Some(b.clone()),
+                _ => None,
+            }),
+            DbsyncType::Json => bind_typed(query, value, target_type, |v| match v {
+                DbsyncValue::Json(json) => Some(json.clone()),
+                DbsyncValue::String(s) => serde_json::from_str::<Value>(s).ok(),
+                _ => None,
+            }),
+            DbsyncType::Array(element) => self.bind_array_as(query, value, target_type, element),
+            _ => Err(mismatch(value, target_type)),
+        }
+    }
+
+    fn bind_array_as<'q>(
+        &self,
+        query: PgQuery<'q>,
+        value: &DbsyncValue,
+        target_type: &DbsyncType,
+        element: &DbsyncType,
+    ) -> Result<PgQuery<'q>> {
+        let int = |item: &Value| item.as_i64();
+        match element {
+            DbsyncType::TinyInt
+            | DbsyncType::SmallInt
+            | DbsyncType::UnsignedTinyInt
+            | DbsyncType::Year => bind_array(query, value, target_type, |item| {
+                int(item).and_then(|i| i16::try_from(i).ok())
+            }),
+            DbsyncType::MediumInt
+            | DbsyncType::Int
+            | DbsyncType::UnsignedSmallInt
+            | DbsyncType::UnsignedMediumInt => bind_array(query, value, target_type, |item| {
+                int(item).and_then(|i| i32::try_from(i).ok())
+            }),
+            DbsyncType::BigInt
+            | DbsyncType::UnsignedInt
+            | DbsyncType::UnsignedBigInt
+            | DbsyncType::Bit(_) => bind_array(query, value, target_type, int),
+            DbsyncType::Float => bind_array(query, value, target_type, |item| {
+                item.as_f64().map(|f| f as f32)
+            }),
+            DbsyncType::Double => bind_array(query, value, target_type, Value::as_f64),
+            DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _) => {
+                bind_array(query, value, target_type, |item| match item {
+                    Value::String(s) => s.parse::<BigDecimal>().ok(),
+                    Value::Number(n) => n.to_string().parse::<BigDecimal>().ok(),
+                    _ => None,
+                })
+            }
+            t if t.is_text() => bind_array(query, value, target_type, json_string),
+            DbsyncType::Uuid => bind_array(query, value, target_type, |item| {
+                item.as_str().and_then(|s| Uuid::parse_str(s).ok())
+            }),
+            DbsyncType::Boolean => bind_array(query, value, target_type, Value::as_bool),
+            DbsyncType::Date => bind_array(query, value, target_type, |item| {
+                item.as_str()
+                    .and_then(|s| NaiveDate::parse_from_str(s, DATE_FORMAT).ok())
+            }),
+            DbsyncType::Time(_) => bind_array(query, value, target_type, |item| {
+                item.as_str().and_then(|s| s.parse::<NaiveTime>().ok())
+            }),
+            DbsyncType::DateTime(_) => bind_array(query, value, target_type, |item| {
+                item.as_str().and_then(|s| s.parse::<NaiveDateTime>().ok())
+            }),
+            DbsyncType::Timestamp(_) => bind_array(query, value, target_type, |item| {
+                item.as_str().and_then(|s| s.parse::<DateTime<Utc>>().ok())
+            }),
+            DbsyncType::Json => bind_array(query, value, target_type, |item| Some(item.clone())),
+            _ => Err(Error::Type(format!(
+                "Unsupported array element type: {:?}",
+                element
+            ))),
+        }
+    }
+
+    fn decode_array(
+        &self,
+        column: &PgColumn,
+        row: &PgRow,
+        element: &DbsyncType,
+    ) -> Result<DbsyncValue> {
+        match element {
+            DbsyncType::SmallInt => decode_array(row, column, |i: i16| Some(i.into())),
+            DbsyncType::Int => decode_array(row, column, |i: i32| Some(i.into())),
+            DbsyncType::BigInt => decode_array(row, column, |i: i64| Some(i.into())),
+            DbsyncType::Float => decode_array(row, column, |f: f32| {
+                serde_json::Number::from_f64(f.into()).map(Value::Number)
+            }),
+            DbsyncType::Double => decode_array(row, column, |f: f64| {
+                serde_json::Number::from_f64(f).map(Value::Number)
+            }),
+            DbsyncType::Numeric(_, _) => decode_array(row, column, |d: BigDecimal| {
+                Some(Value::String(d.to_plain_string()))
+            }),
+            t if t.is_text() => decode_array(row, column, |s: String| Some(Value::String(s))),
+            DbsyncType::Uuid => {
+                decode_array(row, column, |u: Uuid| Some(Value::String(u.to_string())))
+            }
+            DbsyncType::Boolean => decode_array(row, column, |b: bool| Some(Value::Bool(b))),
+            DbsyncType::Date => decode_array(row, column, |d: NaiveDate| {
+                Some(Value::String(d.format(DATE_FORMAT).to_string()))
+            }),
+            DbsyncType::Time(_) => decode_array(row, column, |t: NaiveTime| {
+                Some(Value::String(t.format(TIME_FORMAT).to_string()))
+            }),
+            DbsyncType::DateTime(_) => decode_array(row, column, |dt: NaiveDateTime| {
+                Some(Value::String(
+                    dt.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(),
+                ))
+            }),
+            DbsyncType::Timestamp(_) => decode_array(row, column, |dt: DateTime<Utc>| {
+                Some(Value::String(
+                    dt.format("%Y-%m-%dT%H:%M:%S%.6fZ").to_string(),
+                ))
+            }),
+            DbsyncType::Json => decode_array(row, column, Some),
+            _ => Err(Error::Type(format!(
+                "Unsupported array element type in column {}: {:?}",
+                column.name(),
+                element
+            ))),
+        }
+    }
+}
+
+impl TypeConverter for PostgresValueConverter {
+    type Column = PgColumn;
+    type Row = PgRow;
+
+    fn to_dbsync_value(
+        &self,
+        column: &Self::Column,
+        row: &Self::Row,
+        dbsync_type: &DbsyncType,
+    ) -> Result<DbsyncValue> {
+        // 任何类型的列都可能为 NULL, 先于按类型解码判断
+        let raw = row
+            .try_get_raw(column.ordinal())
+            .map_err(|e| Error::Type(e.to_string()))?;
+        if raw.is_null() {
+            return Ok(DbsyncValue::Null);
+        }
+
+        match dbsync_type {
+            DbsyncType::SmallInt => get::<i16>(row, column).map(|i| DbsyncValue::Integer(i.into())),
+            DbsyncType::Int => get::<i32>(row, column).map(|i| DbsyncValue::Integer(i.into())),
+            DbsyncType::BigInt => get::<i64>(row, column).map(DbsyncValue::Integer),
+            DbsyncType::Float => get::<f32>(row, column).map(|f| DbsyncValue::Float(f.into())),
+            DbsyncType::Double => get::<f64>(row, column).map(DbsyncValue::Float),
+            // 保留原始的小数位, 不使用科学计数法
+            DbsyncType::Numeric(_, _) => {
+                get::<BigDecimal>(row, column).map(|d| DbsyncValue::Decimal(d.to_plain_string()))
+            }
+            // citext 等扩展类型也以文本传输
+            t if t.is_text() => row
+                .try_get_unchecked::<String, _>(column.ordinal())
+                .map(DbsyncValue::String)
+                .map_err(|e| Error::Type(e.to_string())),
+            DbsyncType::Uuid => {
+                get::<Uuid>(row, column).map(|u| DbsyncValue::String(u.to_string()))
+            }
+            DbsyncType::Date => {
+                get::<NaiveDate>(row, column).map(|d| DbsyncValue::Date(date_to_days(d)))
+            }
+            DbsyncType::Time(_) => {
+                get::<NaiveTime>(row, column).map(|t| DbsyncValue::Time(time_to_micros(t)))
+            }
+            DbsyncType::DateTime(_) => get::<NaiveDateTime>(row, column)
+                .map(|dt| DbsyncValue::DateTime(dt.and_utc().timestamp_micros())),
+            DbsyncType::Timestamp(_) => get::<DateTime<Utc>>(row, column)
+                .map(|dt| DbsyncValue::Timestamp(dt.timestamp_micros())),
+            DbsyncType::Boolean => get::<bool>(row, column).map(DbsyncValue::Boolean),
+            t if t.is_binary() => get::<Vec<u8>>(row, column).map(DbsyncValue::Binary),
+            // json 和 jsonb 都解码为 JSON 文档
+            DbsyncType::Json => get::<Value>(row, column).map(DbsyncValue::Json),
+            DbsyncType::Array(element) => self.decode_array(column, row, element),
+            DbsyncType::Null => Ok(DbsyncValue::Null),
+            other => Err(Error::Type(format!(
+                "Column {} of type {:?} does not come from PostgreSQL",
+                column.name(),
+                other
+            ))),
+        }
+    }
+
+    fn from_dbsync_value(&self, value: &DbsyncValue, target_type: &DbsyncType) -> Result<String> {
+        let invalid = || mismatch(value, target_type);
+        match (value, target_type) {
+            // NULL 没有字符串形式, 需要绑定为 SQL NULL
+            (DbsyncValue::Null, _) => Err(Error::Type(
+                "NULL cannot be converted to a string, bind it as SQL NULL".to_string(),
+            )),
+
+            (DbsyncValue::Integer(i), t) if t.is_signed_integer() || t.is_unsigned_integer() => {
+                Ok(i.to_string())
+            }
+            (DbsyncValue::Integer(i), DbsyncType::Year) => Ok(i.to_string()),
+            (DbsyncValue::UInteger(u), t) if t.is_signed_integer() || t.is_unsigned_integer() => {
+                Ok(u.to_string())
+            }
+            (DbsyncValue::Float(f), DbsyncType::Float | DbsyncType::Double) => Ok(f.to_string()),
+            (DbsyncValue::Decimal(d), DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _)) => {
+                Ok(d.clone())
+            }
+            (DbsyncValue::String(s), t) if t.is_text() || *t == DbsyncType::Uuid => Ok(s.clone()),
+
+            (DbsyncValue::Date(days), DbsyncType::Date) => Ok(days_to_date(*days)
+                .ok_or_else(invalid)?
+                .format(DATE_FORMAT)
+                .to_string()),
+            (DbsyncValue::Time(micros), DbsyncType::Time(_)) => Ok(micros_to_time(*micros)
+                .ok_or_else(invalid)?
+                .format(TIME_FORMAT)
+                .to_string()),
+            (DbsyncValue::DateTime(micros), DbsyncType::DateTime(_)) => {
+                Ok(micros_to_datetime(*micros)
+                    .ok_or_else(invalid)?
+                    .format(DATETIME_FORMAT)
+                    .to_string())
+            }
+            (DbsyncValue::Timestamp(micros), DbsyncType::Timestamp(_)) => {
+                Ok(micros_to_datetime(*micros)
+                    .ok_or_else(invalid)?
+                    .format("%Y-%m-%d %H:%M:%S%.6f+00")
+                    .to_string())
+            }
+
+            (DbsyncValue::Boolean(b), DbsyncType::Boolean) => Ok(b.to_string()),
+
+            // bytea 的十六进制格式
+            (DbsyncValue::Binary(b), t) if t.is_binary() => Ok(format!("\\x{}", hex::encode(b))),
+
+            (DbsyncValue::Json(json), DbsyncType::Json) => Ok(json.to_string()),
+            (DbsyncValue::String(s), DbsyncType::Json) => Ok(s.clone()),
+            (DbsyncValue::Json(Value::Array(items)), DbsyncType::Array(_)) => {
+                Ok(array_literal(items))
+            }
+
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // PostgreSQL 原生类型生成的列类型再解析回来应得到同一个 DbsyncType
+    #[test]
+    fn test_type_round_trip() {
+        let mapper = PostgresTypeMapper;
+        let types = [
+            DbsyncType::SmallInt,
+            DbsyncType::Int,
+            DbsyncType::BigInt,
+            DbsyncType::Float,
+            DbsyncType::Double,
+            DbsyncType::Numeric(38, 10),
+            DbsyncType::Numeric(0, 0),
+            DbsyncType::Char(3),
+            DbsyncType::VarChar(1000),
+            DbsyncType::Text,
+            DbsyncType::Date,
+            DbsyncType::Time(0),
+            DbsyncType::Time(6),
+            DbsyncType::DateTime(3),
+            DbsyncType::Timestamp(6),
+            DbsyncType::Boolean,
+            DbsyncType::LongBlob,
+            DbsyncType::Json,
+            DbsyncType::Uuid,
+            DbsyncType::Array(Box::new(DbsyncType::BigInt)),
+            DbsyncType::Array(Box::new(DbsyncType::VarChar(20))),
+            DbsyncType::Array(Box::new(DbsyncType::Timestamp(6))),
+        ];
+        for dbsync_type in types {
+            let target = mapper.to_target_type(&dbsync_type).unwrap();
+            assert_eq!(
+                mapper.to_dbsync_type(&target).unwrap(),
+                dbsync_type,
+                "{}",
+                target
+            );
+        }
+    }
+
+    // format_type() 的输出
+    #[test]
+    fn test_parse_format_type() {
+        let mapper = PostgresTypeMapper;
+        let cases = [
+            ("integer", DbsyncType::Int),
+            ("character varying(255)", DbsyncType::VarChar(255)),
+            ("character varying", DbsyncType::Text),
+            ("character(2)", DbsyncType::Char(2)),
+            ("bpchar", DbsyncType::Text),
+            ("numeric(12,2)", DbsyncType::Numeric(12, 2)),
+            ("numeric(12)", DbsyncType::Numeric(12, 0)),
+            ("numeric", DbsyncType::Numeric(0, 0)),
+            ("timestamp without time zone", DbsyncType::DateTime(6)),
+            ("timestamp(3) with time zone", DbsyncType::Timestamp(3)),
+            ("time(0) without time zone", DbsyncType::Time(0)),
+            ("jsonb", DbsyncType::Json),
+            ("uuid", DbsyncType::Uuid),
+            ("integer[]", DbsyncType::Array(Box::new(DbsyncType::Int))),
+            (
+                "character varying(10)[]",
+                DbsyncType::Array(Box::new(DbsyncType::VarChar(10))),
+            ),
+            (
+                "timestamp(0) with time zone[]",
+                DbsyncType::Array(Box::new(DbsyncType::Timestamp(0))),
+            ),
+        ];
+        for (source, expected) in cases {
+            assert_eq!(
+                mapper.to_dbsync_type(source).unwrap(),
+                expected,
+                "{}",
+                source
+            );
+        }
+
+        assert!(mapper.to_dbsync_type("tsvector").is_err());
+        assert!(mapper.to_dbsync_type("timestamp(7)").is_err());
+        assert!(mapper.to_dbsync_type("numeric(1000,2)").is_err());
+    }
+
+    // MySQL 的类型都能在 PostgreSQL 中建表
+    #[test]
+    fn test_mysql_types_as_target() {
+        let mapper = PostgresTypeMapper;
+        let cases = [
+            (DbsyncType::UnsignedTinyInt, "SMALLINT"),
+            (DbsyncType::MediumInt, "INTEGER"),
+            (DbsyncType::UnsignedBigInt, "BIGINT"),
+            (DbsyncType::Year, "SMALLINT"),
+            (DbsyncType::Decimal(65, 30), "NUMERIC(65,30)"),
+            (DbsyncType::Numeric(0, 0), "NUMERIC"),
+            (DbsyncType::Enum(vec!["a".into()]), "TEXT"),
+            (DbsyncType::DateTime(0), "TIMESTAMP(0)"),
+            (DbsyncType::Timestamp(3), "TIMESTAMPTZ(3)"),
+            (DbsyncType::Binary(16), "BYTEA"),
+            (DbsyncType::Json, "JSONB"),
+        ];
+        for (dbsync_type, expected) in cases {
+            assert_eq!(mapper.to_target_type(&dbsync_type).unwrap(), expected);
+        }
+        assert!(mapper
+            .to_target_type(&DbsyncType::Array(Box::new(DbsyncType::Array(Box::new(
+                DbsyncType::Int
+            )))))
+            .is_err());
+    }
+
+    #[test]
+    fn test_bind_native_values() {
+        let converter = PostgresValueConverter::new();
+        let cases = [
+            (DbsyncValue::Integer(-1), DbsyncType::TinyInt),
+            (DbsyncValue::UInteger(255), DbsyncType::UnsignedTinyInt),
+            (DbsyncValue::UInteger(1 << 40), DbsyncType::UnsignedBigInt),
+            (DbsyncValue::Float(1.5), DbsyncType::Float),
+            (
+                DbsyncValue::Decimal("1234567890123456789012345678.0123456789".into()),
+                DbsyncType::Numeric(38, 10),
+            ),
+            (DbsyncValue::String("a,c".into()), DbsyncType::Set(vec![])),
+            (
+                DbsyncValue::String("67e55044-10b1-426f-9247-bb680e5fe0c8".into()),
+                DbsyncType::Uuid,
+            ),
+            (DbsyncValue::Binary(vec![7; 16]), DbsyncType::Uuid),
+            (
+                DbsyncValue::Json(json!({"a": [1, "it's"]})),
+                DbsyncType::Json,
+            ),
+            (DbsyncValue::Date(-1), DbsyncType::Date),
+            (DbsyncValue::Time(1), DbsyncType::Time(6)),
+            (DbsyncValue::DateTime(0), DbsyncType::DateTime(0)),
+            (DbsyncValue::Timestamp(1), DbsyncType::Timestamp(6)),
+            (DbsyncValue::Binary(vec![0, 0xff]), DbsyncType::Blob),
+            (
+                DbsyncValue::Json(json!([1, null, 3])),
+                DbsyncType::Array(Box::new(DbsyncType::Int)),
+            ),
+            (
+                DbsyncValue::Json(json!(["2024-02-29T23:59:59.123456Z"])),
+                DbsyncType::Array(Box::new(DbsyncType::Timestamp(6))),
+            ),
+            (DbsyncValue::Null, DbsyncType::Uuid),
+            (
+                DbsyncValue::Null,
+                DbsyncType::Array(Box::new(DbsyncType::Text)),
+            ),
+        ];
+        for (value, target_type) in cases {
+            assert!(
+                converter
+                    .bind_as(sqlx::query("SELECT $1"), &value, &target_type)
+                    .is_ok(),
+                "{:?} as {:?}",
+                value,
+                target_type
+            );
+        }
+
+        let rejected = [
+            (DbsyncValue::Integer(40_000), DbsyncType::SmallInt),
+            (DbsyncValue::UInteger(u64::MAX), DbsyncType::UnsignedBigInt),
+            (DbsyncValue::String("not a uuid".into()), DbsyncType::Uuid),
+            // 负数和超过 24 小时的 TIME 超出 PostgreSQL time 的范围
+            (DbsyncValue::Time(-1), DbsyncType::Time(6)),
+            (DbsyncValue::Time(MICROS_PER_DAY), DbsyncType::Time(6)),
+            (
+                DbsyncValue::Json(json!([1, "x"])),
+                DbsyncType::Array(Box::new(DbsyncType::Int)),
+            ),
+            (DbsyncValue::Integer(1), DbsyncType::Text),
+        ];
+        for (value, target_type) in rejected {
+            assert!(
+                converter
+                    .bind_as(sqlx::query("SELECT $1"), &value, &target_type)
+                    .is_err(),
+                "{:?} as {:?}",
+                value,
+                target_type
+            );
+        }
+    }
+
+    #[test]
+    fn test_literal_values() {
+        let converter = PostgresValueConverter::new();
+        let cases = [
+            (
+                DbsyncValue::Binary(vec![0, 0xff]),
+                DbsyncType::Blob,
+                "\\x00ff",
+            ),
+            (DbsyncValue::Boolean(true), DbsyncType::Boolean, "true"),
+            (
+                DbsyncValue::Timestamp(1_709_251_199_123_456),
+                DbsyncType::Timestamp(6),
+                "2024-02-29 23:59:59.123456+00",
+            ),
+            (
+                DbsyncValue::Time(MICROS_PER_DAY - 1),
+                DbsyncType::Time(6),
+                "23:59:59.999999",
+            ),
+            (
+                DbsyncValue::Json(json!([1, null, "a \"b\"", ["c"]])),
+                DbsyncType::Array(Box::new(DbsyncType::Text)),
+                "{1,NULL,\"a \\\"b\\\"\",{\"c\"}}",
+            ),
+        ];
+        for (value, target_type, expected) in cases {
+            assert_eq!(
+                converter.from_dbsync_value(&value, &target_type).unwrap(),
+                expected
+            );
+        }
+        assert!(converter
+            .from_dbsync_value(&DbsyncValue::Null, &DbsyncType::Text)
+            .is_err());
+    }
+}
@@ -0,0 +1,10 @@
+mod config;
+mod schema;
+mod sink;
+mod source;
+mod type_converter;
+
+pub use config::{SqliteSinkConfig, SqliteSourceConfig, WriteMode};
+pub use schema::SqliteDialect;
+pub use sink::SqliteSink;
+pub use source::SqliteSource;
@@ -0,0 +1,220 @@
+use crate::type_converter::SqliteTypeMapper;
+use dbsync_core::{
+    ddl::Dialect,
+    error::{Error, Result},
+    schema::{ColumnDefault, ColumnSchema, IndexSchema, TableSchema},
+    types::TypeMapper,
+};
+use sqlx::{sqlite::SqlitePool, Row};
+
+// 带引号的表名或列名
+pub(crate) fn quote_identifier(name: &str) -> String {
+    SqliteDialect.quote_identifier(name)
+}
+
+// main 库中的表, 返回是否为 WITHOUT ROWID 表; 不存在或不是表 (如视图) 时为 None
+pub(crate) async fn table_kind(pool: &SqlitePool, table: &str) -> Result<Option<bool>> {
+    sqlx::query_scalar(
+        "SELECT wr FROM pragma_table_list(?) WHERE schema = 'main' AND type = 'table'",
+    )
+    .bind(table)
+    .fetch_optional(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))
+}
+
+// 表的列, 按列顺序排列. 生成列不在 table_info 中, 读取和写入都会跳过
+pub(crate) async fn load_columns(pool: &SqlitePool, table: &str) -> Result<Vec<ColumnSchema>> {
+    let rows = sqlx::query(
+        r#"SELECT name, type, "notnull", dflt_value, pk FROM pragma_table_info(?) ORDER BY cid"#,
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))?;
+
+    // 唯一的 INTEGER 主键列是 rowid 的别名, 插入时不指定值会自动分配
+    let key_columns = rows
+        .iter()
+        .filter(|row| row.get::<i64, _>("pk") > 0)
+        .count();
+    let mut columns = Vec::new();
+    for row in &rows {
+        let name: String = row.get("name");
+        let column_type: String = row.get("type");
+        let data_type = SqliteTypeMapper
+            .to_dbsync_type(&column_type)
+            .map_err(|e| Error::Type(format!("Column {}: {}", name, e)))?;
+        let rowid_alias = key_columns == 1
+            && row.get::<i64, _>("pk") == 1
+            && column_type.eq_ignore_ascii_case("INTEGER");
+
+        let mut column = ColumnSchema::new(name, data_type)
+            .with_nullable(row.get::<i64, _>("notnull") == 0 && !rowid_alias)
+            .with_auto_increment(rowid_alias);
+        column.default = column_default(row.get("dflt_value"));
+        columns.push(column);
+    }
+    Ok(columns)
+}
+
+pub(crate) async fn primary_key_columns(pool: &SqlitePool, table: &str) -> Result<Vec<String>> {
+    sqlx::query_scalar("SELECT name FROM pragma_table_info(?) WHERE pk > 0 ORDER BY pk")
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .map_err(|e| Error::Read(e.to_string()))
+}
+
+// 二级索引和 UNIQUE 约束. 主键的自动索引由主键本身表示; 部分索引和
+// 含表达式的索引无法按列重建, 整个跳过
+async fn load_indexes(pool: &SqlitePool, table: &str) -> Result<Vec<IndexSchema>> {
+    let rows = sqlx::query(
+        r#"SELECT name, "unique" FROM pragma_index_list(?)
+           WHERE origin <> 'pk' AND NOT partial ORDER BY name"#,
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .map_err(|e| Error::Read(e.to_string()))?;
+
+    let mut indexes = Vec::new();
+    for row in &rows {
+        let name: String = row.get("name");
+        let columns: Vec<Option<String>> =
+            sqlx::query_scalar("SELECT name FROM pragma_index_info(?) ORDER BY seqno")
+                .bind(&name)
+                .fetch_all(pool)
+                .await
+                .map_err(|e| Error::Read(e.to_string()))?;
+        if let Some(columns) = columns.into_iter().collect::<Option<Vec<_>>>() {
+            indexes.push(IndexSchema::new(name, columns, row.get("unique")));
+        }
+    }
+    Ok(indexes)
+}
+
+// 从 PRAGMA 读取表结构, SQLite 没有注释
+pub(crate) async fn load_table_schema(pool: &SqlitePool, table: &str) -> Result<TableSchema> {
+    if table_kind(pool, table).await?.is_none() {
+        return Err(Error::Read(format!("Table {} not found", table)));
+    }
+
+    let mut schema = TableSchema::new(table).with_dialect(SqliteDialect.name());
+    schema.columns = load_columns(pool, table).await?;
+    schema.primary_key = primary_key_columns(pool, table).await?;
+    schema.indexes = load_indexes(pool, table).await?;
+    Ok(schema)
+}
+
+// table_info 中的默认值是建表语句中的原文, 如 'it''s', -1, CURRENT_TIMESTAMP, (datetime('now'))
+fn column_default(default: Option<String>) -> Option<ColumnDefault> {
+    let default = default?;
+    if default.eq_ignore_ascii_case("NULL") {
+        return None;
+    }
+    if let Some(literal) = default
+        .strip_prefix('\'')
+        .and_then(|rest| rest.strip_suffix('\''))
+        .filter(|text| !text.replace("''", "").contains('\''))
+    {
+        return Some(ColumnDefault::Literal(literal.replace("''", "'")));
+    }
+    if default.parse::<f64>().is_ok() {
+        return Some(ColumnDefault::Literal(default));
+    }
+    Some(ColumnDefault::Expression(default))
+}
+
+// SQLite 方言: 双引号标识符, 索引是单独的语句, 不支持注释.
+// 只有声明为 INTEGER 的主键才是自增的 rowid 别名, 建表时不保留自增属性
+pub struct SqliteDialect;
+
+impl Dialect for SqliteDialect {
+    fn name(&self) -> &str {
+        "sqlite"
+    }
+
+    fn type_mapper(&self) -> &dyn TypeMapper {
+        &SqliteTypeMapper
+    }
+
+    // CURRENT_TIMESTAMP 不接受精度参数, 只有秒
+    fn current_timestamp(&self, _precision: u8) -> String {
+        "CURRENT_TIMESTAMP".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::ddl::create_table_statements;
+    use dbsync_core::types::DbsyncType;
+
+    #[test]
+    fn test_column_default() {
+        assert_eq!(column_default(None), None);
+        assert_eq!(column_default(Some("NULL".into())), None);
+        assert_eq!(
+            column_default(Some("'it''s'".into())),
+            Some(ColumnDefault::Literal("it's".into()))
+        );
+        assert_eq!(
+            column_default(Some("-1.5".into())),
+            Some(ColumnDefault::Literal("-1.5".into()))
+        );
+        assert_eq!(
+            column_default(Some("CURRENT_TIMESTAMP".into())),
+            Some(ColumnDefault::Expression("CURRENT_TIMESTAMP".into()))
+        );
+        assert_eq!(
+            column_default(Some("'a' || 'b'".into())),
+            Some(ColumnDefault::Expression("'a' || 'b'".into()))
+        );
+    }
+
+    #[test]
+    fn test_sqlite_create_table_statements() {
+        let schema = TableSchema::new("users")
+            .with_column(
+                ColumnSchema::new("id", DbsyncType::UnsignedBigInt)
+                    .with_nullable(false)
+                    .with_auto_increment(true),
+            )
+            .with_column(
+                ColumnSchema::new("Name", DbsyncType::VarChar(255))
+                    .with_default(ColumnDefault::Literal("it's".into()))
+                    .with_comment("名称"),
+            )
+            .with_column(ColumnSchema::new("price", DbsyncType::Decimal(20, 4)))
+            // MySQL 的表达式默认值: 当前时间去掉精度, 其余丢弃
+            .with_column(
+                ColumnSchema::new("created_at", DbsyncType::Timestamp(3))
+                    .with_default(ColumnDefault::Expression("CURRENT_TIMESTAMP(3)".into()))
+                    .with_on_update("CURRENT_TIMESTAMP(3)"),
+            )
+            .with_column(
+                ColumnSchema::new("tags", DbsyncType::Json)
+                    .with_default(ColumnDefault::Expression("(json_array())".into())),
+            )
+            .with_primary_key(vec!["id".into()])
+            .with_index(IndexSchema::new("idx_name", vec!["Name".into()], true))
+            .with_comment("用户表")
+            .with_dialect("mysql");
+
+        assert_eq!(
+            create_table_statements(&SqliteDialect, "target", &schema).unwrap(),
+            vec![
+                "CREATE TABLE \"target\" (\n  \
+                 \"id\" BIGINT UNSIGNED NOT NULL,\n  \
+                 \"Name\" VARCHAR(255) NULL DEFAULT 'it''s',\n  \
+                 \"price\" DECIMAL_TEXT(20,4) NULL,\n  \
+                 \"created_at\" TIMESTAMP(3) NULL DEFAULT CURRENT_TIMESTAMP,\n  \
+                 \"tags\" JSON NULL,\n  \
+                 PRIMARY KEY (\"id\")\n\
+                 )",
+                "CREATE UNIQUE INDEX \"target_idx_name\" ON \"target\" (\"Name\")",
+            ]
+        );
+    }
+}
@@ -0,0 +1,585 @@
+use crate::{
+    config::{connect_options, SqliteSinkConfig, WriteMode},
+    schema::{
+        load_columns, load_table_schema, primary_key_columns, quote_identifier, table_kind,
+        SqliteDialect,
+    },
+    type_converter::{SqliteQuery, SqliteValueConverter},
+};
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchSchema, ConnectorConfig, Context, DataBatch, Operation, PoolStats, PooledSink, Record,
+        SaveMode, ShardedSink, Sink,
+    },
+    ddl::create_table_statements,
+    error::{Error, Result},
+    schema::TableSchema,
+    types::{DbsyncType, DbsyncValue},
+};
+use sqlx::sqlite::{SqliteConnection, SqlitePool, SqlitePoolOptions};
+use sqlx::Transaction;
+use std::collections::HashMap;
+use tracing::{error, info};
+
+// SQLite 3.32 起一条语句最多 32766 个参数
+const MAX_PARAMS: usize = 32766;
+
+#[derive(Clone)]
+pub struct SqliteSink {
+    config: SqliteSinkConfig,
+    pool: Option<SqlitePool>,
+    value_converter: SqliteValueConverter,
+    // SaveMode::Ignore 且目标表已存在时跳过所有写入
+    skip_writes: bool,
+    // 目标表主键, 更新, 删除和 upsert 按它定位行
+    primary_key: Vec<String>,
+    // 目标表的列类型, 日期时间按目标列的小数位数写入
+    column_types: HashMap<String, DbsyncType>,
+}
+
+impl SqliteSink {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = SqliteSinkConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            config,
+            pool: None,
+            value_converter: SqliteValueConverter::new(),
+            skip_writes: false,
+            primary_key: Vec::new(),
+            column_types: HashMap::new(),
+        })
+    }
+
+    fn pool(&self) -> Result<&SqlitePool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    async fn connect(&self, max_connections: u32) -> Result<SqlitePool> {
+        SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(connect_options(
+                &self.config.url,
+                self.config.create_if_missing,
+            )?)
+            .await
+            .map_err(|e| Error::Connection(e.to_string()))
+    }
+
+    // 写事务开始时就获取写锁. 默认的 DEFERRED 事务在第一次写入时才加锁,
+    // 两个写入者互相等待时 SQLite 会直接返回 SQLITE_BUSY 而不等待 busy_timeout
+    async fn begin(pool: &SqlitePool) -> Result<Transaction<'static, sqlx::Sqlite>> {
+        pool.begin_with("BEGIN IMMEDIATE")
+            .await
+            .map_err(|e| Error::Write(e.to_string()))
+    }
+
+    async fn table_exists(&self, pool: &SqlitePool) -> Result<bool> {
+        Ok(table_kind(pool, &self.config.table).await?.is_some())
+    }
+
+    fn primary_key(&self) -> Result<&[String]> {
+        if self.primary_key.is_empty() {
+            return Err(Error::Write(format!(
+                "Table {} has no primary key, updates, deletes and upserts require one",
+                self.config.table
+            )));
+        }
+        Ok(&self.primary_key)
+    }
+
+    // 主键列在批次 schema 中的下标
+    fn key_indices(&self, schema: &BatchSchema) -> Result<Vec<usize>> {
+        self.primary_key()?
+            .iter()
+            .map(|column| {
+                schema.index_of(column).ok_or_else(|| {
+                    Error::Write(format!("Batch has no primary key column {}", column))
+                })
+            })
+            .collect()
+    }
+
+    fn bind_values<'q>(
+        &self,
+        mut query: SqliteQuery<'q>,
+        schema: &BatchSchema,
+        values: &[DbsyncValue],
+        indices: impl IntoIterator<Item = usize>,
+    ) -> Result<SqliteQuery<'q>> {
+        for index in indices {
+            let value = values
+                .get(index)
+                .ok_or_else(|| Error::Write(format!("Record has no value for column {}", index)))?;
+            let field = &schema.fields[index];
+            if *value == DbsyncValue::Null && !field.nullable {
+                return Err(Error::Write(format!(
+                    "Column {} is not nullable but the value is NULL",
+                    field.name
+                )));
+            }
+            let target_type = self
+                .column_types
+                .get(&field.name)
+                .unwrap_or(&field.data_type);
+            query = self.value_converter.bind_as(query, value, target_type)?;
+        }
+        Ok(query)
+    }
+
+    // 按变更类型写入, 相邻的同类记录合并成批, 保持源端的变更顺序
+    async fn write_records(&self, conn: &mut SqliteConnection, batch: &DataBatch) -> Result<()> {
+        let schema = &batch.schema;
+        for group in batch.records.chunk_by(|a, b| a.op == b.op) {
+            match group[0].op {
+                Operation::Insert => {
+                    self.insert_records(conn, self.config.write_mode, schema, group)
+                        .await?
+                }
+                Operation::Upsert => {
+                    self.insert_records(conn, WriteMode::Upsert, schema, group)
+                        .await?
+                }
+                Operation::Update => {
+                    for record in group {
+                        self.update_record(conn, schema, record).await?;
+                    }
+                }
+                Operation::Delete => self.delete_records(conn, schema, group).await?,
+            }
+        }
+        Ok(())
+    }
+
+    async fn insert_records(
+        &self,
+        conn: &mut SqliteConnection,
+        write_mode: WriteMode,
+        schema: &BatchSchema,
+        records: &[Record],
+    ) -> Result<()> {
+        let columns = schema.names().map(str::to_string).collect::<Vec<_>>();
+        let key = match write_mode {
+            WriteMode::Upsert => self.primary_key()?,
+            _ => &[],
+        };
+
+        for chunk in records.chunks(rows_per_statement(columns.len())) {
+            let query = insert_statement(&self.config, write_mode, &columns, key, chunk.len());
+            let mut query_builder = sqlx::query(&query);
+            for record in chunk {
+                query_builder =
+                    self.bind_values(query_builder, schema, &record.values, 0..schema.len())?;
+            }
+
+            query_builder
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| Error::Write(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    // 按变更前的主键更新, 主键本身被修改时也能找到目标行
+    async fn update_record(
+        &self,
+        conn: &mut SqliteConnection,
+        schema: &BatchSchema,
+        record: &Record,
+    ) -> Result<()> {
+        let key = self.key_indices(schema)?;
+        let columns = schema.names().map(str::to_string).collect::<Vec<_>>();
+        let query = update_statement(&self.config.table, &columns, self.primary_key()?);
+
+        let query_builder =
+            self.bind_values(sqlx::query(&query), schema, &record.values, 0..schema.len())?;
+        self.bind_values(query_builder, schema, record.key_values(), key)?
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| Error::Write(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn delete_records(
+        &self,
+        conn: &mut SqliteConnection,
+        schema: &BatchSchema,
+        records: &[Record],
+    ) -> Result<()> {
+        let key = self.key_indices(schema)?;
+        for chunk in records.chunks(rows_per_statement(key.len())) {
+            let query = delete_statement(&self.config.table, self.primary_key()?, chunk.len());
+            let mut query_builder = sqlx::query(&query);
+            for record in chunk {
+                query_builder = self.bind_values(
+                    query_builder,
+                    schema,
+                    record.key_values(),
+                    key.iter().copied(),
+                )?;
+            }
+
+            query_builder
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| Error::Write(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    // 在一个写事务中写入整批数据
+    async fn write_in_transaction(&self, batch: &DataBatch) -> Result<()> {
+        let mut tx = Self::begin(self.pool()?).await?;
+        self.write_records(&mut tx, batch).await?;
+        tx.commit().await.map_err(|e| Error::Write(e.to_string()))
+    }
+
+    // 读取目标表的主键和列类型, 表不存在时保持为空
+    async fn load_target(&mut self) -> Result<()> {
+        let pool = self.pool()?;
+        if !self.table_exists(pool).await? {
+            return Ok(());
+        }
+        let primary_key = primary_key_columns(pool, &self.config.table).await?;
+        let column_types = load_columns(pool, &self.config.table)
+            .await?
+            .into_iter()
+            .map(|column| (column.name, column.data_type))
+            .collect();
+        self.primary_key = primary_key;
+        self.column_types = column_types;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Sink for SqliteSink {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let url = &self.config.url;
+        info!("Connecting to SQLite sink: {}", url);
+
+        match self.connect(self.config.max_connections as u32).await {
+            Ok(pool) => {
+                info!("Successfully connected to SQLite sink");
+                self.pool = Some(pool);
+
+                // 增量同步会再次读到已更新的行, 恢复时未完成的区间会从头重新写入,
+                // 普通 INSERT 会因主键冲突失败
+                if (ctx.incremental || ctx.resume) && self.config.write_mode == WriteMode::Insert {
+                    info!("Incremental or resumed sync, writing with upsert instead of insert");
+                    self.config.write_mode = WriteMode::Upsert;
+                }
+
+                // 从上下文获取表结构, 恢复任务时保留已写入的数据
+                if ctx.resume {
+                    info!("Resuming job, keeping existing table {}", self.config.table);
+                } else if let Some(schema) = &ctx.schema {
+                    self.create_table(schema, self.config.save_mode).await?;
+                }
+
+                self.load_target().await
+            }
+            Err(e) => {
+                let err = Error::Connection(format!("Failed to connect to SQLite sink: {}", e));
+                error!("{}", err);
+                Err(err)
+            }
+        }
+    }
+
+    async fn write_batch(&mut self, batch: DataBatch) -> Result<()> {
+        if batch.records.is_empty() || self.skip_writes {
+            return Ok(());
+        }
+        self.write_in_transaction(&batch).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            info!("Closing SQLite connection");
+            pool.close().await;
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Sink> {
+        Box::new(self.clone())
+    }
+}
+
+#[async_trait]
+impl ShardedSink for SqliteSink {
+    // SQLite 同一时刻只有一个写事务
+    async fn get_worker_count(&self) -> Result<usize> {
+        Ok(1)
+    }
+
+    async fn get_shard_size(&self) -> Result<usize> {
+        Ok(1000) // 每个分片1000条记录
+    }
+
+    async fn write_batch_sharded(
+        &mut self,
+        batches: Vec<DataBatch>,
+        worker_id: usize,
+    ) -> Result<()> {
+        if self.skip_writes {
+            return Ok(());
+        }
+
+        for batch in batches {
+            self.write_in_transaction(&batch)
+                .await
+                .map_err(|e| Error::Write(format!("Worker {}: {}", worker_id, e)))?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_target_schema(&self) -> Result<TableSchema> {
+        load_table_schema(self.pool()?, &self.config.table).await
+    }
+}
+
+#[async_trait]
+impl PooledSink for SqliteSink {
+    async fn get_pool_stats(&self) -> Result<PoolStats> {
+        let pool = self.pool()?;
+
+        Ok(PoolStats {
+            max_connections: self.config.max_connections as u32,
+            active_connections: pool.size(),
+            idle_connections: pool.num_idle() as u32,
+            in_use_connections: pool.size().saturating_sub(pool.num_idle() as u32),
+        })
+    }
+
+    async fn set_pool_size(&mut self, size: u32) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            pool.close().await;
+        }
+
+        self.pool = Some(self.connect(size).await?);
+        self.config.max_connections = size as usize;
+        Ok(())
+    }
+
+    async fn write_batch_pooled(&mut self, batch: DataBatch, conn_id: usize) -> Result<()> {
+        if self.skip_writes {
+            return Ok(());
+        }
+
+        self.write_in_transaction(&batch)
+            .await
+            .map_err(|e| Error::Write(format!("Connection {}: {}", conn_id, e)))
+    }
+
+    async fn get_available_connections(&self) -> Result<usize> {
+        let pool = self.pool()?;
+        Ok(pool.num_idle())
+    }
+
+    async fn create_table(&mut self, schema: &TableSchema, mode: SaveMode) -> Result<()> {
+        let pool = self.pool()?;
+
+        let table = quote_identifier(&self.config.table);
+        if self.table_exists(pool).await? {
+            match mode {
+                SaveMode::Overwrite => {
+                    info!("Dropping existing table: {}", table);
+                    sqlx::query(&format!("DROP TABLE {}", table))
+                        .execute(pool)
+                        .await
+                        .map_err(|e| Error::Write(e.to_string()))?;
+                }
+                SaveMode::Append => {
+                    info!("Appending to existing table: {}", table);
+                    return Ok(());
+                }
+                SaveMode::Truncate => {
+                    // SQLite 没有 TRUNCATE, 不带条件的 DELETE 会直接清空表, 保留表结构和索引
+                    info!("Truncating existing table: {}", table);
+                    sqlx::query(&format!("DELETE FROM {}", table))
+                        .execute(pool)
+                        .await
+                        .map_err(|e| Error::Write(e.to_string()))?;
+                    return Ok(());
+                }
+                SaveMode::ErrorIfExists => {
+                    return Err(Error::Write(format!(
+                        "Target table {} already exists",
+                        table
+                    )));
+                }
+                SaveMode::Ignore => {
+                    info!("Target table {} already exists, skipping writes", table);
+                    self.skip_writes = true;
+                    return Ok(());
+                }
+            }
+        }
+
+        // DDL 可以回滚, 建索引失败时不会留下不完整的表
+        let mut tx = Self::begin(pool).await?;
+        for statement in create_table_statements(&SqliteDialect, &self.config.table, schema)? {
+            info!("Creating table with SQL: {}", statement);
+            sqlx::query(&statement)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| Error::Write(e.to_string()))?;
+        }
+        tx.commit().await.map_err(|e| Error::Write(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+// 每条语句写入的行数, 受参数个数上限约束
+fn rows_per_statement(columns: usize) -> usize {
+    const OPTIMAL_BATCH_SIZE: usize = 500; // 每次写入500条记录
+    (MAX_PARAMS / columns.max(1)).clamp(1, OPTIMAL_BATCH_SIZE)
+}
+
+// rows 行占位符, 如 2 列 2 行为 (?,?),(?,?)
+fn placeholder_rows(columns: usize, rows: usize) -> String {
+    let row = format!("({})", vec!["?"; columns].join(","));
+    vec![row; rows].join(",")
+}
+
+fn quote_identifiers(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// 根据写入模式构造 rows 行的批量写入语句, upsert 按主键 key 判断冲突
+fn insert_statement(
+    config: &SqliteSinkConfig,
+    write_mode: WriteMode,
+    columns: &[String],
+    key: &[String],
+    rows: usize,
+) -> String {
+    let verb = match write_mode {
+        WriteMode::InsertIgnore => "INSERT OR IGNORE",
+        WriteMode::Replace => "INSERT OR REPLACE",
+        WriteMode::Insert | WriteMode::Upsert => "INSERT",
+    };
+    let mut query = format!(
+        "{} INTO {} ({}) VALUES {}",
+        verb,
+        quote_identifier(&config.table),
+        quote_identifiers(columns),
+        placeholder_rows(columns.len(), rows)
+    );
+
+    if write_mode == WriteMode::Upsert {
+        let updates = config
+            .update_columns
+            .as_deref()
+            .unwrap_or(columns)
+            .iter()
+            .map(|c| format!("{0}=excluded.{0}", quote_identifier(c)))
+            .collect::<Vec<_>>();
+        query.push_str(&format!(
+            " ON CONFLICT ({}) DO UPDATE SET {}",
+            quote_identifiers(key),
+            updates.join(",")
+        ));
+    }
+    query
+}
+
+fn update_statement(table: &str, columns: &[String], key: &[String]) -> String {
+    let assignments = columns
+        .iter()
+        .map(|c| format!("{}=?", quote_identifier(c)))
+        .collect::<Vec<_>>();
+    let conditions = key
+        .iter()
+        .map(|c| format!("{}=?", quote_identifier(c)))
+        .collect::<Vec<_>>();
+    format!(
+        "UPDATE {} SET {} WHERE {}",
+        quote_identifier(table),
+        assignments.join(","),
+        conditions.join(" AND ")
+    )
+}
+
+// 按主键批量删除 rows 行, 复合主键使用行值比较
+fn delete_statement(table: &str, key: &[String], rows: usize) -> String {
+    format!(
+        "DELETE FROM {} WHERE ({}) IN (VALUES {})",
+        quote_identifier(table),
+        quote_identifiers(key),
+        placeholder_rows(key.len(), rows)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn insert(write_mode: &str, update_columns: Option<Vec<&str>>, rows: usize) -> String {
+        let mut value = json!({
+            "url": "sqlite://data.db",
+            "table": "users",
+            "write_mode": write_mode
+        });
+        if let Some(columns) = update_columns {
+            value["update_columns"] = json!(columns);
+        }
+        let config = SqliteSinkConfig::from_json(value).unwrap();
+        let columns = vec!["id".to_string(), "name".to_string()];
+        insert_statement(&config, config.write_mode, &columns, &columns[..1], rows)
+    }
+
+    #[test]
+    fn test_insert_statement() {
+        assert_eq!(
+            insert("insert", None, 2),
+            "INSERT INTO \"users\" (\"id\",\"name\") VALUES (?,?),(?,?)"
+        );
+        assert_eq!(
+            insert("insert_ignore", None, 1),
+            "INSERT OR IGNORE INTO \"users\" (\"id\",\"name\") VALUES (?,?)"
+        );
+        assert_eq!(
+            insert("replace", None, 1),
+            "INSERT OR REPLACE INTO \"users\" (\"id\",\"name\") VALUES (?,?)"
+        );
+        assert_eq!(
+            insert("upsert", Some(vec!["name"]), 2),
+            "INSERT INTO \"users\" (\"id\",\"name\") VALUES (?,?),(?,?) \
+             ON CONFLICT (\"id\") DO UPDATE SET \"name\"=excluded.\"name\""
+        );
+    }
+
+    #[test]
+    fn test_update_and_delete_statements() {
+        let columns = vec!["id".to_string(), "name".to_string()];
+        let key = vec!["tenant".to_string(), "id".to_string()];
+
+        assert_eq!(
+            update_statement("users", &columns, &key),
+            "UPDATE \"users\" SET \"id\"=?,\"name\"=? WHERE \"tenant\"=? AND \"id\"=?"
+        );
+        assert_eq!(
+            delete_statement("users", &key, 2),
+            "DELETE FROM \"users\" WHERE (\"tenant\",\"id\") IN (VALUES (?,?),(?,?))"
+        );
+        assert_eq!(rows_per_statement(100), 327);
+    }
+}
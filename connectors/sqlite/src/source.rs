@@ -0,0 +1,274 @@
+use crate::config::{connect_options, SqliteSourceConfig};
+use crate::schema::{load_columns, load_table_schema, quote_identifier, table_kind};
+use crate::type_converter::SqliteValueConverter;
+use async_trait::async_trait;
+use dbsync_core::{
+    connector::{
+        BatchField, BatchSchema, ConnectorConfig, Context, DataBatch, KeyRange, KeyValue,
+        RangeBatch, Record, ShardedSource, Source,
+    },
+    error::{Error, Result},
+    schema::TableSchema,
+    types::{DbsyncValue, TypeConverter},
+};
+use sqlx::sqlite::SqlitePool;
+use sqlx::Row;
+use std::any::Any;
+use std::sync::Arc;
+use tracing::info;
+
+// 按 rowid 切分和读取, 与表是否有主键及主键类型无关
+const ROWID: &str = "rowid";
+
+#[derive(Clone)]
+pub struct SqliteSource {
+    config: SqliteSourceConfig,
+    pool: Option<SqlitePool>,
+    value_converter: SqliteValueConverter,
+    // 输出的列, 按列顺序排列, init 时从 table_info 读取
+    schema: Option<Arc<BatchSchema>>,
+    // read_batch 的游标, 即上一次返回的最后一行的 rowid
+    cursor: Option<i64>,
+}
+
+impl SqliteSource {
+    pub fn new(config: ConnectorConfig) -> Result<Self> {
+        let config = SqliteSourceConfig::from_json(serde_json::Value::Object(
+            serde_json::Map::from_iter(config.properties),
+        ))?;
+        Ok(Self {
+            config,
+            pool: None,
+            value_converter: SqliteValueConverter::new(),
+            schema: None,
+            cursor: None,
+        })
+    }
+
+    fn pool(&self) -> Result<&SqlitePool> {
+        self.pool
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    fn schema(&self) -> Result<&Arc<BatchSchema>> {
+        self.schema
+            .as_ref()
+            .ok_or_else(|| Error::Connection("Not connected".into()))
+    }
+
+    // 读取 rowid 在 (after, end] 内的下一批数据, 返回的 last_key 为最后一行的 rowid
+    async fn read_page(
+        &self,
+        after: Option<i64>,
+        end: Option<i64>,
+        limit: usize,
+    ) -> Result<Option<(DataBatch, i64)>> {
+        let pool = self.pool()?;
+        let schema = self.schema()?;
+
+        // rowid 放在第一列, 输出时去掉; 有 INTEGER 主键时它与主键列的值相同
+        let columns = schema
+            .names()
+            .map(quote_identifier)
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut conditions = Vec::new();
+        if after.is_some() {
+            conditions.push(format!("{} > ?", ROWID));
+        }
+        if end.is_some() {
+            conditions.push(format!("{} <= ?", ROWID));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let query = format!(
+            "SELECT {0}, {1} FROM {2} {3} ORDER BY {0} LIMIT ?",
+            ROWID,
+            columns,
+            quote_identifier(&self.config.table),
+            where_clause
+        );
+        let mut query_builder = sqlx::query(&query);
+        for rowid in after.into_iter().chain(end) {
+            query_builder = query_builder.bind(rowid);
+        }
+        let rows = query_builder
+            .bind(limit as i64)
+            .fetch_all(pool)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+
+        let Some(last) = rows.last() else {
+            return Ok(None);
+        };
+        let last_rowid: i64 = last.get(0);
+
+        let records = rows
+            .iter()
+            .map(|row| {
+                row.columns()[1..]
+                    .iter()
+                    .zip(&schema.fields)
+                    .map(|(col, field)| {
+                        self.value_converter
+                            .to_dbsync_value(col, row, &field.data_type)
+                    })
+                    .collect::<Result<Vec<_>>>()
+                    .map(Record::new)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Some((DataBatch::new(schema.clone(), records), last_rowid)))
+    }
+}
+
+fn rowid_key(rowid: i64) -> KeyValue {
+    KeyValue(vec![DbsyncValue::Integer(rowid)])
+}
+
+fn key_rowid(key: &KeyValue) -> Result<i64> {
+    match key.0.as_slice() {
+        [DbsyncValue::Integer(rowid)] => Ok(*rowid),
+        _ => Err(Error::Read(format!("Invalid rowid key: {:?}", key.0))),
+    }
+}
+
+#[async_trait]
+impl Source for SqliteSource {
+    async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+        let url = &self.config.url;
+        info!("Connecting to SQLite source: {}", url);
+
+        let pool = SqlitePool::connect_with(connect_options(url, false)?)
+            .await
+            .map_err(|e| Error::Connection(format!("Failed to connect to SQLite source: {}", e)))?;
+
+        match table_kind(&pool, &self.config.table).await? {
+            None => {
+                return Err(Error::Read(format!(
+                    "Table {} not found",
+                    self.config.table
+                )))
+            }
+            Some(true) => {
+                return Err(Error::Config(format!(
+                    "Table {} is a WITHOUT ROWID table, reads require a rowid table",
+                    self.config.table
+                )))
+            }
+            Some(false) => {}
+        }
+
+        // 获取表结构并放入 context
+        ctx.set_schema(load_table_schema(&pool, &self.config.table).await?);
+
+        let fields = load_columns(&pool, &self.config.table)
+            .await?
+            .into_iter()
+            .map(|column| {
+                BatchField::new(column.name, column.data_type).with_nullable(column.nullable)
+            })
+            .collect();
+        self.schema = Some(Arc::new(BatchSchema::new(fields)));
+
+        info!("Successfully connected to SQLite source");
+        self.pool = Some(pool);
+        Ok(())
+    }
+
+    async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+        match self.read_page(self.cursor, None, batch_size).await? {
+            Some((batch, last_rowid)) => {
+                self.cursor = Some(last_rowid);
+                Ok(Some(batch))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if let Some(pool) = self.pool.take() {
+            info!("Closing SQLite connection");
+            pool.close().await;
+        }
+        Ok(())
+    }
+
+    fn clone_box(&self) -> Box<dyn Source> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
+        Some(self)
+    }
+}
+
+#[async_trait]
+impl ShardedSource for SqliteSource {
+    async fn get_total_records(&self) -> Result<i64> {
+        let query = format!(
+            "SELECT COUNT(*) FROM {}",
+            quote_identifier(&self.config.table)
+        );
+        sqlx::query_scalar(&query)
+            .fetch_one(self.pool()?)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))
+    }
+
+    // 区间按 rowid 划分, rowid 不在输出的列中
+    async fn get_primary_key(&self) -> Result<Vec<String>> {
+        Ok(vec![ROWID.to_string()])
+    }
+
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+        let query = format!(
+            "SELECT MIN({0}), MAX({0}) FROM {1}",
+            ROWID,
+            quote_identifier(&self.config.table)
+        );
+        let (min, max): (Option<i64>, Option<i64>) = sqlx::query_as(&query)
+            .fetch_one(self.pool()?)
+            .await
+            .map_err(|e| Error::Read(e.to_string()))?;
+        Ok(min
+            .zip(max)
+            .map(|(min, max)| (rowid_key(min), rowid_key(max))))
+    }
+
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        let Some((min, max)) = self.get_id_range().await? else {
+            return Ok(Vec::new());
+        };
+        KeyRange::split_integer(&min, &max, chunks)
+            .ok_or_else(|| Error::Read(format!("Cannot split rowid range {:?}..{:?}", min, max)))
+    }
+
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>> {
+        let after = after.map(key_rowid).transpose()?;
+        let end = key_rowid(end)?;
+        let batch = self
+            .read_page(after, Some(end), self.config.batch_size)
+            .await?;
+        Ok(batch.map(|(batch, last_rowid)| RangeBatch {
+            batch,
+            last_key: rowid_key(last_rowid),
+        }))
+    }
+
+    async fn get_schema(&self) -> Result<TableSchema> {
+        load_table_schema(self.pool()?, &self.config.table).await
+    }
+}
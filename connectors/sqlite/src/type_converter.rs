@@ -0,0 +1,761 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, TimeDelta, Timelike};
+use dbsync_core::{
+    error::{Error, Result},
+    types::{
+        DbsyncType, DbsyncValue, SpatialType, TypeConverter, TypeMapper, MICROS_PER_DAY,
+        MICROS_PER_SECOND,
+    },
+};
+use serde_json::Value;
+use sqlx::query::Query;
+use sqlx::sqlite::{Sqlite, SqliteArguments, SqliteColumn, SqliteRow};
+use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+pub(crate) type SqliteQuery<'q> = Query<'q, Sqlite, SqliteArguments<'q>>;
+
+#[derive(Clone)]
+pub struct SqliteTypeMapper;
+
+impl TypeMapper for SqliteTypeMapper {
+    // SQLite 的列类型只是声明, 先按常见的类型名精确解析, 保留长度和精度;
+    // 其他类型名按 SQLite 的类型亲和性规则归类, 如 UNSIGNED BIG INT → BIGINT
+    fn to_dbsync_type(&self, source_type: &str) -> Result<DbsyncType> {
+        let invalid = || Error::Type(format!("Invalid SQLite type: {}", source_type));
+        let (name, params) = parse_type(source_type).ok_or_else(invalid)?;
+        let param = |index: usize, default: u32| -> Result<u32> {
+            params
+                .get(index)
+                .map_or(Ok(default), |p| p.parse().map_err(|_| invalid()))
+        };
+        let narrow = |value: u32| u8::try_from(value).map_err(|_| invalid());
+        // 秒的小数位数, 未指定时为 6
+        let fsp = || -> Result<u8> {
+            match param(0, 6)? {
+                fsp @ 0..=6 => Ok(fsp as u8),
+                _ => Err(invalid()),
+            }
+        };
+
+        let dbsync_type = match name.as_str() {
+            "TINYINT" => DbsyncType::TinyInt,
+            "SMALLINT" => DbsyncType::SmallInt,
+            "MEDIUMINT" => DbsyncType::MediumInt,
+            "INT" => DbsyncType::Int,
+            // INTEGER 是 64 位整数, INTEGER PRIMARY KEY 即 rowid
+            "INTEGER" | "BIGINT" => DbsyncType::BigInt,
+            "TINYINT UNSIGNED" => DbsyncType::UnsignedTinyInt,
+            "SMALLINT UNSIGNED" => DbsyncType::UnsignedSmallInt,
+            "MEDIUMINT UNSIGNED" => DbsyncType::UnsignedMediumInt,
+            "INT UNSIGNED" | "INTEGER UNSIGNED" => DbsyncType::UnsignedInt,
+            "BIGINT UNSIGNED" => DbsyncType::UnsignedBigInt,
+            "BIT" => match param(0, 1)? {
+                bits @ 1..=64 => DbsyncType::Bit(bits as u8),
+                _ => return Err(invalid()),
+            },
+            "FLOAT" => DbsyncType::Float,
+            "DOUBLE" | "DOUBLE PRECISION" | "REAL" => DbsyncType::Double,
+            // DECIMAL 为 NUMERIC 亲和性, 超过 15 位有效数字的值会被转换成 REAL 而丢失精度;
+            // DECIMAL_TEXT 为 TEXT 亲和性, 以文本原样保存
+            "DECIMAL_TEXT" if params.is_empty() => DbsyncType::Numeric(0, 0),
+            "DECIMAL" | "DECIMAL_TEXT" => {
+                DbsyncType::Decimal(narrow(param(0, 10)?)?, narrow(param(1, 0)?)?)
+            }
+            "NUMERIC" if params.is_empty() => DbsyncType::Numeric(0, 0),
+            "NUMERIC" => DbsyncType::Numeric(narrow(param(0, 10)?)?, narrow(param(1, 0)?)?),
+            "CHAR" | "CHARACTER" | "NCHAR" => DbsyncType::Char(param(0, 1)?),
+            "VARCHAR" | "CHARACTER VARYING" | "NVARCHAR" => match params.first() {
+                Some(_) => DbsyncType::VarChar(param(0, 255)?),
+                None => DbsyncType::Text,
+            },
+            "TINYTEXT" => DbsyncType::TinyText,
+            "TEXT" | "CLOB" => DbsyncType::Text,
+            "MEDIUMTEXT" => DbsyncType::MediumText,
+            "LONGTEXT" => DbsyncType::LongText,
+            "BOOL" | "BOOLEAN" => DbsyncType::Boolean,
+            "DATE" => DbsyncType::Date,
+            "TIME" => DbsyncType::Time(fsp()?),
+            "DATETIME" => DbsyncType::DateTime(fsp()?),
+            "TIMESTAMP" => DbsyncType::Timestamp(fsp()?),
+            "YEAR" => DbsyncType::Year,
+            "BINARY" => DbsyncType::Binary(param(0, 1)?),
+            "VARBINARY" => DbsyncType::VarBinary(param(0, 255)?),
+            "TINYBLOB" => DbsyncType::TinyBlob,
+            "BLOB" => DbsyncType::Blob,
+            "MEDIUMBLOB" => DbsyncType::MediumBlob,
+            "LONGBLOB" => DbsyncType::LongBlob,
+            "JSON" => DbsyncType::Json,
+            "UUID" => DbsyncType::Uuid,
+            "GEOMETRY" => DbsyncType::Spatial(SpatialType::Geometry),
+            "POINT" => DbsyncType::Spatial(SpatialType::Point),
+            "LINESTRING" => DbsyncType::Spatial(SpatialType::LineString),
+            "POLYGON" => DbsyncType::Spatial(SpatialType::Polygon),
+            "MULTIPOINT" => DbsyncType::Spatial(SpatialType::MultiPoint),
+            "MULTILINESTRING" => DbsyncType::Spatial(SpatialType::MultiLineString),
+            "MULTIPOLYGON" => DbsyncType::Spatial(SpatialType::MultiPolygon),
+            "GEOMETRYCOLLECTION" => DbsyncType::Spatial(SpatialType::GeometryCollection),
+            name => affinity_type(name),
+        };
+        Ok(dbsync_type)
+    }
+
+    // 类型名尽量与 MySQL 一致, 读回时能还原为同样的类型;
+    // SQLite 的整数都是 64 位有符号数, BIGINT UNSIGNED 超出范围的值写入时报错
+    fn to_target_type(&self, dbsync_type: &DbsyncType) -> Result<String> {
+        match dbsync_type {
+            DbsyncType::TinyInt => Ok("TINYINT".to_string()),
+            DbsyncType::SmallInt => Ok("SMALLINT".to_string()),
+            DbsyncType::MediumInt => Ok("MEDIUMINT".to_string()),
+            DbsyncType::Int => Ok("INT".to_string()),
+            DbsyncType::BigInt => Ok("BIGINT".to_string()),
+            DbsyncType::UnsignedTinyInt => Ok("TINYINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedSmallInt => Ok("SMALLINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedMediumInt => Ok("MEDIUMINT UNSIGNED".to_string()),
+            DbsyncType::UnsignedInt => Ok("INT UNSIGNED".to_string()),
+            DbsyncType::UnsignedBigInt => Ok("BIGINT UNSIGNED".to_string()),
+            DbsyncType::Bit(n) => Ok(format!("BIT({})", n)),
+            DbsyncType::Float => Ok("FLOAT".to_string()),
+            DbsyncType::Double => Ok("DOUBLE".to_string()),
+            t if t.is_unconstrained_numeric() => Ok("DECIMAL_TEXT".to_string()),
+            DbsyncType::Decimal(p, s) | DbsyncType::Numeric(p, s) => {
+                Ok(format!("DECIMAL_TEXT({},{})", p, s))
+            }
+            DbsyncType::Char(n) => Ok(format!("CHAR({})", n)),
+            DbsyncType::VarChar(n) => Ok(format!("VARCHAR({})", n)),
+            DbsyncType::TinyText => Ok("TINYTEXT".to_string()),
+            DbsyncType::MediumText => Ok("MEDIUMTEXT".to_string()),
+            DbsyncType::LongText => Ok("LONGTEXT".to_string()),
+            // 类型名中不能有字符串, ENUM / SET 以成员文本保存
+            DbsyncType::Text | DbsyncType::Enum(_) | DbsyncType::Set(_) => Ok("TEXT".to_string()),
+            DbsyncType::Date => Ok("DATE".to_string()),
+            DbsyncType::Time(fsp) => Ok(format!("TIME({})", fsp)),
+            DbsyncType::DateTime(fsp) => Ok(format!("DATETIME({})", fsp)),
+            DbsyncType::Timestamp(fsp) => Ok(format!("TIMESTAMP({})", fsp)),
+            DbsyncType::Year => Ok("YEAR".to_string()),
+            DbsyncType::Boolean => Ok("BOOLEAN".to_string()),
+            DbsyncType::Binary(n) => Ok(format!("BINARY({})", n)),
+            DbsyncType::VarBinary(n) => Ok(format!("VARBINARY({})", n)),
+            DbsyncType::TinyBlob => Ok("TINYBLOB".to_string()),
+            DbsyncType::Blob => Ok("BLOB".to_string()),
+            DbsyncType::MediumBlob => Ok("MEDIUMBLOB".to_string()),
+            DbsyncType::LongBlob => Ok("LONGBLOB".to_string()),
+            DbsyncType::Json | DbsyncType::Array(_) => Ok("JSON".to_string()),
+            DbsyncType::Uuid => Ok("UUID".to_string()),
+            DbsyncType::Spatial(spatial) => Ok(match spatial {
+                SpatialType::Geometry => "GEOMETRY",
+                SpatialType::Point => "POINT",
+                SpatialType::LineString => "LINESTRING",
+                SpatialType::Polygon => "POLYGON",
+                SpatialType::MultiPoint => "MULTIPOINT",
+                SpatialType::MultiLineString => "MULTILINESTRING",
+                SpatialType::MultiPolygon => "MULTIPOLYGON",
+                SpatialType::GeometryCollection => "GEOMETRYCOLLECTION",
+            }
+            .to_string()),
+            DbsyncType::Null => Err(Error::Type(
+                "SQLite has no column type for NULL".to_string(),
+            )),
+        }
+    }
+}
+
+// 把类型拆成大写的类型名和括号内的参数, 类型名中的多个空白合并为一个,
+// 如 varchar( 20 ) → ("VARCHAR", ["20"]), unsigned  big int → ("UNSIGNED BIG INT", [])
+fn parse_type(source_type: &str) -> Option<(String, Vec<String>)> {
+    let source_type = source_type.trim().to_uppercase();
+    let (name, params) = match source_type.find('(') {
+        Some(open) => {
+            let close = open + source_type[open..].find(')')?;
+            let params = source_type[open + 1..close]
+                .split(',')
+                .map(|p| p.trim().to_string())
+                .collect();
+            (
+                format!("{} {}", &source_type[..open], &source_type[close + 1..]),
+                params,
+            )
+        }
+        None => (source_type, Vec::new()),
+    };
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    Some((name, params))
+}
+
+// SQLite 的类型亲和性规则 (https://www.sqlite.org/datatype3.html#determination_of_column_affinity),
+// 没有声明类型的列可以保存任何值, 按文本读取
+fn affinity_type(name: &str) -> DbsyncType {
+    if name.contains("INT") {
+        DbsyncType::BigInt
+    } else if ["CHAR", "CLOB", "TEXT"].iter().any(|s| name.contains(s)) || name.is_empty() {
+        DbsyncType::Text
+    } else if name.contains("BLOB") {
+        DbsyncType::LongBlob
+    } else if name.contains("REAL") || name.contains("FLOA") || name.contains("DOUB") {
+        DbsyncType::Double
+    } else {
+        DbsyncType::Numeric(0, 0)
+    }
+}
+
+// SQLite 的存储类型, 每个值按自身的存储类型保存, 与列的声明类型无关
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum SqliteValue {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const DATETIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+// 儒略日 0 距 Unix 纪元的天数, julianday() 的返回值减去它即为 Unix 天数
+const UNIX_EPOCH_JULIAN_DAY: f64 = 2_440_587.5;
+
+fn days_to_date(days: i32) -> Option<NaiveDate> {
+    DateTime::UNIX_EPOCH
+        .date_naive()
+        .checked_add_signed(TimeDelta::days(days.into()))
+}
+
+fn date_to_days(date: NaiveDate) -> i32 {
+    (date - DateTime::UNIX_EPOCH.date_naive()).num_days() as i32
+}
+
+// 保留 fsp 位小数, 多余的位直接截断
+fn fraction(micros: u32, fsp: u8) -> String {
+    if fsp == 0 {
+        return String::new();
+    }
+    format!(".{:06}", micros)[..fsp as usize + 1].to_string()
+}
+
+// 与 SQLite 日期函数相同的格式, 如 2024-01-02 03:04:05.123456
+fn format_datetime(micros: i64, fsp: u8) -> Option<String> {
+    let datetime = DateTime::from_timestamp_micros(micros)?.naive_utc();
+    Some(format!(
+        "{}{}",
+        datetime.format(DATETIME_FORMAT),
+        fraction(datetime.nanosecond() / 1000, fsp)
+    ))
+}
+
+// [-]HH:MM:SS[.ffffff], 小时数可以超过 24, 与 MySQL TIME 的取值范围一致
+fn format_time(micros: i64, fsp: u8) -> String {
+    let sign = if micros < 0 { "-" } else { "" };
+    let micros = micros.unsigned_abs();
+    let seconds = micros / MICROS_PER_SECOND as u64;
+    format!(
+        "{}{:02}:{:02}:{:02}{}",
+        sign,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        fraction((micros % MICROS_PER_SECOND as u64) as u32, fsp)
+    )
+}
+
+fn parse_time(text: &str) -> Option<i64> {
+    let text = text.trim();
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (clock, fraction) = match text.split_once('.') {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (text, None),
+    };
+    let mut parts = clock.split(':');
+    let hours: i64 = parts.next()?.parse().ok()?;
+    let minutes: i64 = parts.next()?.parse().ok()?;
+    let seconds: i64 = parts.next().map_or(Some(0), |s| s.parse().ok())?;
+    if parts.next().is_some() || minutes >= 60 || seconds >= 60 {
+        return None;
+    }
+    let fraction_micros = match fraction {
+        Some(f) if !f.is_empty() && f.len() <= 6 && f.bytes().all(|b| b.is_ascii_digit()) => {
+            format!("{:0<6}", f).parse::<i64>().ok()?
+        }
+        Some(_) => return None,
+        None => 0,
+    };
+    let micros = ((hours * 60 + minutes) * 60 + seconds) * MICROS_PER_SECOND + fraction_micros;
+    Some(if negative { -micros } else { micros })
+}
+
+// 接受 SQLite 日期函数能识别的文本格式, 带时区的时刻转换为 UTC
+fn parse_datetime(text: &str) -> Option<i64> {
+    let text = text.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Some(datetime.timestamp_micros());
+    }
+    let text = text.strip_suffix(['Z', 'z']).unwrap_or(text);
+    [
+        "%Y-%m-%d %H:%M:%S%.f",
+        "%Y-%m-%dT%H:%M:%S%.f",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|format| NaiveDateTime::parse_from_str(text, format).ok())
+    .or_else(|| {
+        NaiveDate::parse_from_str(text, DATE_FORMAT)
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    })
+    .map(|datetime| datetime.and_utc().timestamp_micros())
+}
+
+// 数值形式的时刻: 整数为 Unix 秒数 (unixepoch()), 实数为儒略日 (julianday())
+fn numeric_datetime(value: &SqliteValue) -> Option<i64> {
+    match value {
+        SqliteValue::Integer(seconds) => seconds.checked_mul(MICROS_PER_SECOND),
+        SqliteValue::Real(julian_day) => {
+            let micros = (julian_day - UNIX_EPOCH_JULIAN_DAY) * MICROS_PER_DAY as f64;
+            micros.is_finite().then_some(micros.round() as i64)
+        }
+        _ => None,
+    }
+}
+
+fn mismatch(value: &DbsyncValue, target_type: &DbsyncType) -> Error {
+    Error::Type(format!(
+        "Type mismatch: value {:?} cannot be converted to target type {:?}",
+        value, target_type
+    ))
+}
+
+// 按值的类型选择存储类型, 目标列的亲和性会再做转换, 如文本写入 INTEGER 列时转为整数.
+// 日期时间以文本保存, 小数位数与目标列一致
+pub(crate) fn to_sqlite_value(
+    value: &DbsyncValue,
+    target_type: &DbsyncType,
+) -> Result<SqliteValue> {
+    let invalid = || mismatch(value, target_type);
+    let fsp = match target_type {
+        DbsyncType::Time(fsp) | DbsyncType::DateTime(fsp) | DbsyncType::Timestamp(fsp) => *fsp,
+        _ => 6,
+    };
+    Ok(match value {
+        DbsyncValue::Null => SqliteValue::Null,
+        DbsyncValue::Integer(i) => SqliteValue::Integer(*i),
+        DbsyncValue::UInteger(u) => match i64::try_from(*u) {
+            Ok(i) => SqliteValue::Integer(i),
+            // 文本和精确数值列可以保存超出 64 位有符号整数范围的值
+            Err(_) if target_type.is_text() || matches!(target_type, DbsyncType::Decimal(_, _)) => {
+                SqliteValue::Text(u.to_string())
+            }
+            Err(_) => return Err(invalid()),
+        },
+        DbsyncValue::Float(f) => SqliteValue::Real(*f),
+        DbsyncValue::Decimal(d) => SqliteValue::Text(d.clone()),
+        DbsyncValue::String(s) => SqliteValue::Text(s.clone()),
+        DbsyncValue::Date(days) => SqliteValue::Text(
+            days_to_date(*days)
+                .ok_or_else(invalid)?
+                .format(DATE_FORMAT)
+                .to_string(),
+        ),
+        DbsyncValue::Time(micros) => SqliteValue::Text(format_time(*micros, fsp)),
+        DbsyncValue::DateTime(micros) | DbsyncValue::Timestamp(micros) => {
+            SqliteValue::Text(format_datetime(*micros, fsp).ok_or_else(invalid)?)
+        }
+        DbsyncValue::Boolean(b) => SqliteValue::Integer(*b as i64),
+        DbsyncValue::Binary(b) => SqliteValue::Blob(b.clone()),
+        DbsyncValue::Json(json) => SqliteValue::Text(json.to_string()),
+    })
+}
+
+// 按列的声明类型解释读到的值. 亲和性只是倾向, 同一列中可能混有不同存储类型的值,
+// 如 NUMERIC 列中的整数和文本, 能无损转换的都接受
+pub(crate) fn from_sqlite_value(
+    value: SqliteValue,
+    dbsync_type: &DbsyncType,
+) -> Result<DbsyncValue> {
+    let invalid = |value: &SqliteValue| {
+        Error::Type(format!(
+            "Value {:?} cannot be read as {:?}",
+            value, dbsync_type
+        ))
+    };
+    if value == SqliteValue::Null {
+        return Ok(DbsyncValue::Null);
+    }
+
+    let converted = match (&value, dbsync_type) {
+        (_, DbsyncType::Null) => Some(DbsyncValue::Null),
+        (SqliteValue::Integer(i), DbsyncType::Bit(_)) => {
+            u64::try_from(*i).ok().map(DbsyncValue::UInteger)
+        }
+        (SqliteValue::Integer(i), t) if t.is_unsigned_integer() => {
+            (*i >= 0).then_some(DbsyncValue::Integer(*i))
+        }
+        (SqliteValue::Text(s), t) if t.is_unsigned_integer() => {
+            s.trim()
+                .parse::<u64>()
+                .ok()
+                .map(|u| match i64::try_from(u) {
+                    Ok(i) if !matches!(t, DbsyncType::Bit(_)) => DbsyncValue::Integer(i),
+                    _ => DbsyncValue::UInteger(u),
+                })
+        }
+        (SqliteValue::Integer(i), t) if t.is_signed_integer() || *t == DbsyncType::Year => {
+            Some(DbsyncValue::Integer(*i))
+        }
+        (SqliteValue::Real(f), t) if t.is_signed_integer() || *t == DbsyncType::Year => {
+            (f.fract() == 0.0 && f.abs() < i64::MAX as f64)
+                .then_some(DbsyncValue::Integer(*f as i64))
+        }
+        (SqliteValue::Text(s), t) if t.is_signed_integer() || *t == DbsyncType::Year => {
+            s.trim().parse().ok().map(DbsyncValue::Integer)
+        }
+        (SqliteValue::Integer(i), DbsyncType::Float | DbsyncType::Double) => {
+            Some(DbsyncValue::Float(*i as f64))
+        }
+        (SqliteValue::Real(f), DbsyncType::Float | DbsyncType::Double) => {
+            Some(DbsyncValue::Float(*f))
+        }
+        (SqliteValue::Text(s), DbsyncType::Float | DbsyncType::Double) => {
+            s.trim().parse().ok().map(DbsyncValue::Float)
+        }
+        (SqliteValue::Integer(i), DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _)) => {
+            Some(DbsyncValue::Decimal(i.to_string()))
+        }
+        // NUMERIC 亲和性的列中的小数保存为 REAL, 按列的小数位数格式化, 未限制精度时原样输出
+        (SqliteValue::Real(f), t) if t.is_unconstrained_numeric() => {
+            f.is_finite().then(|| DbsyncValue::Decimal(f.to_string()))
+        }
+        (SqliteValue::Real(f), DbsyncType::Decimal(_, s) | DbsyncType::Numeric(_, s)) => f
+            .is_finite()
+            .then(|| DbsyncValue::Decimal(format!("{:.*}", *s as usize, f))),
+        (SqliteValue::Text(s), DbsyncType::Decimal(_, _) | DbsyncType::Numeric(_, _)) => {
+            let s = s.trim();
+            s.parse::<f64>()
+                .is_ok()
+                .then(|| DbsyncValue::Decimal(s.to_string()))
+        }
+        (SqliteValue::Text(s), t) if t.is_text() || *t == DbsyncType::Uuid => {
+            Some(DbsyncValue::String(s.clone()))
+        }
+        (SqliteValue::Integer(i), t) if t.is_text() => Some(DbsyncValue::String(i.to_string())),
+        (SqliteValue::Real(f), t) if t.is_text() => Some(DbsyncValue::String(f.to_string())),
+        (SqliteValue::Blob(b), t) if t.is_text() || *t == DbsyncType::Uuid => {
+            String::from_utf8(b.clone()).ok().map(DbsyncValue::String)
+        }
+        (SqliteValue::Text(s), DbsyncType::Date) => {
+            NaiveDate::parse_from_str(s.trim(), DATE_FORMAT)
+                .ok()
+                .map(date_to_days)
+                .or_else(|| {
+                    parse_datetime(s).map(|micros| micros.div_euclid(MICROS_PER_DAY) as i32)
+                })
+                .map(DbsyncValue::Date)
+        }
+        (value, DbsyncType::Date) => numeric_datetime(value)
+            .map(|micros| DbsyncValue::Date(micros.div_euclid(MICROS_PER_DAY) as i32)),
+        (SqliteValue::Text(s), DbsyncType::Time(_)) => parse_time(s).map(DbsyncValue::Time),
+        (SqliteValue::Integer(seconds), DbsyncType::Time(_)) => seconds
+            .checked_mul(MICROS_PER_SECOND)
+            .map(DbsyncValue::Time),
+        (SqliteValue::Text(s), DbsyncType::DateTime(_)) => {
+            parse_datetime(s).map(DbsyncValue::DateTime)
+        }
+        (value, DbsyncType::DateTime(_)) => numeric_datetime(value).map(DbsyncValue::DateTime),
+        (SqliteValue::Text(s), DbsyncType::Timestamp(_)) => {
+            parse_datetime(s).map(DbsyncValue::Timestamp)
+        }
+        (value, DbsyncType::Timestamp(_)) => numeric_datetime(value).map(DbsyncValue::Timestamp),
+        (SqliteValue::Integer(i), DbsyncType::Boolean) => Some(DbsyncValue::Boolean(*i != 0)),
+        (SqliteValue::Real(f), DbsyncType::Boolean) => Some(DbsyncValue::Boolean(*f != 0.0)),
+        (SqliteValue::Text(s), DbsyncType::Boolean) => match s.trim().to_lowercase().as_str() {
+            "1" | "true" | "t" => Some(DbsyncValue::Boolean(true)),
+            "0" | "false" | "f" => Some(DbsyncValue::Boolean(false)),
+            _ => None,
+        },
+        (SqliteValue::Blob(b), t) if t.is_binary() => Some(DbsyncValue::Binary(b.clone())),
+        (SqliteValue::Text(s), t) if t.is_binary() => {
+            Some(DbsyncValue::Binary(s.as_bytes().to_vec()))
+        }
+        (SqliteValue::Text(s), DbsyncType::Json | DbsyncType::Array(_)) => {
+            serde_json::from_str::<Value>(s).ok().map(DbsyncValue::Json)
+        }
+        (SqliteValue::Integer(i), DbsyncType::Json) => Some(DbsyncValue::Json((*i).into())),
+        (SqliteValue::Real(f), DbsyncType::Json) => {
+            serde_json::Number::from_f64(*f).map(|n| DbsyncValue::Json(Value::Number(n)))
+        }
+        _ => None,
+    };
+    converted.ok_or_else(|| invalid(&value))
+}
+
+#[derive(Clone, Default)]
+pub struct SqliteValueConverter;
+
+impl SqliteValueConverter {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub(crate) fn bind_as<'q>(
+        &self,
+        query: SqliteQuery<'q>,
+        value: &DbsyncValue,
+        target_type: &DbsyncType,
+    ) -> Result<SqliteQuery<'q>> {
+        Ok(match to_sqlite_value(value, target_type)? {
+            SqliteValue::Null => query.bind(None::<String>),
+            SqliteValue::Integer(i) => query.bind(i),
+            SqliteValue::Real(f) => query.bind(f),
+            SqliteValue::Text(s) => query.bind(s),
+            SqliteValue::Blob(b) => query.bind(b),
+        })
+    }
+}
+
+fn get<'r, T>(row: &'r SqliteRow, column: &SqliteColumn) -> Result<T>
+where
+    T: sqlx::Decode<'r, Sqlite>,
+{
+    row.try_get_unchecked(column.ordinal())
+        .map_err(|e| Error::Type(e.to_string()))
+}
+
+impl TypeConverter for SqliteValueConverter {
+    type Column = SqliteColumn;
+    type Row = SqliteRow;
+
+    fn to_dbsync_value(
+        &self,
+        column: &Self::Column,
+        row: &Self::Row,
+        dbsync_type: &DbsyncType,
+    ) -> Result<DbsyncValue> {
+        let raw = row
+            .try_get_raw(column.ordinal())
+            .map_err(|e| Error::Type(e.to_string()))?;
+        // 按值实际的存储类型读取, 而不是列的声明类型.
+        // NULL 值的 type_info 是列的声明类型, 需要先单独判断
+        if raw.is_null() {
+            return Ok(DbsyncValue::Null);
+        }
+        let value = match raw.type_info().name() {
+            "INTEGER" => SqliteValue::Integer(get(row, column)?),
+            "REAL" => SqliteValue::Real(get(row, column)?),
+            "TEXT" => SqliteValue::Text(get(row, column)?),
+            _ => SqliteValue::Blob(get(row, column)?),
+        };
+        from_sqlite_value(value, dbsync_type)
+            .map_err(|e| Error::Type(format!("Column {}: {}", column.name(), e)))
+    }
+
+    fn from_dbsync_value(&self, value: &DbsyncValue, target_type: &DbsyncType) -> Result<String> {
+        match to_sqlite_value(value, target_type)? {
+            // NULL 没有字符串形式, 需要绑定为 SQL NULL
+            SqliteValue::Null => Err(Error::Type(
+                "NULL cannot be converted to a string, bind it as SQL NULL".to_string(),
+            )),
+            SqliteValue::Integer(i) => Ok(i.to_string()),
+            SqliteValue::Real(f) => Ok(f.to_string()),
+            SqliteValue::Text(s) => Ok(s),
+            SqliteValue::Blob(_) => Err(mismatch(value, target_type)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_dbsync_type() {
+        let mapper = SqliteTypeMapper;
+        let cases = [
+            ("INTEGER", DbsyncType::BigInt),
+            ("int", DbsyncType::Int),
+            ("INT UNSIGNED", DbsyncType::UnsignedInt),
+            ("decimal(20, 4)", DbsyncType::Decimal(20, 4)),
+            ("DECIMAL_TEXT(20,4)", DbsyncType::Decimal(20, 4)),
+            ("NUMERIC", DbsyncType::Numeric(0, 0)),
+            ("VARCHAR(20)", DbsyncType::VarChar(20)),
+            ("DATETIME", DbsyncType::DateTime(6)),
+            ("TIMESTAMP(3)", DbsyncType::Timestamp(3)),
+            ("BINARY(16)", DbsyncType::Binary(16)),
+            ("POINT", DbsyncType::Spatial(SpatialType::Point)),
+            // 未知的类型名按亲和性归类
+            ("UNSIGNED BIG INT", DbsyncType::BigInt),
+            ("NATIVE CHARACTER(70)", DbsyncType::Text),
+            ("", DbsyncType::Text),
+            ("BINARY LARGE OBJECT BLOB", DbsyncType::LongBlob),
+            ("FLOATING POINT", DbsyncType::BigInt),
+            ("DOUBLE FLOAT", DbsyncType::Double),
+            ("MONEY", DbsyncType::Numeric(0, 0)),
+        ];
+        for (source_type, expected) in cases {
+            assert_eq!(
+                mapper.to_dbsync_type(source_type).unwrap(),
+                expected,
+                "{}",
+                source_type
+            );
+        }
+        assert!(mapper.to_dbsync_type("TIME(7)").is_err());
+        assert!(mapper.to_dbsync_type("VARCHAR(20").is_err());
+    }
+
+    #[test]
+    fn test_target_type_round_trip() {
+        let mapper = SqliteTypeMapper;
+        for dbsync_type in [
+            DbsyncType::TinyInt,
+            DbsyncType::UnsignedBigInt,
+            DbsyncType::Bit(8),
+            DbsyncType::Decimal(20, 4),
+            DbsyncType::Numeric(0, 0),
+            DbsyncType::Char(2),
+            DbsyncType::MediumText,
+            DbsyncType::Time(3),
+            DbsyncType::DateTime(0),
+            DbsyncType::Timestamp(6),
+            DbsyncType::Year,
+            DbsyncType::Boolean,
+            DbsyncType::VarBinary(16),
+            DbsyncType::Json,
+            DbsyncType::Uuid,
+            DbsyncType::Spatial(SpatialType::MultiPolygon),
+        ] {
+            let target = mapper.to_target_type(&dbsync_type).unwrap();
+            assert_eq!(mapper.to_dbsync_type(&target).unwrap(), dbsync_type);
+        }
+        assert_eq!(
+            mapper
+                .to_target_type(&DbsyncType::Enum(vec!["a".into()]))
+                .unwrap(),
+            "TEXT"
+        );
+        assert!(mapper.to_target_type(&DbsyncType::Null).is_err());
+    }
+
+    #[test]
+    fn test_temporal_formats() {
+        assert_eq!(
+            format_time(-(3600 + 1) * MICROS_PER_SECOND - 500_000, 3),
+            "-01:00:01.500"
+        );
+        assert_eq!(format_time(838 * 3600 * MICROS_PER_SECOND, 0), "838:00:00");
+        assert_eq!(parse_time("-01:00:01.5"), Some(-3_601_500_000));
+        assert_eq!(parse_time("838:00"), Some(838 * 3600 * MICROS_PER_SECOND));
+        assert_eq!(parse_time("12:60:00"), None);
+
+        let micros = 1_704_164_645_123_456;
+        assert_eq!(
+            format_datetime(micros, 6).unwrap(),
+            "2024-01-02 03:04:05.123456"
+        );
+        assert_eq!(format_datetime(micros, 0).unwrap(), "2024-01-02 03:04:05");
+        for text in [
+            "2024-01-02 03:04:05.123456",
+            "2024-01-02T03:04:05.123456Z",
+            "2024-01-02T05:04:05.123456+02:00",
+        ] {
+            assert_eq!(parse_datetime(text), Some(micros), "{}", text);
+        }
+        assert_eq!(parse_datetime("2024-01-02"), Some(1_704_153_600_000_000));
+        assert_eq!(parse_datetime("yesterday"), None);
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let cases = [
+            (DbsyncValue::Integer(-5), DbsyncType::Int),
+            (DbsyncValue::UInteger(255), DbsyncType::Bit(8)),
+            (DbsyncValue::Float(1.5), DbsyncType::Double),
+            (
+                DbsyncValue::Decimal("1234567890123456.7891".into()),
+                DbsyncType::Decimal(20, 4),
+            ),
+            (DbsyncValue::String("héllo".into()), DbsyncType::VarChar(10)),
+            (DbsyncValue::Date(-1), DbsyncType::Date),
+            (DbsyncValue::Time(-3_601_500_000), DbsyncType::Time(6)),
+            (
+                DbsyncValue::DateTime(1_704_164_645_123_456),
+                DbsyncType::DateTime(6),
+            ),
+            (DbsyncValue::Timestamp(-1), DbsyncType::Timestamp(6)),
+            (DbsyncValue::Boolean(true), DbsyncType::Boolean),
+            (DbsyncValue::Binary(vec![0, 255]), DbsyncType::Blob),
+            (DbsyncValue::Json(json!({"a": [1, "b"]})), DbsyncType::Json),
+            (DbsyncValue::Null, DbsyncType::Json),
+        ];
+        for (value, dbsync_type) in cases {
+            let stored = to_sqlite_value(&value, &dbsync_type).unwrap();
+            assert_eq!(
+                from_sqlite_value(stored, &dbsync_type).unwrap(),
+                value,
+                "{:?}",
+                dbsync_type
+            );
+        }
+
+        // 超出 i64 的无符号整数只能写入文本或精确数值列
+        assert!(to_sqlite_value(
+            &DbsyncValue::UInteger(u64::MAX),
+            &DbsyncType::UnsignedBigInt
+        )
+        .is_err());
+        assert_eq!(
+            to_sqlite_value(
+                &DbsyncValue::UInteger(u64::MAX),
+                &DbsyncType::Decimal(20, 0)
+            )
+            .unwrap(),
+            SqliteValue::Text(u64::MAX.to_string())
+        );
+        // 时间按目标列的小数位数截断
+        assert_eq!(
+            to_sqlite_value(
+                &DbsyncValue::DateTime(1_704_164_645_123_456),
+                &DbsyncType::DateTime(3)
+            )
+            .unwrap(),
+            SqliteValue::Text("2024-01-02 03:04:05.123".into())
+        );
+    }
+
+    #[test]
+    fn test_read_by_storage_class() {
+        let read = |value, dbsync_type| from_sqlite_value(value, &dbsync_type).unwrap();
+        assert_eq!(
+            read(SqliteValue::Real(12.5), DbsyncType::Decimal(10, 2)),
+            DbsyncValue::Decimal("12.50".into())
+        );
+        assert_eq!(
+            read(SqliteValue::Real(12.125), DbsyncType::Numeric(0, 0)),
+            DbsyncValue::Decimal("12.125".into())
+        );
+        assert_eq!(
+            read(SqliteValue::Integer(3), DbsyncType::Text),
+            DbsyncValue::String("3".into())
+        );
+        assert_eq!(
+            read(SqliteValue::Real(3.0), DbsyncType::BigInt),
+            DbsyncValue::Integer(3)
+        );
+        assert_eq!(
+            read(SqliteValue::Integer(86_400), DbsyncType::DateTime(0)),
+            DbsyncValue::DateTime(86_400 * MICROS_PER_SECOND)
+        );
+        assert_eq!(
+            read(SqliteValue::Real(2_440_588.5), DbsyncType::Date),
+            DbsyncValue::Date(1)
+        );
+        assert_eq!(
+            read(
+                SqliteValue::Text("18446744073709551615".into()),
+                DbsyncType::UnsignedBigInt
+            ),
+            DbsyncValue::UInteger(u64::MAX)
+        );
+        assert!(from_sqlite_value(SqliteValue::Text("abc".into()), &DbsyncType::Int).is_err());
+        assert!(from_sqlite_value(SqliteValue::Blob(vec![1]), &DbsyncType::Date).is_err());
+    }
+}
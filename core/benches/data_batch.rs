@@ -0,0 +1,154 @@
+// 对比按列名存放的旧行格式 (每行一个 HashMap) 与共享 schema 的批次格式
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use dbsync_core::connector::{BatchField, BatchSchema, DataBatch, Record};
+use dbsync_core::{DbsyncType, DbsyncValue};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const ROWS: usize = 10_000;
+
+type MapRecord = HashMap<String, (DbsyncValue, DbsyncType)>;
+
+fn columns() -> Vec<(String, DbsyncType)> {
+    vec![
+        ("id".to_string(), DbsyncType::BigInt),
+        ("name".to_string(), DbsyncType::VarChar(255)),
+        ("email".to_string(), DbsyncType::VarChar(255)),
+        ("age".to_string(), DbsyncType::Int),
+        ("balance".to_string(), DbsyncType::Decimal(20, 6)),
+        ("active".to_string(), DbsyncType::Boolean),
+        ("created_at".to_string(), DbsyncType::DateTime(6)),
+        ("note".to_string(), DbsyncType::Text),
+    ]
+}
+
+fn row_values(i: usize) -> Vec<DbsyncValue> {
+    vec![
+        DbsyncValue::Integer(i as i64),
+        DbsyncValue::String(format!("user{}", i)),
+        DbsyncValue::String(format!("user{}@example.com", i)),
+        DbsyncValue::Integer((i % 100) as i64),
+        DbsyncValue::Decimal("1234.500000".to_string()),
+        DbsyncValue::Boolean(i.is_multiple_of(2)),
+        DbsyncValue::DateTime((1_700_000_000 + i as i64) * 1_000_000),
+        DbsyncValue::Null,
+    ]
+}
+
+fn build_map_records(columns: &[(String, DbsyncType)]) -> Vec<MapRecord> {
+    (0..ROWS)
+        .map(|i| {
+            columns
+                .iter()
+                .zip(row_values(i))
+                .map(|((name, data_type), value)| (name.clone(), (value, data_type.clone())))
+                .collect()
+        })
+        .collect()
+}
+
+fn build_batch(columns: &[(String, DbsyncType)]) -> DataBatch {
+    let schema = BatchSchema::new(
+        columns
+            .iter()
+            .map(|(name, data_type)| BatchField::new(name, data_type.clone()))
+            .collect(),
+    );
+    DataBatch::new(
+        Arc::new(schema),
+        (0..ROWS).map(|i| Record::new(row_values(i))).collect(),
+    )
+}
+
+fn bench_build(c: &mut Criterion) {
+    let columns = columns();
+    let mut group = c.benchmark_group("build");
+    group.bench_function("map_records", |b| {
+        b.iter(|| black_box(build_map_records(&columns)))
+    });
+    group.bench_function("data_batch", |b| {
+        b.iter(|| black_box(build_batch(&columns)))
+    });
+    group.finish();
+}
+
+fn bench_clone(c: &mut Criterion) {
+    let columns = columns();
+    let records = build_map_records(&columns);
+    let batch = build_batch(&columns);
+    let mut group = c.benchmark_group("clone");
+    group.bench_function("map_records", |b| b.iter(|| black_box(records.clone())));
+    group.bench_function("data_batch", |b| b.iter(|| black_box(batch.clone())));
+    group.finish();
+}
+
+fn bench_rename(c: &mut Criterion) {
+    let columns = columns();
+    let mut group = c.benchmark_group("rename");
+    group.bench_function("map_records", |b| {
+        b.iter_batched(
+            || build_map_records(&columns),
+            |mut records| {
+                for record in &mut records {
+                    if let Some(value) = record.remove("name") {
+                        record.insert("full_name".to_string(), value);
+                    }
+                }
+                records
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("data_batch", |b| {
+        b.iter_batched(
+            || build_batch(&columns),
+            |mut batch| {
+                let schema = Arc::make_mut(&mut batch.schema);
+                if let Some(index) = schema.index_of("name") {
+                    schema.fields[index].name = "full_name".to_string();
+                }
+                batch
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+// 按列名读取每行的一个值, 对应 sink 绑定参数时的访问模式
+fn bench_lookup(c: &mut Criterion) {
+    let columns = columns();
+    let records = build_map_records(&columns);
+    let batch = build_batch(&columns);
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("map_records", |b| {
+        b.iter(|| {
+            records
+                .iter()
+                .filter(|record| {
+                    matches!(record.get("active"), Some((DbsyncValue::Boolean(true), _)))
+                })
+                .count()
+        })
+    });
+    group.bench_function("data_batch", |b| {
+        b.iter(|| {
+            let index = batch.schema.index_of("active").unwrap();
+            batch
+                .records
+                .iter()
+                .filter(|record| matches!(record.values[index], DbsyncValue::Boolean(true)))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_build,
+    bench_clone,
+    bench_rename,
+    bench_lookup
+);
+criterion_main!(benches);
@@ -0,0 +1,871 @@
+// DataBatch 与 Arrow RecordBatch 之间的转换
+//
+// Arrow 字段的 metadata 中保存原始的 DbsyncType, 转换回来时类型保持不变;
+// 没有 metadata 的 RecordBatch (如其他系统产生的) 按 Arrow 类型推断 DbsyncType.
+// Arrow 只携带行值, Record 的 op / before / position 不参与转换.
+use crate::connector::{BatchField, BatchSchema, DataBatch, Record};
+use crate::error::{Error, Result};
+use crate::{DbsyncType, DbsyncValue, MICROS_PER_DAY, MICROS_PER_SECOND};
+use arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, Decimal128Builder, Decimal256Builder,
+    DurationMicrosecondBuilder, Float32Builder, Float64Builder, Int64Builder, StringBuilder,
+    TimestampMicrosecondBuilder, UInt64Builder,
+};
+use arrow_array::cast::AsArray;
+use arrow_array::types::{
+    Date32Type, Date64Type, Decimal128Type, Decimal256Type, DurationMicrosecondType,
+    DurationMillisecondType, DurationNanosecondType, DurationSecondType, Float32Type, Float64Type,
+    Int16Type, Int32Type, Int64Type, Int8Type, Time32MillisecondType, Time32SecondType,
+    Time64MicrosecondType, Time64NanosecondType, TimestampMicrosecondType,
+    TimestampMillisecondType, TimestampNanosecondType, TimestampSecondType, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{Array, ArrayRef, NullArray, RecordBatch};
+use arrow_buffer::i256;
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+// 字段 metadata 中保存 DbsyncType 的键
+pub const DBSYNC_TYPE_KEY: &str = "dbsync.type";
+
+// Decimal128 能表示的最大精度, 更大的 DECIMAL 使用 Decimal256
+const DECIMAL128_MAX_PRECISION: u8 = 38;
+
+// DbsyncType 对应的 Arrow 类型.
+// 整数类型按有无符号统一使用 Int64 / UInt64, 避免按宽度缩小时溢出
+pub fn to_arrow_type(dbsync_type: &DbsyncType) -> DataType {
+    match dbsync_type {
+        DbsyncType::TinyInt
+        | DbsyncType::SmallInt
+        | DbsyncType::MediumInt
+        | DbsyncType::Int
+        | DbsyncType::BigInt
+        | DbsyncType::Year => DataType::Int64,
+        DbsyncType::UnsignedTinyInt
+        | DbsyncType::UnsignedSmallInt
+        | DbsyncType::UnsignedMediumInt
+        | DbsyncType::UnsignedInt
+        | DbsyncType::UnsignedBigInt
+        | DbsyncType::Bit(_) => DataType::UInt64,
+        DbsyncType::Float => DataType::Float32,
+        DbsyncType::Double => DataType::Float64,
+        // Arrow 的 Decimal 位数有限, 未限制精度的 NUMERIC 以十进制文本存放, 字段 metadata 记录原类型
+        t if t.is_unconstrained_numeric() => DataType::Utf8,
+        DbsyncType::Decimal(p, s) | DbsyncType::Numeric(p, s) => {
+            if *p <= DECIMAL128_MAX_PRECISION {
+                DataType::Decimal128(*p, *s as i8)
+            } else {
+                DataType::Decimal256(*p, *s as i8)
+            }
+        }
+        DbsyncType::Char(_)
+        | DbsyncType::VarChar(_)
+        | DbsyncType::TinyText
+        | DbsyncType::Text
+        | DbsyncType::MediumText
+        | DbsyncType::LongText
+        | DbsyncType::Enum(_)
+        | DbsyncType::Set(_)
+        | DbsyncType::Uuid
+        | DbsyncType::Json
+        | DbsyncType::Array(_) => DataType::Utf8,
+        // 时间统一使用微秒, 与 DbsyncValue 的精度一致
+        DbsyncType::Date => DataType::Date32,
+        // MySQL TIME 是 ±838:59:59 的有符号时长, 超出 Time64 要求的一天之内, 用 Duration 表示
+        DbsyncType::Time(_) => DataType::Duration(TimeUnit::Microsecond),
+        DbsyncType::DateTime(_) => DataType::Timestamp(TimeUnit::Microsecond, None),
+        DbsyncType::Timestamp(_) => DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+        DbsyncType::Boolean => DataType::Boolean,
+        DbsyncType::Binary(_)
+        | DbsyncType::VarBinary(_)
+        | DbsyncType::TinyBlob
+        | DbsyncType::Blob
+        | DbsyncType::MediumBlob
+        | DbsyncType::LongBlob
+        | DbsyncType::Spatial(_) => DataType::Binary,
+        DbsyncType::Null => DataType::Null,
+    }
+}
+
+// 没有 metadata 时由 Arrow 类型推断 DbsyncType
+pub fn from_arrow_type(data_type: &DataType) -> Result<DbsyncType> {
+    match data_type {
+        DataType::Int8 => Ok(DbsyncType::TinyInt),
+        DataType::Int16 => Ok(DbsyncType::SmallInt),
+        DataType::Int32 => Ok(DbsyncType::Int),
+        DataType::Int64 => Ok(DbsyncType::BigInt),
+        DataType::UInt8 => Ok(DbsyncType::UnsignedTinyInt),
+        DataType::UInt16 => Ok(DbsyncType::UnsignedSmallInt),
+        DataType::UInt32 => Ok(DbsyncType::UnsignedInt),
+        DataType::UInt64 => Ok(DbsyncType::UnsignedBigInt),
+        DataType::Float32 => Ok(DbsyncType::Float),
+        DataType::Float64 => Ok(DbsyncType::Double),
+        DataType::Decimal128(p, s) | DataType::Decimal256(p, s) if *s >= 0 => {
+            Ok(DbsyncType::Decimal(*p, *s as u8))
+        }
+        DataType::Utf8 => Ok(DbsyncType::Text),
+        DataType::LargeUtf8 => Ok(DbsyncType::LongText),
+        DataType::Date32 | DataType::Date64 => Ok(DbsyncType::Date),
+        DataType::Time32(unit) | DataType::Time64(unit) | DataType::Duration(unit) => {
+            Ok(DbsyncType::Time(fsp(unit)))
+        }
+        DataType::Timestamp(unit, None) => Ok(DbsyncType::DateTime(fsp(unit))),
+        DataType::Timestamp(unit, Some(_)) => Ok(DbsyncType::Timestamp(fsp(unit))),
+        DataType::Boolean => Ok(DbsyncType::Boolean),
+        DataType::Binary => Ok(DbsyncType::Blob),
+        DataType::LargeBinary => Ok(DbsyncType::LongBlob),
+        DataType::Null => Ok(DbsyncType::Null),
+        other => Err(Error::Type(format!("Unsupported Arrow type: {}", other))),
+    }
+}
+
+// 时间单位对应的秒小数位数, 纳秒超出 DbsyncValue 的精度, 截断到微秒
+fn fsp(unit: &TimeUnit) -> u8 {
+    match unit {
+        TimeUnit::Second => 0,
+        TimeUnit::Millisecond => 3,
+        TimeUnit::Microsecond | TimeUnit::Nanosecond => 6,
+    }
+}
+
+impl BatchSchema {
+    pub fn to_arrow_schema(&self) -> Result<Schema> {
+        self.fields
+            .iter()
+            .map(|field| {
+                let metadata = HashMap::from([(
+                    DBSYNC_TYPE_KEY.to_string(),
+                    serde_json::to_string(&field.data_type)?,
+                )]);
+                Ok(
+                    Field::new(&field.name, to_arrow_type(&field.data_type), field.nullable)
+                        .with_metadata(metadata),
+                )
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Schema::new)
+    }
+
+    pub fn from_arrow_schema(schema: &Schema) -> Result<Self> {
+        schema
+            .fields()
+            .iter()
+            .map(|field| {
+                let data_type = match field.metadata().get(DBSYNC_TYPE_KEY) {
+                    Some(data_type) => serde_json::from_str(data_type)?,
+                    None => from_arrow_type(field.data_type())?,
+                };
+                Ok(BatchField::new(field.name(), data_type).with_nullable(field.is_nullable()))
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(BatchSchema::new)
+    }
+}
+
+impl DataBatch {
+    pub fn to_record_batch(&self) -> Result<RecordBatch> {
+        let schema = Arc::new(self.schema.to_arrow_schema()?);
+        let columns = self
+            .schema
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let values = self
+                    .records
+                    .iter()
+                    .map(|record| {
+                        record.values.get(index).ok_or_else(|| {
+                            Error::Type(format!("Record has no value for column {}", field.name))
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                build_array(field, &values)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        RecordBatch::try_new(schema, columns).map_err(|e| Error::Type(e.to_string()))
+    }
+
+    pub fn from_record_batch(batch: &RecordBatch) -> Result<Self> {
+        let schema = BatchSchema::from_arrow_schema(&batch.schema())?;
+        let columns = batch
+            .columns()
+            .iter()
+            .zip(&schema.fields)
+            .map(|(array, field)| read_array(array, field))
+            .collect::<Result<Vec<_>>>()?;
+
+        let records = (0..batch.num_rows())
+            .map(|row| Record::new(columns.iter().map(|column| column[row].clone()).collect()))
+            .collect();
+        Ok(DataBatch::new(Arc::new(schema), records))
+    }
+}
+
+impl TryFrom<&DataBatch> for RecordBatch {
+    type Error = Error;
+
+    fn try_from(batch: &DataBatch) -> Result<Self> {
+        batch.to_record_batch()
+    }
+}
+
+impl TryFrom<&RecordBatch> for DataBatch {
+    type Error = Error;
+
+    fn try_from(batch: &RecordBatch) -> Result<Self> {
+        DataBatch::from_record_batch(batch)
+    }
+}
+
+fn mismatch(field: &BatchField, value: &DbsyncValue) -> Error {
+    Error::Type(format!(
+        "Value {:?} does not match type {:?} of column {}",
+        value, field.data_type, field.name
+    ))
+}
+
+// 依次追加每个值, Null 追加空值, 其余交给 convert 转换
+macro_rules! build {
+    ($builder:expr, $values:expr, $field:expr, $convert:expr) => {{
+        let mut builder = $builder;
+        for value in $values {
+            match value {
+                DbsyncValue::Null => builder.append_null(),
+                value => {
+                    builder.append_value($convert(*value).ok_or_else(|| mismatch($field, value))?)
+                }
+            }
+        }
+        Arc::new(builder.finish()) as ArrayRef
+    }};
+}
+
+fn build_array(field: &BatchField, values: &[&DbsyncValue]) -> Result<ArrayRef> {
+    let len = values.len();
+    let array = match &field.data_type {
+        DbsyncType::TinyInt
+        | DbsyncType::SmallInt
+        | DbsyncType::MediumInt
+        | DbsyncType::Int
+        | DbsyncType::BigInt
+        | DbsyncType::Year => {
+            build!(
+                Int64Builder::with_capacity(len),
+                values,
+                field,
+                |v: &DbsyncValue| match v {
+                    DbsyncValue::Integer(i) => Some(*i),
+                    DbsyncValue::UInteger(u) => i64::try_from(*u).ok(),
+                    DbsyncValue::Boolean(b) => Some(i64::from(*b)),
+                    _ => None,
+                }
+            )
+        }
+        DbsyncType::UnsignedTinyInt
+        | DbsyncType::UnsignedSmallInt
+        | DbsyncType::UnsignedMediumInt
+        | DbsyncType::UnsignedInt
+        | DbsyncType::UnsignedBigInt
+        | DbsyncType::Bit(_) => build!(
+            UInt64Builder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Integer(i) => u64::try_from(*i).ok(),
+                DbsyncValue::UInteger(u) => Some(*u),
+                _ => None,
+            }
+        ),
+        DbsyncType::Float => build!(
+            Float32Builder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| as_f64(v).map(|f| f as f32)
+        ),
+        DbsyncType::Double => build!(Float64Builder::with_capacity(len), values, field, as_f64),
+        t if t.is_unconstrained_numeric() => build!(
+            StringBuilder::with_capacity(len, 0),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Decimal(d) => Some(d.trim().to_string()),
+                DbsyncValue::Integer(i) => Some(i.to_string()),
+                DbsyncValue::UInteger(u) => Some(u.to_string()),
+                _ => None,
+            }
+        ),
+        DbsyncType::Decimal(p, s) | DbsyncType::Numeric(p, s) => {
+            if *p <= DECIMAL128_MAX_PRECISION {
+                build!(
+                    Decimal128Builder::with_capacity(len),
+                    values,
+                    field,
+                    |v: &DbsyncValue| unscaled_decimal(v, *s).and_then(|d| d.parse::<i128>().ok())
+                )
+                .as_primitive::<Decimal128Type>()
+                .clone()
+                .with_precision_and_scale(*p, *s as i8)
+                .map(|array| Arc::new(array) as ArrayRef)
+                .map_err(|e| Error::Type(e.to_string()))?
+            } else {
+                build!(
+                    Decimal256Builder::with_capacity(len),
+                    values,
+                    field,
+                    |v: &DbsyncValue| unscaled_decimal(v, *s).and_then(|d| i256::from_string(&d))
+                )
+                .as_primitive::<Decimal256Type>()
+                .clone()
+                .with_precision_and_scale(*p, *s as i8)
+                .map(|array| Arc::new(array) as ArrayRef)
+                .map_err(|e| Error::Type(e.to_string()))?
+            }
+        }
+        DbsyncType::Char(_)
+        | DbsyncType::VarChar(_)
+        | DbsyncType::TinyText
+        | DbsyncType::Text
+        | DbsyncType::MediumText
+        | DbsyncType::LongText
+        | DbsyncType::Enum(_)
+        | DbsyncType::Set(_)
+        | DbsyncType::Uuid => {
+            build!(
+                StringBuilder::with_capacity(len, 0),
+                values,
+                field,
+                |v: &DbsyncValue| match v {
+                    DbsyncValue::String(s) => Some(s.clone()),
+                    _ => None,
+                }
+            )
+        }
+        // Arrow 没有 JSON 类型, 以 JSON 文本存放, 字段 metadata 记录原类型
+        DbsyncType::Json | DbsyncType::Array(_) => build!(
+            StringBuilder::with_capacity(len, 0),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Json(json) => Some(json.to_string()),
+                DbsyncValue::String(s) => Some(s.clone()),
+                _ => None,
+            }
+        ),
+        DbsyncType::Date => build!(
+            Date32Builder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Date(days) => Some(*days),
+                _ => None,
+            }
+        ),
+        DbsyncType::Time(_) => build!(
+            DurationMicrosecondBuilder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Time(micros) => Some(*micros),
+                _ => None,
+            }
+        ),
+        DbsyncType::DateTime(_) => build!(
+            TimestampMicrosecondBuilder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::DateTime(micros) => Some(*micros),
+                _ => None,
+            }
+        ),
+        DbsyncType::Timestamp(_) => build!(
+            TimestampMicrosecondBuilder::with_capacity(len).with_timezone("UTC"),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Timestamp(micros) => Some(*micros),
+                _ => None,
+            }
+        ),
+        DbsyncType::Boolean => build!(
+            BooleanBuilder::with_capacity(len),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Boolean(b) => Some(*b),
+                DbsyncValue::Integer(i) => Some(*i != 0),
+                _ => None,
+            }
+        ),
+        DbsyncType::Binary(_)
+        | DbsyncType::VarBinary(_)
+        | DbsyncType::TinyBlob
+        | DbsyncType::Blob
+        | DbsyncType::MediumBlob
+        | DbsyncType::LongBlob
+        | DbsyncType::Spatial(_) => build!(
+            BinaryBuilder::with_capacity(len, 0),
+            values,
+            field,
+            |v: &DbsyncValue| match v {
+                DbsyncValue::Binary(b) => Some(b.clone()),
+                DbsyncValue::String(s) => Some(s.as_bytes().to_vec()),
+                _ => None,
+            }
+        ),
+        DbsyncType::Null => {
+            if let Some(value) = values.iter().find(|v| ***v != DbsyncValue::Null) {
+                return Err(mismatch(field, value));
+            }
+            Arc::new(NullArray::new(len))
+        }
+    };
+    Ok(array)
+}
+
+fn as_f64(value: &DbsyncValue) -> Option<f64> {
+    match value {
+        DbsyncValue::Float(f) => Some(*f),
+        DbsyncValue::Integer(i) => Some(*i as f64),
+        DbsyncValue::Decimal(d) => d.parse().ok(),
+        _ => None,
+    }
+}
+
+// 把十进制文本转换为去掉小数点、按 scale 补齐的整数文本, 如 scale 为 2 时 "-1.5" → "-150".
+// 超出 scale 的小数位必须为 0, 避免静默截断
+fn unscaled_decimal(value: &DbsyncValue, scale: u8) -> Option<String> {
+    let text = match value {
+        DbsyncValue::Decimal(d) | DbsyncValue::String(d) => d.trim().to_string(),
+        DbsyncValue::Integer(i) => i.to_string(),
+        DbsyncValue::UInteger(u) => u.to_string(),
+        _ => return None,
+    };
+    let (sign, digits) = match text.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", text.strip_prefix('+').unwrap_or(&text)),
+    };
+    let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+    if int_part.is_empty() && frac_part.is_empty()
+        || !int_part
+            .chars()
+            .chain(frac_part.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let scale = scale as usize;
+    let (kept, dropped) = frac_part.split_at(frac_part.len().min(scale));
+    if dropped.chars().any(|c| c != '0') {
+        return None;
+    }
+    Some(format!(
+        "{}{}{}{}",
+        sign,
+        int_part,
+        kept,
+        "0".repeat(scale - kept.len())
+    ))
+}
+
+// unscaled_decimal 的逆操作
+fn scaled_decimal(unscaled: String, scale: i8) -> String {
+    if scale <= 0 {
+        return format!("{}{}", unscaled, "0".repeat(scale.unsigned_abs() as usize));
+    }
+    let scale = scale as usize;
+    let (sign, digits) = match unscaled.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", unscaled.as_str()),
+    };
+    let digits = format!("{:0>width$}", digits, width = scale + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+    format!("{}{}.{}", sign, int_part, frac_part)
+}
+
+fn read_array(array: &ArrayRef, field: &BatchField) -> Result<Vec<DbsyncValue>> {
+    let value = |row: usize| -> Result<DbsyncValue> {
+        let value = match array.data_type() {
+            DataType::Null => DbsyncValue::Null,
+            DataType::Int8 => {
+                DbsyncValue::Integer(array.as_primitive::<Int8Type>().value(row).into())
+            }
+            DataType::Int16 => {
+                DbsyncValue::Integer(array.as_primitive::<Int16Type>().value(row).into())
+            }
+            DataType::Int32 => {
+                DbsyncValue::Integer(array.as_primitive::<Int32Type>().value(row).into())
+            }
+            DataType::Int64 => DbsyncValue::Integer(array.as_primitive::<Int64Type>().value(row)),
+            DataType::UInt8 => {
+                DbsyncValue::Integer(array.as_primitive::<UInt8Type>().value(row).into())
+            }
+            DataType::UInt16 => {
+                DbsyncValue::Integer(array.as_primitive::<UInt16Type>().value(row).into())
+            }
+            DataType::UInt32 => {
+                DbsyncValue::Integer(array.as_primitive::<UInt32Type>().value(row).into())
+            }
+            DataType::UInt64 => {
+                DbsyncValue::UInteger(array.as_primitive::<UInt64Type>().value(row))
+            }
+            DataType::Float32 => {
+                DbsyncValue::Float(array.as_primitive::<Float32Type>().value(row).into())
+            }
+            DataType::Float64 => DbsyncValue::Float(array.as_primitive::<Float64Type>().value(row)),
+            DataType::Decimal128(_, scale) => DbsyncValue::Decimal(scaled_decimal(
+                array
+                    .as_primitive::<Decimal128Type>()
+                    .value(row)
+                    .to_string(),
+                *scale,
+            )),
+            DataType::Decimal256(_, scale) => DbsyncValue::Decimal(scaled_decimal(
+                array
+                    .as_primitive::<Decimal256Type>()
+                    .value(row)
+                    .to_string(),
+                *scale,
+            )),
+            DataType::Utf8 | DataType::LargeUtf8 => {
+                let text = if *array.data_type() == DataType::Utf8 {
+                    array.as_string::<i32>().value(row)
+                } else {
+                    array.as_string::<i64>().value(row)
+                };
+                if field.data_type.is_unconstrained_numeric() {
+                    DbsyncValue::Decimal(text.to_string())
+                } else if matches!(field.data_type, DbsyncType::Json | DbsyncType::Array(_)) {
+                    DbsyncValue::Json(serde_json::from_str(text).map_err(|e| {
+                        Error::Type(format!("Invalid JSON in column {}: {}", field.name, e))
+                    })?)
+                } else {
+                    DbsyncValue::String(text.to_string())
+                }
+            }
+            DataType::Date32 => DbsyncValue::Date(array.as_primitive::<Date32Type>().value(row)),
+            DataType::Date64 => {
+                let millis = array.as_primitive::<Date64Type>().value(row);
+                let days = (millis * 1_000).div_euclid(MICROS_PER_DAY);
+                DbsyncValue::Date(i32::try_from(days).map_err(|_| {
+                    Error::Type(format!("Date out of range in column {}", field.name))
+                })?)
+            }
+            DataType::Time32(TimeUnit::Second) => DbsyncValue::Time(
+                i64::from(array.as_primitive::<Time32SecondType>().value(row)) * MICROS_PER_SECOND,
+            ),
+            DataType::Time32(TimeUnit::Millisecond) => DbsyncValue::Time(
+                i64::from(array.as_primitive::<Time32MillisecondType>().value(row)) * 1_000,
+            ),
+            DataType::Time64(TimeUnit::Microsecond) => {
+                DbsyncValue::Time(array.as_primitive::<Time64MicrosecondType>().value(row))
+            }
+            DataType::Time64(TimeUnit::Nanosecond) => DbsyncValue::Time(
+                array
+                    .as_primitive::<Time64NanosecondType>()
+                    .value(row)
+                    .div_euclid(1_000),
+            ),
+            DataType::Duration(unit) => {
+                let micros = match unit {
+                    TimeUnit::Second => array
+                        .as_primitive::<DurationSecondType>()
+                        .value(row)
+                        .checked_mul(MICROS_PER_SECOND),
+                    TimeUnit::Millisecond => array
+                        .as_primitive::<DurationMillisecondType>()
+                        .value(row)
+                        .checked_mul(1_000),
+                    TimeUnit::Microsecond => {
+                        Some(array.as_primitive::<DurationMicrosecondType>().value(row))
+                    }
+                    TimeUnit::Nanosecond => Some(
+                        array
+                            .as_primitive::<DurationNanosecondType>()
+                            .value(row)
+                            .div_euclid(1_000),
+                    ),
+                }
+                .ok_or_else(|| {
+                    Error::Type(format!("Duration out of range in column {}", field.name))
+                })?;
+                DbsyncValue::Time(micros)
+            }
+            DataType::Timestamp(unit, tz) => {
+                let micros = match unit {
+                    TimeUnit::Second => array
+                        .as_primitive::<TimestampSecondType>()
+                        .value(row)
+                        .checked_mul(MICROS_PER_SECOND),
+                    TimeUnit::Millisecond => array
+                        .as_primitive::<TimestampMillisecondType>()
+                        .value(row)
+                        .checked_mul(1_000),
+                    TimeUnit::Microsecond => {
+                        Some(array.as_primitive::<TimestampMicrosecondType>().value(row))
+                    }
+                    TimeUnit::Nanosecond => Some(
+                        array
+                            .as_primitive::<TimestampNanosecondType>()
+                            .value(row)
+                            .div_euclid(1_000),
+                    ),
+                }
+                .ok_or_else(|| {
+                    Error::Type(format!("Timestamp out of range in column {}", field.name))
+                })?;
+                // 带时区的 Arrow 时间戳存的是 UTC 时刻, 不带时区的是墙上时间
+                if tz.is_some() {
+                    DbsyncValue::Timestamp(micros)
+                } else {
+                    DbsyncValue::DateTime(micros)
+                }
+            }
+            DataType::Boolean => DbsyncValue::Boolean(array.as_boolean().value(row)),
+            DataType::Binary => DbsyncValue::Binary(array.as_binary::<i32>().value(row).to_vec()),
+            DataType::LargeBinary => {
+                DbsyncValue::Binary(array.as_binary::<i64>().value(row).to_vec())
+            }
+            other => {
+                return Err(Error::Type(format!(
+                    "Unsupported Arrow type {} in column {}",
+                    other, field.name
+                )))
+            }
+        };
+        Ok(value)
+    };
+
+    (0..array.len())
+        .map(|row| {
+            if array.is_null(row) {
+                Ok(DbsyncValue::Null)
+            } else {
+                value(row)
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SpatialType;
+
+    // 每个 DbsyncType 一列, 第二行全部为 NULL
+    fn all_types() -> Vec<(DbsyncType, DbsyncValue)> {
+        vec![
+            (DbsyncType::TinyInt, DbsyncValue::Integer(-8)),
+            (DbsyncType::SmallInt, DbsyncValue::Integer(300)),
+            (DbsyncType::Int, DbsyncValue::Integer(4_000_000_000)),
+            (DbsyncType::BigInt, DbsyncValue::Integer(i64::MIN)),
+            (DbsyncType::Float, DbsyncValue::Float(1.5)),
+            (DbsyncType::Double, DbsyncValue::Float(-2.25)),
+            (
+                DbsyncType::Decimal(10, 2),
+                DbsyncValue::Decimal("-12345678.90".to_string()),
+            ),
+            (
+                DbsyncType::Numeric(40, 4),
+                DbsyncValue::Decimal("123456789012345678901234567890123456.0001".to_string()),
+            ),
+            (DbsyncType::Char(4), DbsyncValue::String("abcd".to_string())),
+            (
+                DbsyncType::VarChar(255),
+                DbsyncValue::String("多字节".to_string()),
+            ),
+            (DbsyncType::Text, DbsyncValue::String("text".to_string())),
+            (DbsyncType::Date, DbsyncValue::Date(19_723)),
+            (
+                DbsyncType::Time(6),
+                DbsyncValue::Time(-(838 * 3600 + 59 * 60 + 59) * MICROS_PER_SECOND - 999_999),
+            ),
+            (
+                DbsyncType::DateTime(6),
+                DbsyncValue::DateTime(1_704_067_200_123_456),
+            ),
+            (DbsyncType::Timestamp(0), DbsyncValue::Timestamp(-1)),
+            (DbsyncType::Boolean, DbsyncValue::Boolean(true)),
+            (DbsyncType::Binary(16), DbsyncValue::Binary(vec![0, 1, 255])),
+            (DbsyncType::Blob, DbsyncValue::Binary(Vec::new())),
+            (
+                DbsyncType::Json,
+                DbsyncValue::Json(serde_json::json!({"a": [1, {"b": null}]})),
+            ),
+            (DbsyncType::Null, DbsyncValue::Null),
+            (DbsyncType::MediumInt, DbsyncValue::Integer(-8_388_608)),
+            (DbsyncType::UnsignedBigInt, DbsyncValue::UInteger(u64::MAX)),
+            (DbsyncType::UnsignedTinyInt, DbsyncValue::UInteger(255)),
+            (DbsyncType::Bit(64), DbsyncValue::UInteger(1 << 63)),
+            (DbsyncType::Year, DbsyncValue::Integer(2155)),
+            (
+                DbsyncType::LongText,
+                DbsyncValue::String("long".to_string()),
+            ),
+            (
+                DbsyncType::Enum(vec!["a".to_string(), "b".to_string()]),
+                DbsyncValue::String("b".to_string()),
+            ),
+            (
+                DbsyncType::Set(vec!["x".to_string(), "y".to_string()]),
+                DbsyncValue::String("x,y".to_string()),
+            ),
+            (DbsyncType::VarBinary(8), DbsyncValue::Binary(vec![7])),
+            (DbsyncType::MediumBlob, DbsyncValue::Binary(vec![1, 2])),
+            (
+                DbsyncType::Spatial(SpatialType::Point),
+                DbsyncValue::Binary(vec![0; 25]),
+            ),
+            (
+                DbsyncType::Uuid,
+                DbsyncValue::String("67e55044-10b1-426f-9247-bb680e5fe0c8".to_string()),
+            ),
+            (
+                DbsyncType::Array(Box::new(DbsyncType::Int)),
+                DbsyncValue::Json(serde_json::json!([1, null, 3])),
+            ),
+            (
+                DbsyncType::Numeric(0, 0),
+                DbsyncValue::Decimal(format!("{}.{}", "9".repeat(100), "1".repeat(50))),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_round_trip_every_type() {
+        let columns = all_types();
+        let schema = BatchSchema::new(
+            columns
+                .iter()
+                .enumerate()
+                .map(|(i, (data_type, _))| BatchField::new(format!("c{}", i), data_type.clone()))
+                .collect(),
+        );
+        let batch = DataBatch::new(
+            Arc::new(schema),
+            vec![
+                Record::new(columns.iter().map(|(_, value)| value.clone()).collect()),
+                Record::new(vec![DbsyncValue::Null; columns.len()]),
+            ],
+        );
+
+        let arrow = batch.to_record_batch().unwrap();
+        assert_eq!(arrow.num_rows(), 2);
+        assert_eq!(
+            arrow.schema().field(7).data_type(),
+            &DataType::Decimal256(40, 4)
+        );
+        assert_eq!(
+            arrow.schema().field(12).data_type(),
+            &DataType::Duration(TimeUnit::Microsecond)
+        );
+        assert_eq!(
+            arrow.schema().field(14).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(
+            arrow.schema().field(columns.len() - 1).data_type(),
+            &DataType::Utf8
+        );
+
+        let back = DataBatch::from_record_batch(&arrow).unwrap();
+        assert_eq!(back.schema, batch.schema);
+        for (back, original) in back.records.iter().zip(&batch.records) {
+            assert_eq!(back.values, original.values);
+        }
+    }
+
+    #[test]
+    fn test_infer_types_without_metadata() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::UInt64, false),
+            Field::new("price", DataType::Decimal128(5, 2), true),
+            Field::new("at", DataType::Timestamp(TimeUnit::Millisecond, None), true),
+        ]);
+        let arrow = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(arrow_array::UInt64Array::from(vec![u64::MAX])),
+                Arc::new(
+                    arrow_array::Decimal128Array::from(vec![Some(-5)])
+                        .with_precision_and_scale(5, 2)
+                        .unwrap(),
+                ),
+                Arc::new(arrow_array::TimestampMillisecondArray::from(vec![1_500])),
+            ],
+        )
+        .unwrap();
+
+        let batch = DataBatch::try_from(&arrow).unwrap();
+        assert_eq!(
+            batch.schema.fields,
+            vec![
+                BatchField::new("id", DbsyncType::UnsignedBigInt).with_nullable(false),
+                BatchField::new("price", DbsyncType::Decimal(5, 2)),
+                BatchField::new("at", DbsyncType::DateTime(3)),
+            ]
+        );
+        assert_eq!(
+            batch.records[0].values,
+            vec![
+                DbsyncValue::UInteger(u64::MAX),
+                DbsyncValue::Decimal("-0.05".to_string()),
+                DbsyncValue::DateTime(1_500_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_mismatched_values() {
+        let schema = BatchSchema::new(vec![BatchField::new("price", DbsyncType::Decimal(5, 2))]);
+        let batch = |value: &str| {
+            DataBatch::new(
+                Arc::new(schema.clone()),
+                vec![Record::new(vec![DbsyncValue::Decimal(value.to_string())])],
+            )
+        };
+        assert!(batch("1.50").to_record_batch().is_ok());
+        // 超出 scale 的非零小数位会丢失精度
+        assert!(batch("1.505").to_record_batch().is_err());
+        assert!(batch("abc").to_record_batch().is_err());
+
+        let schema = BatchSchema::new(vec![BatchField::new("id", DbsyncType::BigInt)]);
+        let batch = DataBatch::new(
+            Arc::new(schema),
+            vec![Record::new(vec![DbsyncValue::UInteger(u64::MAX)])],
+        );
+        assert!(RecordBatch::try_from(&batch).is_err());
+    }
+
+    #[test]
+    fn test_nullability() {
+        let schema = Arc::new(BatchSchema::new(vec![
+            BatchField::new("id", DbsyncType::BigInt).with_nullable(false),
+            BatchField::new("name", DbsyncType::Text),
+        ]));
+        let batch = DataBatch::new(
+            schema.clone(),
+            vec![Record::new(vec![
+                DbsyncValue::Integer(1),
+                DbsyncValue::Null,
+            ])],
+        );
+        let arrow = batch.to_record_batch().unwrap();
+        assert!(!arrow.schema().field(0).is_nullable());
+        assert!(arrow.schema().field(1).is_nullable());
+        let back = DataBatch::try_from(&arrow).unwrap();
+        assert_eq!(back.schema, batch.schema);
+        assert_eq!(back.records[0].values, batch.records[0].values);
+
+        // 不允许 NULL 的列出现 NULL
+        let batch = DataBatch::new(
+            schema,
+            vec![Record::new(vec![DbsyncValue::Null, DbsyncValue::Null])],
+        );
+        assert!(batch.to_record_batch().is_err());
+    }
+}
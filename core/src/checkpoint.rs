@@ -0,0 +1,233 @@
+use crate::connector::KeyRange;
+use crate::error::{Error, Result};
+use crate::DbsyncValue;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::info;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct JobCheckpoint {
+    // 首次运行时的区间划分, 恢复时沿用, 保证区间编号不变
+    pub ranges: Vec<KeyRange>,
+    // 已全部写入 sink 的区间编号
+    pub completed: BTreeSet<usize>,
+    // 上次成功完成的增量同步读到的水位
+    #[serde(default)]
+    pub watermark: Option<DbsyncValue>,
+    // 本次增量同步的水位上界, 恢复时沿用, 成功完成后成为 watermark
+    #[serde(default)]
+    pub target_watermark: Option<DbsyncValue>,
+    // 流式读取时已写入 sink 的最后位置
+    #[serde(default)]
+    pub position: Option<serde_json::Value>,
+}
+
+impl JobCheckpoint {
+    pub fn new(ranges: Vec<KeyRange>) -> Self {
+        Self {
+            ranges,
+            ..Default::default()
+        }
+    }
+
+    // 尚未完成的区间及其编号
+    pub fn pending_ranges(&self) -> Vec<(usize, KeyRange)> {
+        self.ranges
+            .iter()
+            .enumerate()
+            .filter(|(id, _)| !self.completed.contains(id))
+            .map(|(id, range)| (id, range.clone()))
+            .collect()
+    }
+}
+
+// 按 job_name 保存任务进度
+#[async_trait]
+pub trait CheckpointStore: Send + Sync {
+    async fn load(&self, job_name: &str) -> Result<Option<JobCheckpoint>>;
+    async fn save(&self, job_name: &str, checkpoint: &JobCheckpoint) -> Result<()>;
+}
+
+// 每个任务一个 JSON 文件, 先写临时文件再重命名, 避免进程中断时留下半个文件
+pub struct FileCheckpointStore {
+    dir: PathBuf,
+}
+
+impl FileCheckpointStore {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn path(&self, job_name: &str) -> PathBuf {
+        let file_name = job_name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        self.dir.join(format!("{}.json", file_name))
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, job_name: &str) -> Result<Option<JobCheckpoint>> {
+        match tokio::fs::read(self.path(job_name)).await {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    async fn save(&self, job_name: &str, checkpoint: &JobCheckpoint) -> Result<()> {
+        tokio::fs::create_dir_all(&self.dir).await?;
+
+        let path = self.path(job_name);
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(checkpoint)?).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct RangeProgress {
+    // reader 读完该区间后才知道总批次数
+    batches_read: Option<usize>,
+    batches_written: usize,
+}
+
+// 跟踪每个区间的写入进度, 区间的所有批次写入后记录到 checkpoint
+pub(crate) struct RangeTracker {
+    job_name: String,
+    store: Option<Arc<dyn CheckpointStore>>,
+    state: Mutex<(JobCheckpoint, HashMap<usize, RangeProgress>)>,
+}
+
+impl RangeTracker {
+    pub(crate) fn new(
+        job_name: String,
+        store: Option<Arc<dyn CheckpointStore>>,
+        checkpoint: JobCheckpoint,
+    ) -> Self {
+        Self {
+            job_name,
+            store,
+            state: Mutex::new((checkpoint, HashMap::new())),
+        }
+    }
+
+    pub(crate) async fn range_read(&self, range_id: usize, batches: usize) -> Result<()> {
+        let mut state = self.state.lock().await;
+        state.1.entry(range_id).or_default().batches_read = Some(batches);
+        self.try_complete(&mut state, range_id).await
+    }
+
+    pub(crate) async fn batch_written(&self, range_id: usize) -> Result<()> {
+        let mut state = self.state.lock().await;
+        state.1.entry(range_id).or_default().batches_written += 1;
+        self.try_complete(&mut state, range_id).await
+    }
+
+    async fn try_complete(
+        &self,
+        state: &mut (JobCheckpoint, HashMap<usize, RangeProgress>),
+        range_id: usize,
+    ) -> Result<()> {
+        let (checkpoint, progress) = state;
+        let done = progress
+            .get(&range_id)
+            .is_some_and(|p| p.batches_read == Some(p.batches_written));
+        if !done {
+            return Ok(());
+        }
+
+        progress.remove(&range_id);
+        checkpoint.completed.insert(range_id);
+        info!(
+            "Range {} completed ({}/{})",
+            range_id,
+            checkpoint.completed.len(),
+            checkpoint.ranges.len()
+        );
+
+        if let Some(store) = &self.store {
+            store.save(&self.job_name, checkpoint).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connector::KeyValue;
+    use crate::DbsyncValue;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("dbsync-checkpoint-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn ranges() -> Vec<KeyRange> {
+        KeyRange::from_boundaries(
+            [10, 20, 30]
+                .into_iter()
+                .map(|i| KeyValue(vec![DbsyncValue::Integer(i)]))
+                .collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_file_store_round_trip() {
+        let dir = temp_dir("round-trip");
+        let store = FileCheckpointStore::new(&dir);
+        assert_eq!(store.load("orders/daily").await.unwrap(), None);
+
+        let mut checkpoint = JobCheckpoint::new(ranges());
+        checkpoint.completed.insert(1);
+        store.save("orders/daily", &checkpoint).await.unwrap();
+
+        assert_eq!(store.load("orders/daily").await.unwrap(), Some(checkpoint));
+        assert!(dir.join("orders_daily.json").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tracker_completes_range_after_all_batches_written() {
+        let dir = temp_dir("tracker");
+        let store: Arc<dyn CheckpointStore> = Arc::new(FileCheckpointStore::new(&dir));
+        let tracker = RangeTracker::new(
+            "job".to_string(),
+            Some(store.clone()),
+            JobCheckpoint::new(ranges()),
+        );
+
+        // 写入先于读取完成通知到达
+        tracker.batch_written(0).await.unwrap();
+        tracker.batch_written(0).await.unwrap();
+        assert_eq!(store.load("job").await.unwrap(), None);
+        tracker.range_read(0, 2).await.unwrap();
+
+        // 空区间在读取完成时即视为完成
+        tracker.range_read(2, 0).await.unwrap();
+
+        tracker.range_read(1, 2).await.unwrap();
+        tracker.batch_written(1).await.unwrap();
+
+        let checkpoint = store.load("job").await.unwrap().unwrap();
+        assert_eq!(checkpoint.completed, BTreeSet::from([0, 2]));
+        assert_eq!(checkpoint.pending_ranges(), vec![(1, ranges()[1].clone())]);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
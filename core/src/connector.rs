@@ -1,10 +1,12 @@
 use crate::error::Result;
+use crate::schema::TableSchema;
 use crate::{DbsyncType, DbsyncValue};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::any::Any;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectorConfig {
@@ -13,14 +15,126 @@ pub struct ConnectorConfig {
     pub properties: HashMap<String, Value>,
 }
 
-#[derive(Clone, Debug)]
+// 批次中的一列
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatchField {
+    pub name: String,
+    pub data_type: DbsyncType,
+    // 该列是否允许 NULL, 不确定时按允许处理
+    pub nullable: bool,
+}
+
+impl BatchField {
+    pub fn new(name: impl Into<String>, data_type: DbsyncType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            nullable: true,
+        }
+    }
+
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+}
+
+// 批次中所有记录共享的列定义, 记录的值按这里的列顺序存放
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct BatchSchema {
+    pub fields: Vec<BatchField>,
+}
+
+impl BatchSchema {
+    pub fn new(fields: Vec<BatchField>) -> Self {
+        Self { fields }
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    // 列数通常很少, 线性查找比维护哈希表更快
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.fields.iter().position(|field| field.name == name)
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.fields.iter().map(|field| field.name.as_str())
+    }
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct DataBatch {
+    pub schema: Arc<BatchSchema>,
     pub records: Vec<Record>,
 }
 
-#[derive(Clone, Debug)]
+impl DataBatch {
+    pub fn new(schema: Arc<BatchSchema>, records: Vec<Record>) -> Self {
+        Self { schema, records }
+    }
+
+    // 第 row 条记录中 column 列的值
+    pub fn value(&self, row: usize, column: &str) -> Option<&DbsyncValue> {
+        let index = self.schema.index_of(column)?;
+        self.records.get(row)?.values.get(index)
+    }
+}
+
+// 记录代表的变更类型, 全量和增量读取的记录均为 Insert
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operation {
+    #[default]
+    Insert,
+    Update,
+    Delete,
+    Upsert, // 存在则更新, 不存在则插入
+}
+
+#[derive(Clone, Debug, Default)]
 pub struct Record {
-    pub fields: HashMap<String, (DbsyncValue, DbsyncType)>,
+    // 变更后的行, 按批次 schema 的列顺序排列; Delete 时为被删除的行
+    pub values: Vec<DbsyncValue>,
+    pub op: Operation,
+    // Update 前的行, 主键被修改时 sink 按它定位目标行
+    pub before: Option<Vec<DbsyncValue>>,
+    // 该变更在源端的位置, 如 binlog 文件和偏移
+    pub position: Option<Value>,
+}
+
+impl Record {
+    pub fn new(values: Vec<DbsyncValue>) -> Self {
+        Self {
+            values,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_op(mut self, op: Operation) -> Self {
+        self.op = op;
+        self
+    }
+
+    pub fn with_before(mut self, before: Vec<DbsyncValue>) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    pub fn with_position(mut self, position: Value) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    // 定位目标行所用的值: Update 取变更前的行, 其余取 values
+    pub fn key_values(&self) -> &[DbsyncValue] {
+        self.before.as_deref().unwrap_or(&self.values)
+    }
 }
 
 // 基础 Source trait
@@ -36,15 +150,157 @@ pub trait Source: Send + Sync + Any {
     fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
         None
     }
+
+    // 默认返回 None 表示每次全量读取
+    fn as_incremental(&mut self) -> Option<&mut dyn IncrementalSource> {
+        None
+    }
+
+    // 默认返回 None 表示不支持流式读取
+    fn as_streaming(&mut self) -> Option<&mut dyn StreamingSource> {
+        None
+    }
+}
+
+// 主键值, 复合主键按主键列顺序排列
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyValue(pub Vec<DbsyncValue>);
+
+impl KeyValue {
+    // 单列整数主键的数值, 其他主键返回 None
+    fn as_integer(&self) -> Option<i128> {
+        match self.0.as_slice() {
+            [DbsyncValue::Integer(i)] => Some(*i as i128),
+            [DbsyncValue::UInteger(u)] => Some(*u as i128),
+            _ => None,
+        }
+    }
+}
+
+// 主键区间 (start, end], start 为 None 时从最小主键开始
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct KeyRange {
+    pub start: Option<KeyValue>,
+    pub end: KeyValue,
+}
+
+impl KeyRange {
+    // 由升序排列的区间终点构造首尾相接的区间
+    pub fn from_boundaries(ends: Vec<KeyValue>) -> Vec<KeyRange> {
+        let mut start = None;
+        ends.into_iter()
+            .map(|end| KeyRange {
+                start: start.replace(end.clone()),
+                end,
+            })
+            .collect()
+    }
+
+    // 单列整数主键按数值均分为至多 chunks 个区间, 其他主键返回 None
+    pub fn split_integer(min: &KeyValue, max: &KeyValue, chunks: usize) -> Option<Vec<KeyRange>> {
+        let (lo, hi) = (min.as_integer()?, max.as_integer()?);
+        let unsigned = matches!(max.0.as_slice(), [DbsyncValue::UInteger(_)]);
+        let chunks = chunks.max(1) as i128;
+
+        let mut ends = (1..chunks)
+            .map(|i| lo + (hi - lo) * i / chunks)
+            .filter(|end| *end < hi)
+            .collect::<Vec<_>>();
+        ends.dedup();
+        ends.push(hi);
+
+        let ends = ends
+            .into_iter()
+            .map(|end| {
+                KeyValue(vec![if unsigned {
+                    DbsyncValue::UInteger(end as u64)
+                } else {
+                    DbsyncValue::Integer(end as i64)
+                }])
+            })
+            .collect();
+        Some(Self::from_boundaries(ends))
+    }
+}
+
+// 区间读取的结果, last_key 为本批次最后一行的主键, 作为下一次读取的游标
+#[derive(Clone, Debug)]
+pub struct RangeBatch {
+    pub batch: DataBatch,
+    pub last_key: KeyValue,
 }
 
 // 分片功能 trait
 #[async_trait]
 pub trait ShardedSource: Send + Sync {
     async fn get_total_records(&self) -> Result<i64>;
-    async fn get_id_range(&self) -> Result<(i64, i64)>;
-    async fn read_batch_range(&mut self, start_id: i64, end_id: i64) -> Result<Option<DataBatch>>;
-    async fn get_schema(&self) -> Result<String>;
+
+    // 主键列, 按主键顺序排列; 没有主键的表返回错误
+    async fn get_primary_key(&self) -> Result<Vec<String>>;
+
+    // 最小和最大主键, 表为空时返回 None
+    async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>>;
+
+    // 将主键范围切分为至多 chunks 个区间, 默认只能按数值切分单列整数主键
+    async fn split_id_range(&self, chunks: usize) -> Result<Vec<KeyRange>> {
+        let Some((min, max)) = self.get_id_range().await? else {
+            return Ok(Vec::new());
+        };
+        Ok(
+            KeyRange::split_integer(&min, &max, chunks).unwrap_or_else(|| {
+                vec![KeyRange {
+                    start: None,
+                    end: max,
+                }]
+            }),
+        )
+    }
+
+    // 按主键顺序读取 (after, end] 区间内的下一批数据 (keyset 分页),
+    // after 为 None 时从最小主键开始
+    async fn read_batch_range(
+        &mut self,
+        after: Option<&KeyValue>,
+        end: &KeyValue,
+    ) -> Result<Option<RangeBatch>>;
+
+    async fn get_schema(&self) -> Result<TableSchema>;
+}
+
+// 增量读取 trait, 按随数据写入单调递增的水位列 (如 updated_at 或自增 id) 过滤
+#[async_trait]
+pub trait IncrementalSource: Send + Sync {
+    fn watermark_column(&self) -> &str;
+
+    // 当前的最大水位, 表为空时返回 None
+    async fn get_max_watermark(&self) -> Result<Option<DbsyncValue>>;
+
+    // 之后的所有读取 (包括区间切分) 只包含水位在 (low, high] 内的行,
+    // low 为 None 时不设下界
+    fn set_watermark_range(&mut self, low: Option<DbsyncValue>, high: DbsyncValue);
+}
+
+// 流式读取的结果, position 为读完本批次后的源端位置, 从该位置恢复不会重复或遗漏数据
+#[derive(Clone, Debug)]
+pub struct StreamBatch {
+    pub batch: DataBatch,
+    pub position: Value,
+}
+
+// 流式读取 trait, 如 binlog CDC: 没有主键区间, 按源端位置顺序读取并断点续传
+#[async_trait]
+pub trait StreamingSource: Send + Sync {
+    // 从 position 之后开始读取, position 为 None 时从 source 配置的起点开始
+    async fn start(&mut self, position: Option<Value>) -> Result<()>;
+
+    // 读取下一批数据, 流结束时返回 None
+    async fn read_stream_batch(&mut self, batch_size: usize) -> Result<Option<StreamBatch>>;
+
+    // position 及之前的变更都已写入 sink, source 可以释放源端为其保留的数据,
+    // 如复制槽保留的 WAL. 只会以递增的位置调用
+    async fn acknowledge(&mut self, _position: Value) -> Result<()> {
+        Ok(())
+    }
 }
 
 // 基础 Sink trait
@@ -74,7 +330,7 @@ pub trait ShardedSink: Sink {
     ) -> Result<()>;
 
     // 获取目标表结构
-    async fn get_target_schema(&self) -> Result<String>;
+    async fn get_target_schema(&self) -> Result<TableSchema>;
 }
 
 #[async_trait]
@@ -99,8 +355,8 @@ pub trait PooledSink: Sink {
     // 获取可用连接数
     async fn get_available_connections(&self) -> Result<usize>;
 
-    // 创建目标表
-    async fn create_table(&mut self, schema: &str, mode: SaveMode) -> Result<()>;
+    // 按源表结构创建目标表
+    async fn create_table(&mut self, schema: &TableSchema, mode: SaveMode) -> Result<()>;
 }
 
 // 连接池统计信息
@@ -111,13 +367,15 @@ pub struct PoolStats {
     pub in_use_connections: u32,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SaveMode {
+    #[default]
     Overwrite, // 删除重建
-               // 后续以添加其他模式
-               // Append,    // 追
-               // ErrorIfExists, // 已存在则报错
-               // Ignore,    // 已存在则跳过
+    Append,        // 保留已有表和数据, 不存在时创建
+    Truncate,      // 保留表结构和索引, 清空数据
+    ErrorIfExists, // 已存在则报错
+    Ignore,        // 已存在则跳过, 不写入任何数据
 }
 
 #[async_trait]
@@ -127,19 +385,94 @@ pub trait ShardedSourceExt: Source + ShardedSource {}
 impl<T: Source + ShardedSource> ShardedSourceExt for T {}
 
 pub struct Context {
-    pub schema: Option<String>,
+    pub schema: Option<TableSchema>,
+    // 任务从 checkpoint 恢复或在上次增量同步的基础上继续, sink 应保留已有的目标表和数据
+    pub resume: bool,
+    // 增量同步, 已写入的行会再次出现, sink 应覆盖旧版本而不是报重复键错误
+    pub incremental: bool,
+    // 从 checkpoint 恢复按区间读取的任务, 未完成的区间从头重新写入,
+    // 中断前已写入的部分会再次出现, 无法覆盖旧行的 sink 应拒绝
+    pub replay: bool,
     pub properties: HashMap<String, Value>,
 }
 
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Context {
     pub fn new() -> Self {
         Self {
             schema: None,
+            resume: false,
+            incremental: false,
+            replay: false,
             properties: HashMap::new(),
         }
     }
 
-    pub fn set_schema(&mut self, schema: String) {
+    pub fn set_schema(&mut self, schema: TableSchema) {
         self.schema = Some(schema);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn int(i: i64) -> KeyValue {
+        KeyValue(vec![DbsyncValue::Integer(i)])
+    }
+
+    fn ends(ranges: &[KeyRange]) -> Vec<KeyValue> {
+        ranges.iter().map(|r| r.end.clone()).collect()
+    }
+
+    #[test]
+    fn test_from_boundaries_chains_ranges() {
+        let ranges = KeyRange::from_boundaries(vec![int(10), int(20), int(30)]);
+        assert_eq!(ranges.len(), 3);
+        assert_eq!(ranges[0].start, None);
+        assert_eq!(ranges[1].start, Some(int(10)));
+        assert_eq!(ranges[2].start, Some(int(20)));
+        assert_eq!(ends(&ranges), vec![int(10), int(20), int(30)]);
+    }
+
+    #[test]
+    fn test_split_integer_covers_range() {
+        let ranges = KeyRange::split_integer(&int(1), &int(100), 4).unwrap();
+        assert_eq!(ends(&ranges), vec![int(25), int(50), int(75), int(100)]);
+
+        // 区间数多于主键个数时不产生空区间
+        let ranges = KeyRange::split_integer(&int(5), &int(7), 10).unwrap();
+        assert_eq!(ends(&ranges), vec![int(5), int(6), int(7)]);
+
+        let ranges = KeyRange::split_integer(&int(42), &int(42), 4).unwrap();
+        assert_eq!(ends(&ranges), vec![int(42)]);
+    }
+
+    #[test]
+    fn test_split_integer_unsigned_beyond_i64() {
+        let min = KeyValue(vec![DbsyncValue::UInteger(0)]);
+        let max = KeyValue(vec![DbsyncValue::UInteger(u64::MAX)]);
+        let ranges = KeyRange::split_integer(&min, &max, 2).unwrap();
+        assert_eq!(
+            ends(&ranges),
+            vec![
+                KeyValue(vec![DbsyncValue::UInteger(u64::MAX / 2)]),
+                KeyValue(vec![DbsyncValue::UInteger(u64::MAX)]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_integer_rejects_other_keys() {
+        let uuid = KeyValue(vec![DbsyncValue::String("a".into())]);
+        assert!(KeyRange::split_integer(&uuid, &uuid, 4).is_none());
+
+        let composite = KeyValue(vec![DbsyncValue::Integer(1), DbsyncValue::Integer(2)]);
+        assert!(KeyRange::split_integer(&composite, &composite, 4).is_none());
+    }
+}
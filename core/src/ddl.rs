@@ -0,0 +1,437 @@
+use crate::error::{Error, Result};
+use crate::schema::{ColumnDefault, ColumnSchema, IndexColumn, TableSchema};
+use crate::types::TypeMapper;
+use crate::DbsyncType;
+use tracing::warn;
+
+// 注释的写法
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentStyle {
+    // 写在列定义和表选项里, 如 MySQL 的 COMMENT '...'
+    Inline,
+    // 单独的 COMMENT ON 语句, 如 PostgreSQL
+    Statement,
+    // 不支持注释, 直接丢弃
+    None,
+}
+
+// 目标数据库的 SQL 方言, 决定类型名, 标识符引用和建表语句的细节
+pub trait Dialect: Send + Sync {
+    // 方言名, 记录在 TableSchema::dialect 中
+    fn name(&self) -> &str;
+
+    fn type_mapper(&self) -> &dyn TypeMapper;
+
+    fn quote_identifier(&self, name: &str) -> String {
+        format!("\"{}\"", name.replace('"', "\"\""))
+    }
+
+    fn quote_literal(&self, value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    // 自增列的列约束, None 表示不支持, 只保留列本身
+    fn auto_increment(&self) -> Option<&str> {
+        None
+    }
+
+    // 二级索引是否写在 CREATE TABLE 内部
+    fn inline_indexes(&self) -> bool {
+        false
+    }
+
+    // 是否支持只索引列的前缀, 不支持时索引整列
+    fn prefix_indexes(&self) -> bool {
+        false
+    }
+
+    fn comment_style(&self) -> CommentStyle {
+        CommentStyle::None
+    }
+
+    // 当前时间的表达式, precision 为秒的小数位数
+    fn current_timestamp(&self, precision: u8) -> String {
+        match precision {
+            0 => "CURRENT_TIMESTAMP".to_string(),
+            n => format!("CURRENT_TIMESTAMP({})", n),
+        }
+    }
+
+    // 是否支持列定义中的 ON UPDATE 子句
+    fn on_update(&self) -> bool {
+        false
+    }
+}
+
+// 按方言生成建表所需的全部语句, 第一条是 CREATE TABLE, 之后是索引和注释
+pub fn create_table_statements(
+    dialect: &dyn Dialect,
+    table: &str,
+    schema: &TableSchema,
+) -> Result<Vec<String>> {
+    if schema.columns.is_empty() {
+        return Err(Error::Config(format!(
+            "Cannot create table {} without columns",
+            table
+        )));
+    }
+
+    let quoted_table = dialect.quote_identifier(table);
+    let inline_comments = dialect.comment_style() == CommentStyle::Inline;
+    let mut definitions = Vec::new();
+    for column in &schema.columns {
+        let mut definition = format!(
+            "{} {}",
+            dialect.quote_identifier(&column.name),
+            dialect.type_mapper().to_target_type(&column.data_type)?
+        );
+        // 显式写出 NULL, 避免 MySQL 旧版本把 TIMESTAMP 列默认为 NOT NULL
+        definition += if column.nullable {
+            " NULL"
+        } else {
+            " NOT NULL"
+        };
+        match &column.default {
+            Some(ColumnDefault::Literal(value)) => {
+                definition += &format!(" DEFAULT {}", dialect.quote_literal(value))
+            }
+            Some(ColumnDefault::Expression(expr)) => {
+                match translate_expression(dialect, schema, column, expr) {
+                    Some(expr) => definition += &format!(" DEFAULT {}", expr),
+                    None => warn!(
+                        table,
+                        column = %column.name,
+                        expression = %expr,
+                        "Dropping default expression the target database cannot evaluate"
+                    ),
+                }
+            }
+            None => {}
+        }
+        if let Some(expr) = &column.on_update {
+            match translate_expression(dialect, schema, column, expr) {
+                Some(expr) if dialect.on_update() => definition += &format!(" ON UPDATE {}", expr),
+                _ => warn!(
+                    table,
+                    column = %column.name,
+                    expression = %expr,
+                    "Dropping ON UPDATE expression the target database does not support"
+                ),
+            }
+        }
+        if let (true, Some(auto_increment)) = (column.auto_increment, dialect.auto_increment()) {
+            definition += &format!(" {}", auto_increment);
+        }
+        if let (true, Some(comment)) = (inline_comments, &column.comment) {
+            definition += &format!(" COMMENT {}", dialect.quote_literal(comment));
+        }
+        definitions.push(definition);
+    }
+
+    if !schema.primary_key.is_empty() {
+        definitions.push(format!(
+            "PRIMARY KEY ({})",
+            quote_identifiers(dialect, &schema.primary_key)
+        ));
+    }
+
+    let mut statements = Vec::new();
+    if dialect.inline_indexes() {
+        for index in &schema.indexes {
+            definitions.push(format!(
+                "{} {} ({})",
+                if index.unique { "UNIQUE KEY" } else { "KEY" },
+                dialect.quote_identifier(&index.name),
+                index_columns(dialect, &index.columns)
+            ));
+        }
+    } else {
+        // 单独建的索引名在整个库内唯一, 加上目标表名避免与源表的索引冲突
+        for index in &schema.indexes {
+            statements.push(format!(
+                "CREATE {}INDEX {} ON {} ({})",
+                if index.unique { "UNIQUE " } else { "" },
+                dialect.quote_identifier(&format!("{}_{}", table, index.name)),
+                quoted_table,
+                index_columns(dialect, &index.columns)
+            ));
+        }
+    }
+
+    let mut create_table = format!(
+        "CREATE TABLE {} (\n  {}\n)",
+        quoted_table,
+        definitions.join(",\n  ")
+    );
+    match (dialect.comment_style(), &schema.comment) {
+        (CommentStyle::Inline, Some(comment)) => {
+            create_table += &format!(" COMMENT={}", dialect.quote_literal(comment))
+        }
+        (CommentStyle::Statement, Some(comment)) => statements.push(format!(
+            "COMMENT ON TABLE {} IS {}",
+            quoted_table,
+            dialect.quote_literal(comment)
+        )),
+        _ => {}
+    }
+    if dialect.comment_style() == CommentStyle::Statement {
+        for column in &schema.columns {
+            if let Some(comment) = &column.comment {
+                statements.push(format!(
+                    "COMMENT ON COLUMN {}.{} IS {}",
+                    quoted_table,
+                    dialect.quote_identifier(&column.name),
+                    dialect.quote_literal(comment)
+                ));
+            }
+        }
+    }
+
+    statements.insert(0, create_table);
+    Ok(statements)
+}
+
+fn quote_identifiers(dialect: &dyn Dialect, names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| dialect.quote_identifier(name))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// 表达式只在源表的方言中原样使用. 其他方言只转换当前时间, 按列的精度生成,
+// 其余无法转换的返回 None
+fn translate_expression(
+    dialect: &dyn Dialect,
+    schema: &TableSchema,
+    column: &ColumnSchema,
+    expr: &str,
+) -> Option<String> {
+    if schema.dialect.as_deref() == Some(dialect.name()) {
+        return Some(expr.to_string());
+    }
+    is_current_timestamp(expr).then(|| {
+        dialect.current_timestamp(match column.data_type {
+            DbsyncType::DateTime(fsp) | DbsyncType::Timestamp(fsp) => fsp,
+            _ => 0,
+        })
+    })
+}
+
+// CURRENT_TIMESTAMP, CURRENT_TIMESTAMP(3), now() 等, 可以带外层括号
+fn is_current_timestamp(expr: &str) -> bool {
+    let mut expr = expr.trim();
+    while let Some(inner) = expr.strip_prefix('(').and_then(|e| e.strip_suffix(')')) {
+        expr = inner.trim();
+    }
+    let expr = expr.to_uppercase();
+    match expr.split_once('(') {
+        Some((name, args)) => {
+            matches!(
+                name.trim(),
+                "CURRENT_TIMESTAMP" | "NOW" | "LOCALTIMESTAMP" | "LOCALTIME"
+            ) && args
+                .strip_suffix(')')
+                .is_some_and(|args| args.trim().chars().all(|c| c.is_ascii_digit()))
+        }
+        None => matches!(
+            expr.as_str(),
+            "CURRENT_TIMESTAMP" | "LOCALTIMESTAMP" | "LOCALTIME"
+        ),
+    }
+}
+
+fn index_columns(dialect: &dyn Dialect, columns: &[IndexColumn]) -> String {
+    columns
+        .iter()
+        .map(|column| {
+            let mut definition = dialect.quote_identifier(&column.name);
+            if let (true, Some(length)) = (dialect.prefix_indexes(), column.prefix_length) {
+                definition += &format!("({})", length);
+            }
+            if column.descending {
+                definition += " DESC";
+            }
+            definition
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::{ColumnSchema, IndexSchema};
+    use crate::DbsyncType;
+
+    struct TestTypeMapper;
+
+    impl TypeMapper for TestTypeMapper {
+        fn to_dbsync_type(&self, source_type: &str) -> Result<DbsyncType> {
+            Err(Error::Type(format!("Unknown type: {}", source_type)))
+        }
+
+        fn to_target_type(&self, dbsync_type: &DbsyncType) -> Result<String> {
+            match dbsync_type {
+                DbsyncType::BigInt => Ok("INT8".to_string()),
+                DbsyncType::VarChar(n) => Ok(format!("VARCHAR({})", n)),
+                DbsyncType::Timestamp(n) => Ok(format!("TIMESTAMPTZ({})", n)),
+                _ => Err(Error::Type(format!("Unsupported type: {:?}", dbsync_type))),
+            }
+        }
+    }
+
+    // 与 PostgreSQL 类似: 双引号标识符, 独立的索引和注释语句
+    struct TestDialect;
+
+    impl Dialect for TestDialect {
+        fn name(&self) -> &str {
+            "test"
+        }
+
+        fn type_mapper(&self) -> &dyn TypeMapper {
+            &TestTypeMapper
+        }
+
+        fn auto_increment(&self) -> Option<&str> {
+            Some("GENERATED BY DEFAULT AS IDENTITY")
+        }
+
+        fn comment_style(&self) -> CommentStyle {
+            CommentStyle::Statement
+        }
+    }
+
+    fn users() -> TableSchema {
+        TableSchema::new("users")
+            .with_column(
+                ColumnSchema::new("id", DbsyncType::BigInt)
+                    .with_nullable(false)
+                    .with_auto_increment(true),
+            )
+            .with_column(
+                ColumnSchema::new("name", DbsyncType::VarChar(64))
+                    .with_default(ColumnDefault::Literal("it's".into()))
+                    .with_comment("名称"),
+            )
+            .with_primary_key(vec!["id".into()])
+            .with_index(IndexSchema::new("uk_name", vec!["name".into()], true))
+            .with_comment("用户表")
+    }
+
+    // 其他方言的表达式只转换当前时间, 其余丢弃; 同一方言原样保留
+    #[test]
+    fn test_expression_defaults() {
+        let schema = TableSchema::new("events")
+            .with_column(
+                ColumnSchema::new("uid", DbsyncType::VarChar(36))
+                    .with_default(ColumnDefault::Expression("(uuid())".into())),
+            )
+            .with_column(
+                ColumnSchema::new("updated_at", DbsyncType::Timestamp(3))
+                    .with_default(ColumnDefault::Expression("CURRENT_TIMESTAMP(3)".into()))
+                    .with_on_update("CURRENT_TIMESTAMP(3)"),
+            )
+            .with_column(
+                ColumnSchema::new("created_at", DbsyncType::Timestamp(6))
+                    .with_default(ColumnDefault::Expression("now()".into())),
+            );
+        let expected = "CREATE TABLE \"target\" (\n  \
+                        \"uid\" VARCHAR(36) NULL,\n  \
+                        \"updated_at\" TIMESTAMPTZ(3) NULL DEFAULT CURRENT_TIMESTAMP(3),\n  \
+                        \"created_at\" TIMESTAMPTZ(6) NULL DEFAULT CURRENT_TIMESTAMP(6)\n)";
+        assert_eq!(
+            create_table_statements(
+                &TestDialect,
+                "target",
+                &schema.clone().with_dialect("mysql")
+            )
+            .unwrap(),
+            vec![expected]
+        );
+        // 没有记录方言时同样处理
+        assert_eq!(
+            create_table_statements(&TestDialect, "target", &schema).unwrap(),
+            vec![expected]
+        );
+
+        let statements =
+            create_table_statements(&TestDialect, "target", &schema.with_dialect("test")).unwrap();
+        assert!(statements[0].contains("\"uid\" VARCHAR(36) NULL DEFAULT (uuid())"));
+        assert!(statements[0].contains("DEFAULT now()"));
+        // 方言不支持 ON UPDATE
+        assert!(!statements[0].contains("ON UPDATE"));
+    }
+
+    #[test]
+    fn test_is_current_timestamp() {
+        for expr in [
+            "CURRENT_TIMESTAMP",
+            "current_timestamp()",
+            "CURRENT_TIMESTAMP(3)",
+            "(now())",
+            "now(6)",
+            "LOCALTIMESTAMP",
+        ] {
+            assert!(is_current_timestamp(expr), "{}", expr);
+        }
+        for expr in [
+            "now",
+            "uuid()",
+            "(json_array())",
+            "CURRENT_TIMESTAMP + 1",
+            "now(a)",
+            "CURRENT_DATE",
+        ] {
+            assert!(!is_current_timestamp(expr), "{}", expr);
+        }
+    }
+
+    // 不支持前缀索引的方言索引整列, 降序保留
+    #[test]
+    fn test_index_columns() {
+        let schema = users().with_index(IndexSchema::with_columns(
+            "idx_name_id",
+            vec![
+                IndexColumn::new("name").with_prefix_length(8),
+                IndexColumn::new("id").with_descending(true),
+            ],
+            false,
+        ));
+        let statements = create_table_statements(&TestDialect, "target", &schema).unwrap();
+        assert_eq!(
+            statements[2],
+            "CREATE INDEX \"target_idx_name_id\" ON \"target\" (\"name\", \"id\" DESC)"
+        );
+    }
+
+    #[test]
+    fn test_create_table_statements() {
+        let statements = create_table_statements(&TestDialect, "target", &users()).unwrap();
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE TABLE \"target\" (\n  \
+                 \"id\" INT8 NOT NULL GENERATED BY DEFAULT AS IDENTITY,\n  \
+                 \"name\" VARCHAR(64) NULL DEFAULT 'it''s',\n  \
+                 PRIMARY KEY (\"id\")\n)"
+                    .to_string(),
+                "CREATE UNIQUE INDEX \"target_uk_name\" ON \"target\" (\"name\")".to_string(),
+                "COMMENT ON TABLE \"target\" IS '用户表'".to_string(),
+                "COMMENT ON COLUMN \"target\".\"name\" IS '名称'".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_table_statements_errors() {
+        assert!(create_table_statements(&TestDialect, "target", &TableSchema::new("t")).is_err());
+
+        // 目标方言无法表示的类型直接报错, 而不是生成错误的 DDL
+        let schema = users().with_column(ColumnSchema::new("payload", DbsyncType::Json));
+        assert!(matches!(
+            create_table_statements(&TestDialect, "target", &schema),
+            Err(Error::Type(_))
+        ));
+    }
+}
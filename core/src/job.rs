@@ -1,35 +1,62 @@
 use crate::{
-    connector::{Context, ShardedSourceExt, Sink, Transform},
+    checkpoint::{CheckpointStore, JobCheckpoint, RangeTracker},
+    connector::{
+        Context, DataBatch, KeyRange, RangeBatch, ShardedSource, Sink, Source, StreamBatch,
+        StreamingSource, Transform,
+    },
     error::{Error, Result},
     metrics::Metrics,
 };
-use tracing::info;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use tracing::{error, info};
 
 pub struct JobConfig {
+    // 任务名, checkpoint 以此为键
+    pub job_name: String,
     pub batch_size: usize,
+    // 并行读取的 reader 数量
+    pub reader_count: usize,
+    // 主键范围切分的区间数, 也是 checkpoint 的粒度, 不少于 reader 数量
+    pub range_count: usize,
+    // 从上次的 checkpoint 恢复: 跳过已完成的区间, 并且不重建目标表.
+    // 中断时未完成的区间会整体重新读取写入
+    pub resume: bool,
+    // 读取 → 转换 → 写入各阶段之间通道的容量(批次数)
     pub channel_size: usize,
+    // 并行写入的 worker 数量
+    pub writer_count: usize,
 }
 
 impl Default for JobConfig {
     fn default() -> Self {
         Self {
+            job_name: "dbsync".to_string(),
             batch_size: 2000,
+            reader_count: 4,
+            range_count: 32,
+            resume: false,
             channel_size: 10,
+            writer_count: 10,
         }
     }
 }
 
 pub struct SyncJob {
-    source: Box<dyn ShardedSourceExt>,
+    source: Box<dyn Source>,
     transforms: Vec<Box<dyn Transform>>,
     sink: Box<dyn Sink>,
     config: JobConfig,
+    checkpoint_store: Option<Arc<dyn CheckpointStore>>,
     metrics: Metrics,
 }
 
 impl SyncJob {
     pub fn new(
-        source: Box<dyn ShardedSourceExt>,
+        source: Box<dyn Source>,
         transforms: Vec<Box<dyn Transform>>,
         sink: Box<dyn Sink>,
     ) -> Self {
@@ -38,6 +65,7 @@ impl SyncJob {
             transforms,
             sink,
             config: JobConfig::default(),
+            checkpoint_store: None,
             metrics: Metrics::new(),
         }
     }
@@ -47,6 +75,35 @@ impl SyncJob {
         self
     }
 
+    pub fn with_checkpoint_store(mut self, store: Arc<dyn CheckpointStore>) -> Self {
+        self.checkpoint_store = Some(store);
+        self
+    }
+
+    // 读取上次保存的 checkpoint, 恢复运行和增量同步都需要 checkpoint store
+    async fn load_checkpoint(&self, incremental: bool) -> Result<Option<JobCheckpoint>> {
+        let Some(store) = &self.checkpoint_store else {
+            if self.config.resume {
+                return Err(Error::Config(
+                    "Resume requires a checkpoint store".to_string(),
+                ));
+            }
+            if incremental {
+                return Err(Error::Config(
+                    "Incremental sync requires a checkpoint store".to_string(),
+                ));
+            }
+            return Ok(None);
+        };
+        store.load(&self.config.job_name).await
+    }
+
+    fn sharded(&mut self) -> Result<&mut dyn ShardedSource> {
+        self.source
+            .as_sharded()
+            .ok_or_else(|| Error::Config("Source does not support sharded reads".into()))
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         info!("Starting sync job");
         self.metrics.start_job().await;
@@ -54,91 +111,1216 @@ impl SyncJob {
         // 创建上下文
         let mut ctx = Context::new();
 
-        // 初始化 source 并获取表结构
         info!("Initializing source");
         self.source.init(&mut ctx).await?;
-        let schema = self.source.get_schema().await?;
+
+        // 支持分片的 source 按主键区间并行读取, 否则按源端位置流式读取
+        if self.source.as_sharded().is_some() {
+            self.run_ranges(ctx).await?;
+        } else if self.source.as_streaming().is_some() {
+            self.run_stream(ctx).await?;
+        } else {
+            return Err(Error::Config(
+                "Source supports neither sharded nor streaming reads".into(),
+            ));
+        }
+
+        // 关闭连接器
+        self.sink.close().await?;
+        self.source.close().await?;
+
+        info!("Sync job completed");
+        self.metrics.end_job().await;
+        self.metrics.print_summary().await;
+
+        Ok(())
+    }
+
+    async fn run_ranges(&mut self, mut ctx: Context) -> Result<()> {
+        // 获取表结构
+        let schema = self.sharded()?.get_schema().await?;
         ctx.set_schema(schema);
 
-        // 初始化 sink (只执行一次建表)
+        let incremental = self.source.as_incremental().is_some();
+        let stored = self.load_checkpoint(incremental).await?;
+        // 上次成功的增量同步的水位, 只在增量模式下使用
+        let watermark = stored
+            .as_ref()
+            .filter(|_| incremental)
+            .and_then(|c| c.watermark.clone());
+        // 增量同步成功后 checkpoint 只保留水位, 没有可恢复的运行
+        let checkpoint =
+            stored.filter(|c| self.config.resume && (!incremental || c.target_watermark.is_some()));
+        if self.config.resume && checkpoint.is_none() {
+            info!(
+                "No checkpoint found for job {}, starting from scratch",
+                self.config.job_name
+            );
+        }
+
+        // 确定本次读取的水位区间, 恢复时沿用中断前的上界
+        let target_watermark = match self.source.as_incremental() {
+            Some(source) => {
+                let high = match checkpoint.as_ref().and_then(|c| c.target_watermark.clone()) {
+                    Some(high) => Some(high),
+                    None => source.get_max_watermark().await?,
+                };
+                info!(
+                    "Incremental sync on {} after {:?} up to {:?}",
+                    source.watermark_column(),
+                    watermark,
+                    high
+                );
+                if let Some(high) = &high {
+                    source.set_watermark_range(watermark.clone(), high.clone());
+                }
+                high
+            }
+            None => None,
+        };
+
+        ctx.resume = checkpoint.is_some() || watermark.is_some();
+        ctx.incremental = incremental;
+        ctx.replay = checkpoint
+            .as_ref()
+            .is_some_and(|c| !c.pending_ranges().is_empty());
+
+        // 初始化 sink (只执行一次建表, 恢复时保留已有的表)
         info!("Initializing sink");
         self.sink.init(&mut ctx).await?;
 
-        // Get ranges first
-        let sharded = self.source.as_sharded().unwrap();
-        let (min_id, max_id) = sharded.get_id_range().await?;
-        drop(sharded);
+        // 将主键范围切分为多个区间, 由多个 reader 并行读取
+        let reader_count = self.config.reader_count.max(1);
+        let checkpoint = match checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let range_count = self.config.range_count.max(reader_count);
+                // 增量模式下没有水位上界说明源表为空
+                let ranges = if incremental && target_watermark.is_none() {
+                    Vec::new()
+                } else {
+                    self.sharded()?.split_id_range(range_count).await?
+                };
+                let checkpoint = JobCheckpoint {
+                    watermark: watermark.clone(),
+                    target_watermark: target_watermark.clone(),
+                    ..JobCheckpoint::new(ranges)
+                };
+                if let Some(store) = &self.checkpoint_store {
+                    store.save(&self.config.job_name, &checkpoint).await?;
+                }
+                checkpoint
+            }
+        };
+        if checkpoint.ranges.is_empty() {
+            info!("Source table is empty");
+        }
 
-        // 读取所有数据
-        let mut source = self.source.clone_box();
-        let sharded = source.as_sharded().unwrap();
-        let mut batches = Vec::new();
-        let mut current_id = min_id;
+        let pending = checkpoint.pending_ranges();
+        info!(
+            "Reading {} of {} key ranges with {} readers",
+            pending.len(),
+            checkpoint.ranges.len(),
+            reader_count
+        );
+        let ranges = Arc::new(Mutex::new(VecDeque::from(pending)));
+        let tracker = Arc::new(RangeTracker::new(
+            self.config.job_name.clone(),
+            self.checkpoint_store.clone(),
+            checkpoint,
+        ));
+
+        // 读取 → 转换 → 写入 流水线, 各阶段之间使用有界通道,
+        // 通道写满时上游阻塞, 内存占用与表大小无关
+        let channel_size = self.config.channel_size.max(1);
+        let (read_tx, read_rx) = mpsc::channel(channel_size);
+        let (write_tx, write_rx) = mpsc::channel(channel_size);
 
-        while current_id < max_id {
-            if let Some(mut batch) = sharded.read_batch_range(current_id, max_id).await? {
-                self.metrics.record_read_batch(&batch).await;
+        let readers = (0..reader_count)
+            .map(|reader_id| {
+                tokio::spawn(read_ranges(
+                    reader_id,
+                    self.source.clone_box(),
+                    ranges.clone(),
+                    read_tx.clone(),
+                    tracker.clone(),
+                    self.metrics.clone(),
+                ))
+            })
+            .collect::<Vec<_>>();
+        // 所有 reader 结束后通道随之关闭
+        drop(read_tx);
+
+        let transforms = self.transforms.iter().map(|t| t.clone_box()).collect();
+        let transformer = tokio::spawn(apply_transforms(
+            transforms,
+            read_rx,
+            write_tx,
+            self.metrics.clone(),
+        ));
 
-                for transform in self.transforms.iter_mut() {
-                    batch = transform.transform(batch).await?;
-                    self.metrics.record_transform_batch(&batch).await;
+        // 多个写入 worker 共享同一个接收端
+        let write_rx = Arc::new(Mutex::new(write_rx));
+        let writers = (0..self.config.writer_count.max(1))
+            .map(|worker_id| {
+                tokio::spawn(write_batches(
+                    worker_id,
+                    self.sink.clone_box(),
+                    write_rx.clone(),
+                    tracker.clone(),
+                    self.metrics.clone(),
+                ))
+            })
+            .collect::<Vec<_>>();
+
+        // 等待所有阶段结束
+        let mut handles = readers;
+        handles.push(transformer);
+        handles.extend(writers);
+        join_pipeline(handles).await?;
+        self.sink.commit().await?;
+
+        // 所有数据写入后推进水位, 下次运行只读取新的行
+        if incremental {
+            if let Some(store) = &self.checkpoint_store {
+                let checkpoint = JobCheckpoint {
+                    watermark: target_watermark.or(watermark),
+                    ..Default::default()
+                };
+                store.save(&self.config.job_name, &checkpoint).await?;
+                info!("Watermark advanced to {:?}", checkpoint.watermark);
+            }
+        }
+
+        Ok(())
+    }
+
+    // 流式读取保证顺序: 单个 reader 按源端位置读取, 单个 writer 按顺序写入,
+    // 每个批次写入后保存位置, 恢复时从该位置之后继续. 写入的位置再交回 source 确认
+    async fn run_stream(&mut self, mut ctx: Context) -> Result<()> {
+        let checkpoint = self
+            .load_checkpoint(false)
+            .await?
+            .filter(|_| self.config.resume);
+        let position = checkpoint.and_then(|c| c.position);
+        if self.config.resume && position.is_none() {
+            info!(
+                "No checkpoint found for job {}, starting from the source's initial position",
+                self.config.job_name
+            );
+        }
+        ctx.resume = position.is_some();
+        // 变更会再次写入已存在的行
+        ctx.incremental = true;
+
+        info!("Initializing sink");
+        self.sink.init(&mut ctx).await?;
+
+        let channel_size = self.config.channel_size.max(1);
+        let (read_tx, read_rx) = mpsc::channel(channel_size);
+        let (write_tx, write_rx) = mpsc::channel(channel_size);
+        // 只需要最新的已写入位置
+        let (written_tx, mut written_rx) = watch::channel(None);
+
+        let transforms = self.transforms.iter().map(|t| t.clone_box()).collect();
+        let transformer = tokio::spawn(apply_transforms(
+            transforms,
+            read_rx,
+            write_tx,
+            self.metrics.clone(),
+        ));
+        let writer = tokio::spawn(write_stream(
+            self.sink.clone_box(),
+            write_rx,
+            written_tx,
+            self.checkpoint_store.clone(),
+            self.config.job_name.clone(),
+            self.metrics.clone(),
+        ));
+
+        // reader 在当前任务中运行, 流式 source 持有的复制连接无法交给其他任务
+        let streaming = self
+            .source
+            .as_streaming()
+            .ok_or_else(|| Error::Config("Source does not support streaming reads".into()))?;
+        let read_result = read_stream(
+            streaming,
+            position,
+            self.config.batch_size,
+            read_tx,
+            &mut written_rx,
+            &self.metrics,
+        )
+        .await;
+        if let Err(e) = &read_result {
+            if !matches!(e, Error::Channel(_)) {
+                self.metrics.record_error().await;
+                error!("Stream reader failed: {}", e);
+            }
+        }
+
+        let pipeline = join_pipeline(vec![transformer, writer]).await;
+        first_error([read_result, pipeline])?;
+        self.sink.commit().await?;
+
+        // reader 结束后写入的批次在此确认
+        let written = written_rx.borrow_and_update().clone();
+        match (written, self.source.as_streaming()) {
+            (Some(position), Some(streaming)) => streaming.acknowledge(position).await,
+            _ => Ok(()),
+        }
+    }
+}
+
+// 在流水线中传递的批次, 附带所属区间的编号
+type RangedBatch = (usize, DataBatch);
+
+// 流式读取的批次, 附带读完该批次后的源端位置
+type PositionedBatch = (Value, DataBatch);
+
+// 读取阶段: 从共享队列中依次取出区间读取, 直到队列为空
+async fn read_ranges(
+    reader_id: usize,
+    mut source: Box<dyn Source>,
+    ranges: Arc<Mutex<VecDeque<(usize, KeyRange)>>>,
+    tx: mpsc::Sender<RangedBatch>,
+    tracker: Arc<RangeTracker>,
+    metrics: Metrics,
+) -> Result<()> {
+    let sharded = source
+        .as_sharded()
+        .ok_or_else(|| Error::Config("Source does not support sharded reads".into()))?;
+
+    loop {
+        let range = ranges.lock().await.pop_front();
+        let Some((range_id, range)) = range else {
+            break;
+        };
+
+        let result = match read_range(sharded, range_id, range, &tx, &metrics).await {
+            Ok(batches) => tracker.range_read(range_id, batches).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            // 清空队列, 让其他 reader 读完当前区间后停止
+            ranges.lock().await.clear();
+            if !matches!(e, Error::Channel(_)) {
+                metrics.record_error().await;
+                error!("Reader {} failed: {}", reader_id, e);
+            }
+            return Err(e);
+        }
+    }
+
+    Ok(())
+}
+
+// 以上一批最后一行的主键为游标顺序读取整个区间, 返回读取的批次数
+async fn read_range(
+    sharded: &mut dyn ShardedSource,
+    range_id: usize,
+    range: KeyRange,
+    tx: &mpsc::Sender<RangedBatch>,
+    metrics: &Metrics,
+) -> Result<usize> {
+    let KeyRange { start, end } = range;
+    let mut cursor = start;
+    let mut batches = 0;
+
+    while let Some(RangeBatch { batch, last_key }) =
+        sharded.read_batch_range(cursor.as_ref(), &end).await?
+    {
+        metrics.record_read_batch(&batch).await;
+
+        tx.send((range_id, batch))
+            .await
+            .map_err(|_| Error::Channel("Transform stage stopped".into()))?;
+        batches += 1;
+
+        if last_key == end {
+            break;
+        }
+        cursor = Some(last_key);
+    }
+
+    Ok(batches)
+}
+
+// 流式读取阶段: 从 position 之后顺序读取, 直到流结束.
+// 每次读取前把 writer 最新写入的位置交给 source 确认
+async fn read_stream(
+    streaming: &mut dyn StreamingSource,
+    position: Option<Value>,
+    batch_size: usize,
+    tx: mpsc::Sender<PositionedBatch>,
+    written: &mut watch::Receiver<Option<Value>>,
+    metrics: &Metrics,
+) -> Result<()> {
+    streaming.start(position).await?;
+
+    loop {
+        if written.has_changed().unwrap_or(false) {
+            let position = written.borrow_and_update().clone();
+            if let Some(position) = position {
+                streaming.acknowledge(position).await?;
+            }
+        }
+
+        let next = tokio::select! {
+            next = streaming.read_stream_batch(batch_size) => next?,
+            // 下游失败时不必等到下一条数据到达
+            _ = tx.closed() => return Err(Error::Channel("Transform stage stopped".into())),
+        };
+        let Some(StreamBatch { batch, position }) = next else {
+            break;
+        };
+        metrics.record_read_batch(&batch).await;
+
+        tx.send((position, batch))
+            .await
+            .map_err(|_| Error::Channel("Transform stage stopped".into()))?;
+    }
+
+    Ok(())
+}
+
+// 转换阶段: 依次应用所有 transform, 批次附带的标记原样传递
+async fn apply_transforms<T: Send + 'static>(
+    mut transforms: Vec<Box<dyn Transform>>,
+    mut rx: mpsc::Receiver<(T, DataBatch)>,
+    tx: mpsc::Sender<(T, DataBatch)>,
+    metrics: Metrics,
+) -> Result<()> {
+    loop {
+        let item = tokio::select! {
+            item = rx.recv() => item,
+            // 写入阶段失败时上游可能长时间没有数据, 直接停止
+            _ = tx.closed() => return Err(Error::Channel("Write stage stopped".into())),
+        };
+        let Some((tag, mut batch)) = item else {
+            break;
+        };
+
+        for transform in transforms.iter_mut() {
+            batch = transform.transform(batch).await?;
+            metrics.record_transform_batch(&batch).await;
+        }
+
+        tx.send((tag, batch))
+            .await
+            .map_err(|_| Error::Channel("Write stage stopped".into()))?;
+    }
+
+    Ok(())
+}
+
+// 写入阶段: 从共享通道中取出批次并写入 sink
+async fn write_batches(
+    worker_id: usize,
+    mut sink: Box<dyn Sink>,
+    rx: Arc<Mutex<mpsc::Receiver<RangedBatch>>>,
+    tracker: Arc<RangeTracker>,
+    metrics: Metrics,
+) -> Result<()> {
+    loop {
+        // 只在取批次时持有锁, 写入期间其他 worker 可以继续取
+        let batch = rx.lock().await.recv().await;
+        let Some((range_id, batch)) = batch else {
+            break;
+        };
+
+        let result = match sink.write_batch(batch).await {
+            Ok(()) => tracker.batch_written(range_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            metrics.record_error().await;
+            // 关闭通道, 让上游阶段尽快停止
+            rx.lock().await.close();
+            error!("Worker {} failed to write batch: {}", worker_id, e);
+            return Err(e);
+        }
+        metrics.record_write_batch().await;
+    }
+
+    Ok(())
+}
+
+// 流式写入阶段: 按顺序写入, 每个批次写入后保存其位置, 并通知 reader 已写入的位置
+async fn write_stream(
+    mut sink: Box<dyn Sink>,
+    mut rx: mpsc::Receiver<PositionedBatch>,
+    written: watch::Sender<Option<Value>>,
+    store: Option<Arc<dyn CheckpointStore>>,
+    job_name: String,
+    metrics: Metrics,
+) -> Result<()> {
+    while let Some((position, batch)) = rx.recv().await {
+        let result = match sink.write_batch(batch).await {
+            Ok(()) => match &store {
+                Some(store) => {
+                    let checkpoint = JobCheckpoint {
+                        position: Some(position.clone()),
+                        ..Default::default()
+                    };
+                    store.save(&job_name, &checkpoint).await
                 }
-                batches.push(batch);
+                None => Ok(()),
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            metrics.record_error().await;
+            rx.close();
+            error!("Stream writer failed to write batch: {}", e);
+            return Err(e);
+        }
+        written.send_replace(Some(position));
+        metrics.record_write_batch().await;
+    }
 
-                // 更新当前ID
-                current_id += 1000; // 使用批次大小作为增量
-            } else {
-                break;
+    Ok(())
+}
+
+// 等待流水线各阶段结束
+async fn join_pipeline(handles: Vec<JoinHandle<Result<()>>>) -> Result<()> {
+    let mut results = Vec::new();
+    for handle in handles {
+        results.push(handle.await.unwrap_or_else(|e| Err(Error::Task(e))));
+    }
+    first_error(results)
+}
+
+// 优先返回真正的错误而不是由此引发的通道关闭错误
+fn first_error(results: impl IntoIterator<Item = Result<()>>) -> Result<()> {
+    let mut first_error = None;
+
+    for result in results {
+        let Err(err) = result else {
+            continue;
+        };
+
+        let replace = match &first_error {
+            None => true,
+            Some(Error::Channel(_)) => !matches!(err, Error::Channel(_)),
+            Some(_) => false,
+        };
+        if replace {
+            first_error = Some(err);
+        }
+    }
+
+    match first_error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checkpoint::CheckpointStore;
+    use crate::connector::{BatchField, BatchSchema, IncrementalSource, KeyValue, Record};
+    use crate::{DbsyncType, DbsyncValue, TableSchema};
+    use async_trait::async_trait;
+    use serde_json::json;
+    use std::any::Any;
+    use std::collections::HashMap;
+
+    fn key(id: i64) -> KeyValue {
+        KeyValue(vec![DbsyncValue::Integer(id)])
+    }
+
+    fn id_of(key: &KeyValue) -> i64 {
+        match key.0.as_slice() {
+            [DbsyncValue::Integer(id)] => *id,
+            other => panic!("unexpected key: {:?}", other),
+        }
+    }
+
+    fn id_batch(ids: Vec<i64>) -> DataBatch {
+        DataBatch::new(
+            Arc::new(BatchSchema::new(vec![BatchField::new(
+                "id",
+                DbsyncType::BigInt,
+            )])),
+            ids.into_iter()
+                .map(|id| Record::new(vec![DbsyncValue::Integer(id)]))
+                .collect(),
+        )
+    }
+
+    #[derive(Clone)]
+    struct MemorySource {
+        ids: Arc<Vec<i64>>,
+        batch_size: usize,
+        // 读取到该 id 时返回错误
+        fail_at: Option<i64>,
+        // 以 id 作为水位列的增量读取
+        incremental: bool,
+        watermark: Option<(Option<i64>, i64)>,
+    }
+
+    impl MemorySource {
+        fn new(ids: Vec<i64>, batch_size: usize) -> Self {
+            Self {
+                ids: Arc::new(ids),
+                batch_size,
+                fail_at: None,
+                incremental: false,
+                watermark: None,
             }
         }
 
-        // 读取完成后打印详细信息
-        info!("Total batches: {}", batches.len());
-        info!(
-            "Total records read: {}",
-            batches.iter().map(|b| b.records.len()).sum::<usize>()
-        );
+        // 水位区间内的 id
+        fn visible_ids(&self) -> Vec<i64> {
+            self.ids
+                .iter()
+                .copied()
+                .filter(|id| {
+                    self.watermark
+                        .is_none_or(|(low, high)| low.is_none_or(|low| *id > low) && *id <= high)
+                })
+                .collect()
+        }
+    }
+
+    #[async_trait]
+    impl Source for MemorySource {
+        async fn init(&mut self, _ctx: &mut Context) -> Result<()> {
+            Ok(())
+        }
+
+        async fn read_batch(&mut self, _batch_size: usize) -> Result<Option<DataBatch>> {
+            Ok(None)
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn Source> {
+            Box::new(self.clone())
+        }
 
-        // 并行写入数据
-        let worker_count = 10;
-        let mut handles = Vec::new();
-        let batch_size = batches.len() / worker_count;
-
-        for worker_id in 0..worker_count {
-            let mut sink = self.sink.clone_box();
-            let metrics = self.metrics.clone();
-            let start = worker_id * batch_size;
-            let end = if worker_id == worker_count - 1 {
-                batches.len()
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_sharded(&mut self) -> Option<&mut dyn ShardedSource> {
+            Some(self)
+        }
+
+        fn as_incremental(&mut self) -> Option<&mut dyn IncrementalSource> {
+            if self.incremental {
+                Some(self)
             } else {
-                (worker_id + 1) * batch_size
+                None
+            }
+        }
+    }
+
+    #[async_trait]
+    impl IncrementalSource for MemorySource {
+        fn watermark_column(&self) -> &str {
+            "id"
+        }
+
+        async fn get_max_watermark(&self) -> Result<Option<DbsyncValue>> {
+            Ok(self.ids.last().map(|id| DbsyncValue::Integer(*id)))
+        }
+
+        fn set_watermark_range(&mut self, low: Option<DbsyncValue>, high: DbsyncValue) {
+            let id = |value: DbsyncValue| id_of(&KeyValue(vec![value]));
+            self.watermark = Some((low.map(id), id(high)));
+        }
+    }
+
+    #[async_trait]
+    impl ShardedSource for MemorySource {
+        async fn get_total_records(&self) -> Result<i64> {
+            Ok(self.ids.len() as i64)
+        }
+
+        async fn get_primary_key(&self) -> Result<Vec<String>> {
+            Ok(vec!["id".to_string()])
+        }
+
+        async fn get_id_range(&self) -> Result<Option<(KeyValue, KeyValue)>> {
+            let ids = self.visible_ids();
+            Ok(ids.first().map(|min| (key(*min), key(ids[ids.len() - 1]))))
+        }
+
+        async fn read_batch_range(
+            &mut self,
+            after: Option<&KeyValue>,
+            end: &KeyValue,
+        ) -> Result<Option<RangeBatch>> {
+            let after = after.map(id_of);
+            let end = id_of(end);
+            let ids = self
+                .visible_ids()
+                .into_iter()
+                .filter(|id| after.is_none_or(|after| *id > after) && *id <= end)
+                .take(self.batch_size)
+                .collect::<Vec<_>>();
+
+            if let Some(fail_at) = self.fail_at.filter(|id| ids.contains(id)) {
+                return Err(Error::Read(format!("failed at {}", fail_at)));
+            }
+            let Some(last_id) = ids.last().copied() else {
+                return Ok(None);
             };
-            let worker_batches = batches[start..end].to_vec();
+            Ok(Some(RangeBatch {
+                batch: id_batch(ids),
+                last_key: key(last_id),
+            }))
+        }
 
-            let handle = tokio::spawn(async move {
-                for batch in worker_batches {
-                    sink.write_batch(batch).await?;
-                    metrics.record_write_batch().await;
-                }
-                Ok::<_, Error>(())
-            });
-            handles.push(handle);
+        async fn get_schema(&self) -> Result<TableSchema> {
+            Ok(TableSchema::default())
         }
+    }
+
+    #[derive(Clone, Default)]
+    struct MemorySink {
+        written: Arc<Mutex<Vec<i64>>>,
+        // 写入包含该 id 的批次时返回错误
+        fail_at: Option<i64>,
+        resumed: Arc<Mutex<Option<bool>>>,
+    }
 
-        // 等待所有写入任务完成
-        for handle in handles {
-            handle.await??;
+    #[async_trait]
+    impl Sink for MemorySink {
+        async fn init(&mut self, ctx: &mut Context) -> Result<()> {
+            *self.resumed.lock().await = Some(ctx.resume);
+            Ok(())
         }
 
-        // 关闭连接器
-        self.sink.commit().await?;
-        self.sink.close().await?;
-        self.source.close().await?;
+        async fn write_batch(&mut self, batch: DataBatch) -> Result<()> {
+            let ids = (0..batch.records.len())
+                .filter_map(|row| match batch.value(row, "id") {
+                    Some(DbsyncValue::Integer(id)) => Some(*id),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+            if self.fail_at.is_some_and(|id| ids.contains(&id)) {
+                return Err(Error::Write("sink failure".into()));
+            }
+            self.written.lock().await.extend(ids);
+            Ok(())
+        }
 
-        info!("Sync job completed");
-        self.metrics.end_job().await;
-        self.metrics.print_summary().await;
+        async fn commit(&mut self) -> Result<()> {
+            Ok(())
+        }
 
-        Ok(())
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn Sink> {
+            Box::new(self.clone())
+        }
+    }
+
+    fn job(ids: Vec<i64>, sink: MemorySink, channel_size: usize) -> SyncJob {
+        let source = MemorySource::new(ids, 1000);
+        SyncJob::new(Box::new(source), vec![], Box::new(sink)).with_config(JobConfig {
+            channel_size,
+            writer_count: 4,
+            ..Default::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_writes_all_records() {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let sink = MemorySink {
+            written: written.clone(),
+            ..Default::default()
+        };
+
+        // 通道容量远小于批次数, 验证读取与写入交替进行
+        job((1..=20_000).collect(), sink, 1).run().await.unwrap();
+
+        let mut written = written.lock().await.clone();
+        written.sort();
+        assert_eq!(written, (1..=20_000).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_propagates_write_error() {
+        let sink = MemorySink {
+            fail_at: Some(1),
+            ..Default::default()
+        };
+
+        let result = job((1..=20_000).collect(), sink, 2).run().await;
+        assert!(matches!(result, Err(Error::Write(_))));
+    }
+
+    async fn read_all(ids: Vec<i64>, batch_size: usize, reader_count: usize) -> Vec<i64> {
+        let written = Arc::new(Mutex::new(Vec::new()));
+        let sink = MemorySink {
+            written: written.clone(),
+            ..Default::default()
+        };
+        let source = MemorySource::new(ids, batch_size);
+        SyncJob::new(Box::new(source), vec![], Box::new(sink))
+            .with_config(JobConfig {
+                reader_count,
+                ..Default::default()
+            })
+            .run()
+            .await
+            .unwrap();
+
+        let mut written = written.lock().await.clone();
+        written.sort();
+        written
+    }
+
+    #[tokio::test]
+    async fn test_keyset_reads_dense_ids_once() {
+        let ids = (1..=10_000).collect::<Vec<_>>();
+        for reader_count in [1, 3, 8] {
+            assert_eq!(read_all(ids.clone(), 333, reader_count).await, ids);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keyset_reads_gapped_ids_once() {
+        // 稀疏且间隔不均匀的主键, 包括大于批次大小的空洞
+        let ids = (0..5_000)
+            .map(|i: i64| i * i + if i % 3 == 0 { 100_000 } else { 0 })
+            .collect::<std::collections::BTreeSet<_>>()
+            .into_iter()
+            .collect::<Vec<_>>();
+        for reader_count in [1, 3, 8] {
+            assert_eq!(read_all(ids.clone(), 100, reader_count).await, ids);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keyset_single_row_and_empty_table() {
+        assert_eq!(read_all(vec![42], 10, 4).await, vec![42]);
+        assert_eq!(read_all(vec![], 10, 4).await, Vec::<i64>::new());
+    }
+
+    #[tokio::test]
+    async fn test_reader_error_stops_job() {
+        let sink = MemorySink::default();
+        let mut source = MemorySource::new((1..=20_000).collect(), 100);
+        source.fail_at = Some(12_345);
+
+        let result = SyncJob::new(Box::new(source), vec![], Box::new(sink))
+            .with_config(JobConfig {
+                reader_count: 4,
+                ..Default::default()
+            })
+            .run()
+            .await;
+        assert!(matches!(result, Err(Error::Read(_))));
+    }
+
+    #[derive(Default)]
+    struct MemoryCheckpointStore {
+        checkpoints: std::sync::Mutex<HashMap<String, JobCheckpoint>>,
+    }
+
+    #[async_trait]
+    impl CheckpointStore for MemoryCheckpointStore {
+        async fn load(&self, job_name: &str) -> Result<Option<JobCheckpoint>> {
+            Ok(self.checkpoints.lock().unwrap().get(job_name).cloned())
+        }
+
+        async fn save(&self, job_name: &str, checkpoint: &JobCheckpoint) -> Result<()> {
+            self.checkpoints
+                .lock()
+                .unwrap()
+                .insert(job_name.to_string(), checkpoint.clone());
+            Ok(())
+        }
+    }
+
+    fn resumable_job(sink: MemorySink, store: Arc<MemoryCheckpointStore>, resume: bool) -> SyncJob {
+        let source = MemorySource::new((1..=10_000).collect(), 100);
+        SyncJob::new(Box::new(source), vec![], Box::new(sink))
+            .with_config(JobConfig {
+                job_name: "orders".to_string(),
+                reader_count: 2,
+                range_count: 10,
+                writer_count: 1,
+                channel_size: 1,
+                resume,
+                ..Default::default()
+            })
+            .with_checkpoint_store(store)
+    }
+
+    #[tokio::test]
+    async fn test_resume_skips_completed_ranges() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+
+        // 第一次运行在写入 id 5050 所在批次时失败
+        let first = MemorySink {
+            fail_at: Some(5_050),
+            ..Default::default()
+        };
+        let result = resumable_job(first.clone(), store.clone(), false)
+            .run()
+            .await;
+        assert!(matches!(result, Err(Error::Write(_))));
+        assert_eq!(*first.resumed.lock().await, Some(false));
+
+        let checkpoint = store.load("orders").await.unwrap().unwrap();
+        assert_eq!(checkpoint.ranges.len(), 10);
+        assert!(!checkpoint.completed.is_empty());
+        assert!(checkpoint.completed.len() < 10);
+        // 区间 [5001, 6000] 中的数据未能写入, 不会被标记为完成
+        assert!(!checkpoint.completed.contains(&5));
+
+        // 恢复运行只读取未完成的区间, 并通知 sink 保留已有数据
+        let second = MemorySink::default();
+        resumable_job(second.clone(), store.clone(), true)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(*second.resumed.lock().await, Some(true));
+
+        let completed_ids = checkpoint
+            .completed
+            .iter()
+            .flat_map(|id| (*id as i64 * 1000 + 1)..=(*id as i64 + 1) * 1000)
+            .collect::<std::collections::BTreeSet<_>>();
+        let second_ids = second.written.lock().await.clone();
+        assert!(second_ids.iter().all(|id| !completed_ids.contains(id)));
+
+        // 两次运行合起来覆盖所有数据
+        let mut all_ids = first.written.lock().await.clone();
+        all_ids.extend(second_ids);
+        all_ids.sort();
+        all_ids.dedup();
+        assert_eq!(all_ids, (1..=10_000).collect::<Vec<_>>());
+
+        let checkpoint = store.load("orders").await.unwrap().unwrap();
+        assert_eq!(checkpoint.completed.len(), 10);
+    }
+
+    #[tokio::test]
+    async fn test_resume_without_checkpoint_starts_fresh() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let sink = MemorySink::default();
+        resumable_job(sink.clone(), store, true)
+            .run()
+            .await
+            .unwrap();
+
+        assert_eq!(*sink.resumed.lock().await, Some(false));
+        assert_eq!(sink.written.lock().await.len(), 10_000);
+    }
+
+    fn incremental_job(
+        ids: std::ops::RangeInclusive<i64>,
+        sink: MemorySink,
+        store: Arc<MemoryCheckpointStore>,
+        resume: bool,
+    ) -> SyncJob {
+        let mut source = MemorySource::new(ids.collect(), 100);
+        source.incremental = true;
+        SyncJob::new(Box::new(source), vec![], Box::new(sink))
+            .with_config(JobConfig {
+                job_name: "events".to_string(),
+                reader_count: 2,
+                range_count: 4,
+                writer_count: 1,
+                channel_size: 1,
+                resume,
+                ..Default::default()
+            })
+            .with_checkpoint_store(store)
+    }
+
+    async fn written_ids(sink: &MemorySink) -> Vec<i64> {
+        let mut ids = sink.written.lock().await.clone();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn test_incremental_reads_rows_past_watermark() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+
+        let first = MemorySink::default();
+        incremental_job(1..=1_000, first.clone(), store.clone(), false)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(written_ids(&first).await, (1..=1_000).collect::<Vec<_>>());
+        assert_eq!(*first.resumed.lock().await, Some(false));
+
+        let checkpoint = store.load("events").await.unwrap().unwrap();
+        assert_eq!(checkpoint.watermark, Some(DbsyncValue::Integer(1_000)));
+        assert!(checkpoint.ranges.is_empty());
+
+        // 第二次运行只读取新增的行, 并保留目标表中已有的数据
+        let second = MemorySink::default();
+        incremental_job(1..=1_500, second.clone(), store.clone(), false)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            written_ids(&second).await,
+            (1_001..=1_500).collect::<Vec<_>>()
+        );
+        assert_eq!(*second.resumed.lock().await, Some(true));
+
+        // 没有新数据时不写入, 水位不变
+        let third = MemorySink::default();
+        incremental_job(1..=1_500, third.clone(), store.clone(), false)
+            .run()
+            .await
+            .unwrap();
+        assert!(written_ids(&third).await.is_empty());
+        let checkpoint = store.load("events").await.unwrap().unwrap();
+        assert_eq!(checkpoint.watermark, Some(DbsyncValue::Integer(1_500)));
+    }
+
+    #[tokio::test]
+    async fn test_incremental_resume_keeps_upper_bound() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+        incremental_job(1..=1_000, MemorySink::default(), store.clone(), false)
+            .run()
+            .await
+            .unwrap();
+
+        // 第二次运行失败, 水位不推进
+        let failed = MemorySink {
+            fail_at: Some(1_850),
+            ..Default::default()
+        };
+        let result = incremental_job(1..=2_000, failed.clone(), store.clone(), false)
+            .run()
+            .await;
+        assert!(matches!(result, Err(Error::Write(_))));
+        let checkpoint = store.load("events").await.unwrap().unwrap();
+        assert_eq!(checkpoint.watermark, Some(DbsyncValue::Integer(1_000)));
+        assert_eq!(
+            checkpoint.target_watermark,
+            Some(DbsyncValue::Integer(2_000))
+        );
+
+        // 恢复时沿用中断前的上界, 之后新增的行留给下一次运行
+        let resumed = MemorySink::default();
+        incremental_job(1..=3_000, resumed.clone(), store.clone(), true)
+            .run()
+            .await
+            .unwrap();
+        let mut ids = failed.written.lock().await.clone();
+        ids.extend(written_ids(&resumed).await);
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids, (1_001..=2_000).collect::<Vec<_>>());
+
+        let next = MemorySink::default();
+        incremental_job(1..=3_000, next.clone(), store.clone(), true)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(
+            written_ids(&next).await,
+            (2_001..=3_000).collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_incremental_requires_checkpoint_store() {
+        let mut source = MemorySource::new(vec![1, 2, 3], 100);
+        source.incremental = true;
+        let result = SyncJob::new(Box::new(source), vec![], Box::new(MemorySink::default()))
+            .run()
+            .await;
+        assert!(matches!(result, Err(Error::Config(_))));
+    }
+
+    // 以 id 的序号作为位置的流式 source
+    #[derive(Clone)]
+    struct MemoryStream {
+        ids: Arc<Vec<i64>>,
+        offset: usize,
+        // 依次收到的确认位置
+        acknowledged: Arc<Mutex<Vec<u64>>>,
+    }
+
+    #[async_trait]
+    impl Source for MemoryStream {
+        async fn init(&mut self, _ctx: &mut Context) -> Result<()> {
+            Ok(())
+        }
+
+        async fn read_batch(&mut self, batch_size: usize) -> Result<Option<DataBatch>> {
+            Ok(self
+                .read_stream_batch(batch_size)
+                .await?
+                .map(|stream_batch| stream_batch.batch))
+        }
+
+        async fn close(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        fn clone_box(&self) -> Box<dyn Source> {
+            Box::new(self.clone())
+        }
+
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_streaming(&mut self) -> Option<&mut dyn StreamingSource> {
+            Some(self)
+        }
+    }
+
+    #[async_trait]
+    impl StreamingSource for MemoryStream {
+        async fn start(&mut self, position: Option<Value>) -> Result<()> {
+            self.offset = position.and_then(|p| p.as_u64()).unwrap_or(0) as usize;
+            Ok(())
+        }
+
+        async fn read_stream_batch(&mut self, batch_size: usize) -> Result<Option<StreamBatch>> {
+            let ids = self
+                .ids
+                .iter()
+                .skip(self.offset)
+                .take(batch_size)
+                .copied()
+                .collect::<Vec<_>>();
+            if ids.is_empty() {
+                return Ok(None);
+            }
+            self.offset += ids.len();
+
+            Ok(Some(StreamBatch {
+                batch: id_batch(ids),
+                position: json!(self.offset),
+            }))
+        }
+
+        async fn acknowledge(&mut self, position: Value) -> Result<()> {
+            self.acknowledged
+                .lock()
+                .await
+                .push(position.as_u64().unwrap());
+            Ok(())
+        }
+    }
+
+    fn stream_job(sink: MemorySink, store: Arc<MemoryCheckpointStore>, resume: bool) -> SyncJob {
+        stream_job_with_acks(sink, store, resume, Arc::default())
+    }
+
+    fn stream_job_with_acks(
+        sink: MemorySink,
+        store: Arc<MemoryCheckpointStore>,
+        resume: bool,
+        acknowledged: Arc<Mutex<Vec<u64>>>,
+    ) -> SyncJob {
+        let source = MemoryStream {
+            ids: Arc::new((1..=1_000).collect()),
+            offset: 0,
+            acknowledged,
+        };
+        SyncJob::new(Box::new(source), vec![], Box::new(sink))
+            .with_config(JobConfig {
+                job_name: "stream".to_string(),
+                batch_size: 30,
+                channel_size: 1,
+                resume,
+                ..Default::default()
+            })
+            .with_checkpoint_store(store)
+    }
+
+    #[tokio::test]
+    async fn test_stream_writes_in_order_and_saves_position() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let sink = MemorySink::default();
+        stream_job(sink.clone(), store.clone(), false)
+            .run()
+            .await
+            .unwrap();
+
+        // 单个 writer 按读取顺序写入
+        assert_eq!(*sink.written.lock().await, (1..=1_000).collect::<Vec<_>>());
+        let checkpoint = store.load("stream").await.unwrap().unwrap();
+        assert_eq!(checkpoint.position, Some(json!(1_000)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_resumes_after_last_written_position() {
+        let store = Arc::new(MemoryCheckpointStore::default());
+        let failed = MemorySink {
+            fail_at: Some(500),
+            ..Default::default()
+        };
+        let result = stream_job(failed.clone(), store.clone(), false).run().await;
+        assert!(matches!(result, Err(Error::Write(_))));
+
+        // 位置只推进到最后一个写入成功的批次
+        let written = failed.written.lock().await.clone();
+        let checkpoint = store.load("stream").await.unwrap().unwrap();
+        assert_eq!(checkpoint.position, Some(json!(written.len())));
+
+        let resumed = MemorySink::default();
+        stream_job(resumed.clone(), store.clone(), true)
+            .run()
+            .await
+            .unwrap();
+        assert_eq!(*resumed.resumed.lock().await, Some(true));
+
+        let mut all = written;
+        all.extend(resumed.written.lock().await.iter());
+        assert_eq!(all, (1..=1_000).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_stream_acknowledges_written_positions() {
+        let acknowledged = Arc::new(Mutex::new(Vec::new()));
+        let failed = MemorySink {
+            fail_at: Some(500),
+            ..Default::default()
+        };
+        let result = stream_job_with_acks(
+            failed.clone(),
+            Arc::new(MemoryCheckpointStore::default()),
+            false,
+            acknowledged.clone(),
+        )
+        .run()
+        .await;
+        assert!(result.is_err());
+        // 写入失败后不再确认, 已确认的位置都已写入
+        let written = failed.written.lock().await.len() as u64;
+        let acks = acknowledged.lock().await.clone();
+        assert!(acks.windows(2).all(|w| w[0] < w[1]));
+        assert!(acks.last().is_none_or(|last| *last <= written));
+
+        acknowledged.lock().await.clear();
+        stream_job_with_acks(
+            MemorySink::default(),
+            Arc::new(MemoryCheckpointStore::default()),
+            false,
+            acknowledged.clone(),
+        )
+        .run()
+        .await
+        .unwrap();
+        // 流结束后确认最后写入的位置
+        let acks = acknowledged.lock().await.clone();
+        assert!(acks.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(acks.last(), Some(&1_000));
     }
 }
@@ -1,10 +1,15 @@
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod checkpoint;
 pub mod config;
 pub mod connector;
+pub mod ddl;
 pub mod error;
 pub mod job;
 pub mod metrics;
 pub mod plugin;
 pub mod runtime;
+pub mod schema;
 pub mod types;
 
 pub use config::Config;
@@ -13,4 +18,5 @@ pub use error::Error;
 pub use job::SyncJob;
 pub use plugin::PluginManager;
 pub use runtime::Runtime;
+pub use schema::{ColumnDefault, ColumnSchema, IndexColumn, IndexSchema, TableSchema};
 pub use types::*;
@@ -6,6 +6,12 @@ pub struct PluginLoader {
     plugin_path: Option<String>,
 }
 
+impl Default for PluginLoader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PluginLoader {
     pub fn new() -> Self {
         Self { plugin_path: None }
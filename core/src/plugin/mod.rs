@@ -5,26 +5,30 @@ pub use loader::PluginLoader;
 pub use registry::PluginRegistry;
 
 use crate::{
-    connector::{ConnectorConfig, ShardedSourceExt, Sink, Source},
+    connector::{ConnectorConfig, Sink, Source},
     error::{Error, Result},
 };
 
 pub struct PluginManager {
     registry: PluginRegistry,
-    loader: PluginLoader,
+}
+
+impl Default for PluginManager {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl PluginManager {
     pub fn new() -> Self {
         Self {
             registry: PluginRegistry::new(),
-            loader: PluginLoader::new(),
         }
     }
 
     pub fn register_source<F>(&mut self, name: &str, factory: F)
     where
-        F: Fn(ConnectorConfig) -> Result<Box<dyn ShardedSourceExt>> + Send + Sync + 'static,
+        F: Fn(ConnectorConfig) -> Result<Box<dyn Source>> + Send + Sync + 'static,
     {
         self.registry.register_source(name, Box::new(factory));
     }
@@ -36,7 +40,7 @@ impl PluginManager {
         self.registry.register_sink(name, Box::new(factory));
     }
 
-    pub fn create_source(&self, config: ConnectorConfig) -> Result<Box<dyn ShardedSourceExt>> {
+    pub fn create_source(&self, config: ConnectorConfig) -> Result<Box<dyn Source>> {
         let factory = self
             .registry
             .get_source_factory(&config.connector_type)
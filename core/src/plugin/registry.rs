@@ -1,11 +1,10 @@
 use crate::{
-    connector::{ConnectorConfig, ShardedSourceExt, Sink},
+    connector::{ConnectorConfig, Sink, Source},
     error::Result,
 };
 use std::collections::HashMap;
 
-type SourceFactory =
-    Box<dyn Fn(ConnectorConfig) -> Result<Box<dyn ShardedSourceExt>> + Send + Sync>;
+type SourceFactory = Box<dyn Fn(ConnectorConfig) -> Result<Box<dyn Source>> + Send + Sync>;
 type SinkFactory = Box<dyn Fn(ConnectorConfig) -> Result<Box<dyn Sink>> + Send + Sync>;
 
 pub struct PluginRegistry {
@@ -13,6 +12,12 @@ pub struct PluginRegistry {
     sinks: HashMap<String, SinkFactory>,
 }
 
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl PluginRegistry {
     pub fn new() -> Self {
         Self {
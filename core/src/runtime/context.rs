@@ -7,6 +7,12 @@ pub struct RuntimeContext {
     pub shutdown: Arc<Mutex<bool>>,
 }
 
+impl Default for RuntimeContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl RuntimeContext {
     pub fn new() -> Self {
         Self {
@@ -1,17 +1,18 @@
 pub mod context;
 
 use crate::error::Result;
-use context::RuntimeContext;
 
-pub struct Runtime {
-    context: RuntimeContext,
+pub struct Runtime;
+
+impl Default for Runtime {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Runtime {
     pub fn new() -> Self {
-        Self {
-            context: RuntimeContext::new(),
-        }
+        Self
     }
 
     pub async fn run(&self) -> Result<()> {
@@ -0,0 +1,235 @@
+use crate::connector::{BatchField, BatchSchema};
+use crate::DbsyncType;
+use serde::{Deserialize, Serialize};
+
+// 列默认值
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnDefault {
+    // 字面量, 生成 DDL 时按字符串加引号
+    Literal(String),
+    // 表达式, 如 CURRENT_TIMESTAMP, 同一方言中原样输出
+    Expression(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: DbsyncType,
+    pub nullable: bool,
+    pub default: Option<ColumnDefault>,
+    // 更新行时自动赋值的表达式, 如 MySQL 的 ON UPDATE CURRENT_TIMESTAMP
+    #[serde(default)]
+    pub on_update: Option<String>,
+    pub auto_increment: bool,
+    pub comment: Option<String>,
+}
+
+impl ColumnSchema {
+    // 默认可为空, 无默认值
+    pub fn new(name: impl Into<String>, data_type: DbsyncType) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            nullable: true,
+            default: None,
+            on_update: None,
+            auto_increment: false,
+            comment: None,
+        }
+    }
+
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = nullable;
+        self
+    }
+
+    pub fn with_default(mut self, default: ColumnDefault) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub fn with_on_update(mut self, on_update: impl Into<String>) -> Self {
+        self.on_update = Some(on_update.into());
+        self
+    }
+
+    pub fn with_auto_increment(mut self, auto_increment: bool) -> Self {
+        self.auto_increment = auto_increment;
+        self
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+}
+
+// 索引中的一列
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexColumn {
+    pub name: String,
+    // 前缀索引只索引前 N 个字符 (二进制类型为字节), 如 MySQL 的 KEY (name(10))
+    pub prefix_length: Option<u32>,
+    pub descending: bool,
+}
+
+impl IndexColumn {
+    // 整列, 升序
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            prefix_length: None,
+            descending: false,
+        }
+    }
+
+    pub fn with_prefix_length(mut self, prefix_length: u32) -> Self {
+        self.prefix_length = Some(prefix_length);
+        self
+    }
+
+    pub fn with_descending(mut self, descending: bool) -> Self {
+        self.descending = descending;
+        self
+    }
+}
+
+// 二级索引, 主键单独记录在 TableSchema::primary_key 中
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexSchema {
+    pub name: String,
+    pub columns: Vec<IndexColumn>,
+    pub unique: bool,
+}
+
+impl IndexSchema {
+    // 各列都是整列升序
+    pub fn new(name: impl Into<String>, columns: Vec<String>, unique: bool) -> Self {
+        Self::with_columns(
+            name,
+            columns.into_iter().map(IndexColumn::new).collect(),
+            unique,
+        )
+    }
+
+    pub fn with_columns(name: impl Into<String>, columns: Vec<IndexColumn>, unique: bool) -> Self {
+        Self {
+            name: name.into(),
+            columns,
+            unique,
+        }
+    }
+
+    pub fn column_names(&self) -> Vec<String> {
+        self.columns.iter().map(|c| c.name.clone()).collect()
+    }
+}
+
+// 与具体数据库无关的表结构, 由 source 读取, sink 按自己的方言生成 DDL
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableSchema {
+    // 源表名, sink 使用自己配置的目标表名
+    pub name: String,
+    // 按列定义顺序排列
+    pub columns: Vec<ColumnSchema>,
+    pub primary_key: Vec<String>,
+    pub indexes: Vec<IndexSchema>,
+    pub comment: Option<String>,
+    // 源数据库的方言名 (Dialect::name), 表达式只能在同一方言中原样使用
+    #[serde(default)]
+    pub dialect: Option<String>,
+}
+
+impl TableSchema {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_column(mut self, column: ColumnSchema) -> Self {
+        self.columns.push(column);
+        self
+    }
+
+    pub fn with_primary_key(mut self, columns: Vec<String>) -> Self {
+        self.primary_key = columns;
+        self
+    }
+
+    pub fn with_index(mut self, index: IndexSchema) -> Self {
+        self.indexes.push(index);
+        self
+    }
+
+    pub fn with_comment(mut self, comment: impl Into<String>) -> Self {
+        self.comment = Some(comment.into());
+        self
+    }
+
+    pub fn with_dialect(mut self, dialect: impl Into<String>) -> Self {
+        self.dialect = Some(dialect.into());
+        self
+    }
+
+    pub fn column(&self, name: &str) -> Option<&ColumnSchema> {
+        self.columns.iter().find(|c| c.name == name)
+    }
+
+    // 转换为批次的列描述
+    pub fn batch_schema(&self) -> BatchSchema {
+        BatchSchema::new(
+            self.columns
+                .iter()
+                .map(|c| {
+                    BatchField::new(c.name.clone(), c.data_type.clone()).with_nullable(c.nullable)
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_table_schema_serde() {
+        let schema = TableSchema::new("users")
+            .with_column(
+                ColumnSchema::new("id", DbsyncType::BigInt)
+                    .with_nullable(false)
+                    .with_auto_increment(true),
+            )
+            .with_column(
+                ColumnSchema::new("name", DbsyncType::VarChar(64))
+                    .with_default(ColumnDefault::Literal("anon".into()))
+                    .with_comment("用户名"),
+            )
+            .with_primary_key(vec!["id".into()])
+            .with_column(
+                ColumnSchema::new("updated_at", DbsyncType::Timestamp(0))
+                    .with_default(ColumnDefault::Expression("CURRENT_TIMESTAMP".into()))
+                    .with_on_update("CURRENT_TIMESTAMP"),
+            )
+            .with_index(IndexSchema::new("uk_name", vec!["name".into()], true))
+            .with_comment("用户表")
+            .with_dialect("mysql");
+
+        let json = serde_json::to_string(&schema).unwrap();
+        let parsed: TableSchema = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, schema);
+
+        assert!(!schema.column("id").unwrap().nullable);
+        assert!(schema.column("missing").is_none());
+        let batch_schema = schema.batch_schema();
+        assert_eq!(
+            batch_schema.names().collect::<Vec<_>>(),
+            vec!["id", "name", "updated_at"]
+        );
+        assert!(!batch_schema.fields[0].nullable);
+        assert!(batch_schema.fields[1].nullable);
+    }
+}
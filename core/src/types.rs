@@ -4,10 +4,19 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DbsyncType {
     // 整数类型
-    TinyInt,  // MySQL TINYINT
-    SmallInt, // SMALLINT
-    Int,      // INT
-    BigInt,   // BIGINT
+    TinyInt,   // MySQL TINYINT
+    SmallInt,  // SMALLINT
+    MediumInt, // MEDIUMINT
+    Int,       // INT
+    BigInt,    // BIGINT
+
+    // 无符号整数类型, 超出 i64 范围的值使用 DbsyncValue::UInteger
+    UnsignedTinyInt,   // TINYINT UNSIGNED
+    UnsignedSmallInt,  // SMALLINT UNSIGNED
+    UnsignedMediumInt, // MEDIUMINT UNSIGNED
+    UnsignedInt,       // INT UNSIGNED
+    UnsignedBigInt,    // BIGINT UNSIGNED
+    Bit(u8),           // BIT(n), n 为 1..=64, 值为 UInteger
 
     // 浮点数类型
     Float,  // FLOAT
@@ -15,39 +24,155 @@ pub enum DbsyncType {
 
     // 精确数值类型
     Decimal(u8, u8), // DECIMAL(precision, scale)
-    Numeric(u8, u8), // NUMERIC(precision, scale)
+    Numeric(u8, u8), // NUMERIC(precision, scale), Numeric(0, 0) 为未限制精度的 NUMERIC
 
     // 字符串类型
-    Char(u32),    // CHAR(n)
-    VarChar(u32), // VARCHAR(n)
-    Text,         // TEXT
+    Char(u32),         // CHAR(n)
+    VarChar(u32),      // VARCHAR(n)
+    TinyText,          // TINYTEXT
+    Text,              // TEXT
+    MediumText,        // MEDIUMTEXT
+    LongText,          // LONGTEXT
+    Enum(Vec<String>), // ENUM('a', 'b'), 值为成员的文本
+    Set(Vec<String>),  // SET('a', 'b'), 值为逗号分隔的成员
 
     // 时间类型
-    Date,      // DATE
-    Time,      // TIME
-    DateTime,  // DATETIME
-    Timestamp, // TIMESTAMP
+    // 参数为秒的小数位数 (fsp), 0..=6
+    Date,          // DATE, 值为 Date
+    Time(u8),      // TIME(fsp), 值为 Time
+    DateTime(u8),  // DATETIME(fsp), 值为 DateTime, 不带时区
+    Timestamp(u8), // TIMESTAMP(fsp), 值为 Timestamp, UTC 时刻
+    Year,          // YEAR, 值为 Integer
+
+    // 二进制类型
+    Binary(u32),    // BINARY(n), 定长
+    VarBinary(u32), // VARBINARY(n)
+    TinyBlob,       // TINYBLOB
+    Blob,           // BLOB
+    MediumBlob,     // MEDIUMBLOB
+    LongBlob,       // LONGBLOB
 
     // 其他类型
-    Boolean,     // BOOLEAN/TINYINT(1)
-    Binary(u32), // BINARY/VARBINARY(n)
-    Blob,        // BLOB
-    Json,        // JSON
+    Boolean,                // BOOLEAN/TINYINT(1)
+    Json,                   // JSON
+    Spatial(SpatialType),   // GEOMETRY 及其子类型, 值为 4 字节小端 SRID + WKB
+    Uuid,                   // PostgreSQL UUID, 值为带连字符的小写文本
+    Array(Box<DbsyncType>), // PostgreSQL 一维数组, 值为 Json 数组, NULL 元素为 null
 
     // 特殊类型
     Null, // NULL 值
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+// 空间类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpatialType {
+    Geometry,
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+}
+
+impl DbsyncType {
+    pub fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            DbsyncType::Date
+                | DbsyncType::Time(_)
+                | DbsyncType::DateTime(_)
+                | DbsyncType::Timestamp(_)
+        )
+    }
+
+    pub fn is_signed_integer(&self) -> bool {
+        matches!(
+            self,
+            DbsyncType::TinyInt
+                | DbsyncType::SmallInt
+                | DbsyncType::MediumInt
+                | DbsyncType::Int
+                | DbsyncType::BigInt
+        )
+    }
+
+    pub fn is_unsigned_integer(&self) -> bool {
+        matches!(
+            self,
+            DbsyncType::UnsignedTinyInt
+                | DbsyncType::UnsignedSmallInt
+                | DbsyncType::UnsignedMediumInt
+                | DbsyncType::UnsignedInt
+                | DbsyncType::UnsignedBigInt
+                | DbsyncType::Bit(_)
+        )
+    }
+
+    // 值为 DbsyncValue::String 的文本类型
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self,
+            DbsyncType::Char(_)
+                | DbsyncType::VarChar(_)
+                | DbsyncType::TinyText
+                | DbsyncType::Text
+                | DbsyncType::MediumText
+                | DbsyncType::LongText
+                | DbsyncType::Enum(_)
+                | DbsyncType::Set(_)
+        )
+    }
+
+    // 未指定精度的 NUMERIC, 如 PostgreSQL 的 numeric, 位数不受限制
+    pub fn is_unconstrained_numeric(&self) -> bool {
+        *self == DbsyncType::Numeric(0, 0)
+    }
+
+    // 值为 DbsyncValue::Binary 的类型
+    pub fn is_binary(&self) -> bool {
+        matches!(
+            self,
+            DbsyncType::Binary(_)
+                | DbsyncType::VarBinary(_)
+                | DbsyncType::TinyBlob
+                | DbsyncType::Blob
+                | DbsyncType::MediumBlob
+                | DbsyncType::LongBlob
+                | DbsyncType::Spatial(_)
+        )
+    }
+}
+
+pub const MICROS_PER_SECOND: i64 = 1_000_000;
+pub const MICROS_PER_DAY: i64 = 86_400 * MICROS_PER_SECOND;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DbsyncValue {
     Null,
     Integer(i64),
+    UInteger(u64), // 无符号整数, 可超出 i64 范围
     Float(f64),
     Decimal(String),
     String(String),
-    DateTime(i64),
+    Date(i32),      // 距 1970-01-01 的天数
+    Time(i64),      // 微秒数, 可为负或超过 24 小时 (MySQL TIME 范围为 ±838:59:59)
+    DateTime(i64),  // 墙上时间, 按 UTC 解释时距 1970-01-01 00:00:00 的微秒数
+    Timestamp(i64), // UTC 时刻, 距 Unix 纪元的微秒数
     Boolean(bool),
     Binary(Vec<u8>),
+    Json(serde_json::Value), // JSON 文档, 保留嵌套结构
+}
+
+impl DbsyncValue {
+    // 按 JSON Pointer (RFC 6901) 取嵌套的值, 如 "/address/city", 非 JSON 值返回 None
+    pub fn json_pointer(&self, pointer: &str) -> Option<&serde_json::Value> {
+        match self {
+            DbsyncValue::Json(json) => json.pointer(pointer),
+            _ => None,
+        }
+    }
 }
 
 pub trait TypeMapper {
@@ -65,5 +190,6 @@ pub trait TypeConverter {
         row: &Self::Row,
         type_name: &DbsyncType,
     ) -> Result<DbsyncValue>;
+    #[allow(clippy::wrong_self_convention)]
     fn from_dbsync_value(&self, value: &DbsyncValue, target_type: &DbsyncType) -> Result<String>;
 }
@@ -16,10 +16,18 @@ pub struct Cli {
 
     /// 是否开启计时
     #[arg(short, long)]
-    timing: bool,
+    pub timing: bool,
+
+    /// 从上次中断的位置恢复任务
+    #[arg(short, long)]
+    pub resume: bool,
+
+    /// checkpoint 文件目录
+    #[arg(long, default_value = ".dbsync/checkpoints")]
+    pub checkpoint_dir: PathBuf,
 }
 
-pub fn parse_config() -> Result<(Config, bool), Box<dyn std::error::Error>> {
+pub fn parse_config() -> Result<(Config, Cli), Box<dyn std::error::Error>> {
     // 初始化日志
     tracing_subscriber::fmt().with_env_filter("info").init();
 
@@ -27,10 +35,10 @@ pub fn parse_config() -> Result<(Config, bool), Box<dyn std::error::Error>> {
     let cli = Cli::parse();
 
     // 读取配置文件
-    let config_content = std::fs::read_to_string(cli.config)?;
+    let config_content = std::fs::read_to_string(&cli.config)?;
     let config: Config = serde_json::from_str(&config_content)?;
 
-    Ok((config, cli.timing))
+    Ok((config, cli))
 }
 
 pub struct Timer {
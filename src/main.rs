@@ -1,36 +1,98 @@
 mod cli;
 
+use dbsync_core::checkpoint::FileCheckpointStore;
+use dbsync_core::job::JobConfig;
 use dbsync_core::plugin::PluginManager;
-use dbsync_core::{Config, SyncJob};
-use dbsync_transforms::FieldRenameTransform;
+use dbsync_core::SyncJob;
+use std::sync::Arc;
 use tracing::info;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 解析配置并获取计时标志
-    let (config, timing) = cli::parse_config()?;
-    let timer = cli::Timer::new(timing);
+    // 解析配置和命令行参数
+    let (config, cli) = cli::parse_config()?;
+    let timer = cli::Timer::new(cli.timing);
 
     info!("Starting dbsync...");
-
-    // 读取配置文件
-    let config_content = std::fs::read_to_string("config.json")?;
-    let config: Config = serde_json::from_str(&config_content)?;
     info!("Loaded configuration: {:?}", config);
 
     // 创建插件管理器
+    // 未启用任何连接器 feature 时不会注册连接器
+    #[cfg_attr(
+        not(any(
+            feature = "mysql",
+            feature = "postgres",
+            feature = "sqlite",
+            feature = "file"
+        )),
+        allow(unused_mut)
+    )]
     let mut plugin_manager = PluginManager::new();
     info!("Initializing plugins...");
 
     // 注册内置连接器
+    #[cfg(feature = "mysql")]
     plugin_manager.register_source("mysql", |config| {
         info!("Creating MySQL source");
         Ok(Box::new(dbsync_mysql::MySQLSource::new(config)?))
     });
+    #[cfg(feature = "mysql")]
+    plugin_manager.register_source("mysql_cdc", |config| {
+        info!("Creating MySQL binlog source");
+        Ok(Box::new(dbsync_mysql::MySQLBinlogSource::new(config)?))
+    });
+    #[cfg(feature = "mysql")]
     plugin_manager.register_sink("mysql", |config| {
         info!("Creating MySQL sink");
         Ok(Box::new(dbsync_mysql::MySQLSink::new(config)?))
     });
+    #[cfg(feature = "postgres")]
+    plugin_manager.register_source("postgres", |config| {
+        info!("Creating PostgreSQL source");
+        Ok(Box::new(dbsync_postgres::PostgresSource::new(config)?))
+    });
+    #[cfg(feature = "postgres")]
+    plugin_manager.register_source("postgres_cdc", |config| {
+        info!("Creating PostgreSQL logical replication source");
+        Ok(Box::new(dbsync_postgres::PostgresReplicationSource::new(
+            config,
+        )?))
+    });
+    #[cfg(feature = "postgres")]
+    plugin_manager.register_sink("postgres", |config| {
+        info!("Creating PostgreSQL sink");
+        Ok(Box::new(dbsync_postgres::PostgresSink::new(config)?))
+    });
+    #[cfg(feature = "sqlite")]
+    plugin_manager.register_source("sqlite", |config| {
+        info!("Creating SQLite source");
+        Ok(Box::new(dbsync_sqlite::SqliteSource::new(config)?))
+    });
+    #[cfg(feature = "sqlite")]
+    plugin_manager.register_sink("sqlite", |config| {
+        info!("Creating SQLite sink");
+        Ok(Box::new(dbsync_sqlite::SqliteSink::new(config)?))
+    });
+    #[cfg(feature = "file")]
+    plugin_manager.register_source("csv", |config| {
+        info!("Creating CSV source");
+        Ok(Box::new(dbsync_file::CsvSource::new(config)?))
+    });
+    #[cfg(feature = "file")]
+    plugin_manager.register_sink("csv", |config| {
+        info!("Creating CSV sink");
+        Ok(Box::new(dbsync_file::CsvSink::new(config)?))
+    });
+    #[cfg(feature = "file")]
+    plugin_manager.register_source("jsonl", |config| {
+        info!("Creating JSON Lines source");
+        Ok(Box::new(dbsync_file::JsonlSource::new(config)?))
+    });
+    #[cfg(feature = "file")]
+    plugin_manager.register_sink("jsonl", |config| {
+        info!("Creating JSON Lines sink");
+        Ok(Box::new(dbsync_file::JsonlSink::new(config)?))
+    });
 
     // 创建并运行任务
     info!("Creating source connector...");
@@ -40,7 +102,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let sink = plugin_manager.create_sink(config.sink)?;
 
     info!("Creating sync job...");
-    let mut job = SyncJob::new(source, vec![], sink);
+    let job_config = JobConfig {
+        job_name: config.job_name.clone(),
+        resume: cli.resume,
+        ..Default::default()
+    };
+    let checkpoint_store = Arc::new(FileCheckpointStore::new(&cli.checkpoint_dir));
+    let mut job = SyncJob::new(source, vec![], sink)
+        .with_config(job_config)
+        .with_checkpoint_store(checkpoint_store);
 
     info!("Starting sync job...");
     job.run().await?;
@@ -0,0 +1,437 @@
+mod common;
+
+use dbsync_core::connector::{
+    BatchField, BatchSchema, Context, DataBatch, Operation, RangeBatch, Record, ShardedSource,
+    Sink, Source,
+};
+use dbsync_core::error::{Error, Result};
+use dbsync_core::job::JobConfig;
+use dbsync_core::{DbsyncType, DbsyncValue, SyncJob};
+use dbsync_file::{CsvSink, CsvSource};
+use dbsync_sqlite::{SqliteSink, SqliteSource};
+use serde_json::json;
+use std::sync::Arc;
+
+// 用 source 顺序读出所有行
+async fn read_rows(source: &mut dyn Source) -> Result<Vec<Vec<DbsyncValue>>> {
+    source.init(&mut Context::new()).await?;
+    let mut rows = Vec::new();
+    while let Some(batch) = source.read_batch(7).await? {
+        rows.extend(batch.records.into_iter().map(|record| record.values));
+    }
+    source.close().await?;
+    Ok(rows)
+}
+
+// 带 BOM 和 \r\n 的文件, 列类型全部由推断得到; 按行号区间并行读取后写入 SQLite
+#[tokio::test]
+async fn test_csv_to_sqlite_job() -> Result<()> {
+    let path = common::file_test_path("job.csv");
+    let mut csv = String::from("\u{feff}id,name,price,ratio,active,born,updated_at,note\r\n");
+    for i in 1..=3000 {
+        let note = match i {
+            i if i % 3 == 0 => String::new(),
+            i if i % 5 == 0 => "\"line\r\nbreak\"".to_string(),
+            i => format!("n{}", i),
+        };
+        csv.push_str(&format!(
+            "{},\"row {}, \"\"quoted\"\"\",{}.{:02},{}e-1,{},2024-01-{:02},2024-01-02 03:04:05.{:03},{}\r\n",
+            i,
+            i,
+            i,
+            i % 100,
+            i,
+            i % 2 == 0,
+            i % 28 + 1,
+            i % 1000,
+            note
+        ));
+    }
+    std::fs::write(&path, csv).unwrap();
+
+    let mut source = CsvSource::new(common::create_csv_source_config(&path))?;
+    let mut ctx = Context::new();
+    source.init(&mut ctx).await?;
+    let types = ctx
+        .schema
+        .unwrap()
+        .columns
+        .into_iter()
+        .map(|column| column.data_type)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            DbsyncType::BigInt,
+            DbsyncType::Text,
+            DbsyncType::Decimal(6, 2),
+            DbsyncType::Double,
+            DbsyncType::Boolean,
+            DbsyncType::Date,
+            DbsyncType::DateTime(3),
+            DbsyncType::Text,
+        ]
+    );
+    let source_rows = read_rows(&mut source).await?;
+    assert_eq!(source_rows.len(), 3000);
+    assert_eq!(
+        source_rows[9],
+        vec![
+            DbsyncValue::Integer(10),
+            DbsyncValue::String("row 10, \"quoted\"".into()),
+            DbsyncValue::Decimal("10.10".into()),
+            DbsyncValue::Float(1.0),
+            DbsyncValue::Boolean(true),
+            DbsyncValue::Date(19733),
+            DbsyncValue::DateTime(1_704_164_645_010_000),
+            DbsyncValue::String("line\r\nbreak".into()),
+        ]
+    );
+    assert_eq!(source_rows[2][7], DbsyncValue::Null);
+
+    let url = common::sqlite_test_url("csv_job");
+    SyncJob::new(
+        Box::new(CsvSource::new(common::create_csv_source_config(&path))?),
+        vec![],
+        Box::new(SqliteSink::new(common::create_sqlite_sink_config(
+            &url, "target",
+        ))?),
+    )
+    .with_config(JobConfig {
+        job_name: "csv_to_sqlite".to_string(),
+        reader_count: 3,
+        range_count: 7,
+        writer_count: 3,
+        ..Default::default()
+    })
+    .run()
+    .await?;
+
+    let mut target_rows = read_rows(&mut SqliteSource::new(
+        common::create_sqlite_source_config(&url, "target"),
+    )?)
+    .await?;
+    target_rows.sort_by_key(|row| match row[0] {
+        DbsyncValue::Integer(id) => id,
+        _ => panic!("unexpected id: {:?}", row[0]),
+    });
+    assert_eq!(target_rows, source_rows);
+    Ok(())
+}
+
+fn typed_batch() -> DataBatch {
+    let schema = Arc::new(BatchSchema::new(vec![
+        BatchField::new("id", DbsyncType::UnsignedBigInt).with_nullable(false),
+        BatchField::new("name", DbsyncType::VarChar(32)),
+        BatchField::new("amount", DbsyncType::Decimal(10, 2)),
+        BatchField::new("payload", DbsyncType::Blob),
+        BatchField::new("doc", DbsyncType::Json),
+        BatchField::new("alarm", DbsyncType::Time(0)),
+        BatchField::new("created_at", DbsyncType::Timestamp(3)),
+    ]));
+    let records = vec![
+        Record::new(vec![
+            DbsyncValue::UInteger(u64::MAX),
+            DbsyncValue::String("名称; \"引号\"".into()),
+            DbsyncValue::Decimal("-12.50".into()),
+            DbsyncValue::Binary(vec![0, 1, 254, 255]),
+            DbsyncValue::Json(json!({"a": [1, "x,y"]})),
+            DbsyncValue::Time(-3_600_000_000),
+            DbsyncValue::Timestamp(1_704_164_645_123_000),
+        ]),
+        Record::new(vec![
+            DbsyncValue::Integer(2),
+            DbsyncValue::String("\\N".into()),
+            DbsyncValue::Null,
+            DbsyncValue::Binary(Vec::new()),
+            DbsyncValue::Null,
+            DbsyncValue::Null,
+            DbsyncValue::Null,
+        ])
+        .with_op(Operation::Upsert),
+        Record::new(vec![
+            DbsyncValue::Integer(3),
+            DbsyncValue::String(String::new()),
+            DbsyncValue::Decimal("0.00".into()),
+            DbsyncValue::Null,
+            DbsyncValue::Json(json!("text")),
+            DbsyncValue::Time(0),
+            DbsyncValue::Timestamp(0),
+        ]),
+    ];
+    DataBatch::new(schema, records)
+}
+
+fn explicit_columns() -> serde_json::Value {
+    json!([
+        {"name": "id", "type": "BIGINT UNSIGNED", "nullable": false},
+        {"name": "name", "type": "VARCHAR(32)"},
+        {"name": "amount", "type": "DECIMAL(10,2)"},
+        {"name": "payload", "type": "BLOB"},
+        {"name": "doc", "type": "JSON"},
+        {"name": "alarm", "type": "TIME"},
+        {"name": "created_at", "type": "TIMESTAMP(3)"}
+    ])
+}
+
+async fn write_batch(config: dbsync_core::connector::ConnectorConfig) -> Result<()> {
+    let mut sink = CsvSink::new(config)?;
+    sink.init(&mut Context::new()).await?;
+    sink.write_batch(typed_batch()).await?;
+    sink.commit().await?;
+    sink.close().await
+}
+
+// 按类型写出的文本能按显式的列类型原样读回, 包括 NULL 与空字符串, 非 UTF-8 编码
+#[tokio::test]
+async fn test_csv_sink_round_trip() -> Result<()> {
+    let path = common::file_test_path("round_trip.csv");
+    write_batch(common::create_csv_sink_config(&path)).await?;
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "id,name,amount,payload,doc,alarm,created_at\n\
+         18446744073709551615,\"名称; \"\"引号\"\"\",-12.50,AAH+/w==,\"{\"\"a\"\":[1,\"\"x,y\"\"]}\",-01:00:00,2024-01-02T03:04:05.123Z\n\
+         2,\\N,,\"\",,,\n\
+         3,\"\",0.00,,\"\"\"text\"\"\",00:00:00,1970-01-01T00:00:00.000Z\n"
+    );
+
+    let mut source_config = common::create_csv_source_config(&path);
+    source_config
+        .properties
+        .insert("columns".to_string(), explicit_columns());
+    let rows = read_rows(&mut CsvSource::new(source_config)?).await?;
+    let expected = typed_batch()
+        .records
+        .into_iter()
+        .map(|record| record.values)
+        .collect::<Vec<_>>();
+    assert_eq!(rows, expected);
+
+    let path = common::file_test_path("round_trip_gbk.csv");
+    let options = [
+        ("delimiter", json!(";")),
+        ("encoding", json!("gbk")),
+        ("null_value", json!("\\N")),
+        ("quoting", json!("non_numeric")),
+    ];
+    let mut sink_config = common::create_csv_sink_config(&path);
+    sink_config
+        .properties
+        .extend(options.iter().map(|(k, v)| (k.to_string(), v.clone())));
+    write_batch(sink_config).await?;
+    let bytes = std::fs::read(&path).unwrap();
+    assert!(String::from_utf8(bytes.clone()).is_err());
+    assert!(bytes.starts_with(b"\"id\";\"name\";"));
+
+    let mut source_config = common::create_csv_source_config(&path);
+    source_config
+        .properties
+        .extend(options[..3].iter().map(|(k, v)| (k.to_string(), v.clone())));
+    source_config
+        .properties
+        .insert("columns".to_string(), explicit_columns());
+    let rows = read_rows(&mut CsvSource::new(source_config)?).await?;
+    assert_eq!(rows, expected);
+    Ok(())
+}
+
+// GBK 汉字的后续字节可以是 | 或 \, 这类字符不能作分隔符; 用逗号时字段不会被拆开
+#[tokio::test]
+async fn test_csv_gbk_multibyte_delimiter() -> Result<()> {
+    let path = common::file_test_path("gbk_delimiter.csv");
+    // 亅 = 0x81 0x7C, 乗 = 0x81 0x5C
+    std::fs::write(&path, b"id,name\n1,\x81\x7c\x81\x5c\n2,a|b\n").unwrap();
+
+    let gbk = |mut config: dbsync_core::connector::ConnectorConfig, delimiter: &str| {
+        config.properties.extend([
+            ("encoding".to_string(), json!("gbk")),
+            ("delimiter".to_string(), json!(delimiter)),
+        ]);
+        config
+    };
+    for result in [
+        CsvSource::new(gbk(common::create_csv_source_config(&path), "|")).map(|_| ()),
+        CsvSink::new(gbk(common::create_csv_sink_config(&path), "|")).map(|_| ()),
+    ] {
+        assert!(
+            matches!(&result, Err(Error::Config(msg)) if msg.contains("GBK")),
+            "{:?}",
+            result.err()
+        );
+    }
+
+    let rows = read_rows(&mut CsvSource::new(gbk(
+        common::create_csv_source_config(&path),
+        ",",
+    ))?)
+    .await?;
+    assert_eq!(
+        rows,
+        vec![
+            vec![DbsyncValue::Integer(1), DbsyncValue::String("亅乗".into())],
+            vec![DbsyncValue::Integer(2), DbsyncValue::String("a|b".into())],
+        ]
+    );
+    Ok(())
+}
+
+// 没有表头的文件按行号切分, 每行恰好读到一次; 字段数不一致时报告行号
+#[tokio::test]
+async fn test_csv_row_ranges() -> Result<()> {
+    let path = common::file_test_path("ranges.csv");
+    let lines = (1..=2500)
+        .map(|i| format!("{}|event-{}", i, i))
+        .collect::<Vec<_>>();
+    // 空行被跳过, 最后一行没有换行符
+    std::fs::write(&path, format!("\n{}\n\n", lines.join("\n")).trim_end()).unwrap();
+
+    let mut config = common::create_csv_source_config(&path);
+    config.properties.extend([
+        ("header".into(), json!(false)),
+        ("delimiter".into(), json!("|")),
+    ]);
+    let mut source = CsvSource::new(config)?;
+    source.init(&mut Context::new()).await?;
+    assert_eq!(source.get_primary_key().await?, vec!["row_number"]);
+    assert_eq!(source.get_total_records().await?, 2500);
+
+    for chunks in [1, 3, 7, 5000] {
+        let mut names = Vec::new();
+        for range in source.split_id_range(chunks).await? {
+            let mut cursor = range.start.clone();
+            while let Some(RangeBatch { batch, last_key }) =
+                source.read_batch_range(cursor.as_ref(), &range.end).await?
+            {
+                assert!(batch.records.len() <= 100);
+                assert_eq!(
+                    batch.schema.names().collect::<Vec<_>>(),
+                    ["column_1", "column_2"]
+                );
+                names.extend(batch.records.into_iter().map(|r| r.values[1].clone()));
+                if last_key == range.end {
+                    break;
+                }
+                cursor = Some(last_key);
+            }
+        }
+        let expected = (1..=2500)
+            .map(|i| DbsyncValue::String(format!("event-{}", i)))
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected, "chunks={}", chunks);
+    }
+
+    std::fs::write(&path, "id,name\n1,a\n2\n").unwrap();
+    let mut source = CsvSource::new(common::create_csv_source_config(&path))?;
+    let err = source.init(&mut Context::new()).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Read(msg) if msg.contains("row 2 has 1 fields, expected 2")),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_csv_sink_save_modes() -> Result<()> {
+    let path = common::file_test_path("save_modes.csv");
+    let sink_config = |save_mode: &str| {
+        let mut config = common::create_csv_sink_config(&path);
+        config
+            .properties
+            .insert("save_mode".to_string(), json!(save_mode));
+        config
+    };
+    let batch = |ids: &[i64]| {
+        let schema = Arc::new(BatchSchema::new(vec![BatchField::new(
+            "id",
+            DbsyncType::BigInt,
+        )]));
+        let records = ids
+            .iter()
+            .map(|id| Record::new(vec![DbsyncValue::Integer(*id)]))
+            .collect();
+        DataBatch::new(schema, records)
+    };
+    let write = |save_mode: &str, ids: &[i64]| {
+        let (config, batch) = (sink_config(save_mode), batch(ids));
+        async move {
+            let mut sink = CsvSink::new(config)?;
+            sink.init(&mut Context::new()).await?;
+            sink.write_batch(batch).await?;
+            sink.close().await
+        }
+    };
+    let content = || std::fs::read_to_string(&path).unwrap();
+
+    write("error_if_exists", &[1, 2]).await?;
+    assert_eq!(content(), "id\n1\n2\n");
+    assert!(write("error_if_exists", &[3]).await.is_err());
+    write("append", &[3]).await?;
+    assert_eq!(content(), "id\n1\n2\n3\n");
+    write("ignore", &[4]).await?;
+    assert_eq!(content(), "id\n1\n2\n3\n");
+    write("overwrite", &[5]).await?;
+    assert_eq!(content(), "id\n5\n");
+
+    // 文件只能追加, 更新和删除报错
+    let mut sink = CsvSink::new(sink_config("append"))?;
+    sink.init(&mut Context::new()).await?;
+    let mut update = batch(&[6]);
+    update.records[0].op = Operation::Delete;
+    assert!(sink.write_batch(update).await.is_err());
+    sink.close().await?;
+    assert_eq!(content(), "id\n5\n");
+
+    // write_batch 返回时数据已在磁盘上, 不依赖 commit 或 close
+    let mut sink = CsvSink::new(sink_config("append"))?;
+    sink.init(&mut Context::new()).await?;
+    sink.write_batch(batch(&[7])).await?;
+    assert_eq!(content(), "id\n5\n7\n");
+    drop(sink);
+    assert_eq!(content(), "id\n5\n7\n");
+    Ok(())
+}
+
+// 恢复按区间读取的任务会重新写入写了一半的区间, 追加到文件会产生重复的行, 因此报错且不修改文件
+#[tokio::test]
+async fn test_csv_rejects_resume_with_partly_written_range() -> Result<()> {
+    let url = common::sqlite_test_url("csv_resume");
+    common::create_resume_source(&url).await;
+    let path = common::file_test_path("resume.csv");
+    let store = common::resume_checkpoint_store("csv_resume");
+    let sink = || CsvSink::new(common::create_csv_sink_config(&path));
+    let lines = || std::fs::read_to_string(&path).unwrap().lines().count();
+
+    let failing = common::FailingTransform::new(4);
+    let err = common::run_resume_job(
+        &url,
+        Box::new(sink()?),
+        &store,
+        vec![Box::new(failing)],
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::Transform(_)),
+        "unexpected error: {}",
+        err
+    );
+    // 表头和前 3 个批次
+    assert_eq!(lines(), 301);
+
+    let err = common::run_resume_job(&url, Box::new(sink()?), &store, vec![], true)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Config(msg) if msg.contains("without resume")),
+        "unexpected error: {}",
+        err
+    );
+    assert_eq!(lines(), 301);
+
+    // 不恢复时重新写入整个文件
+    common::run_resume_job(&url, Box::new(sink()?), &store, vec![], false).await?;
+    assert_eq!(lines(), 401);
+    Ok(())
+}
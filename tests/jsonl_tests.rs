@@ -0,0 +1,358 @@
+mod common;
+
+use dbsync_core::connector::{
+    BatchField, BatchSchema, Context, DataBatch, Operation, Record, ShardedSource, Sink, Source,
+};
+use dbsync_core::error::{Error, Result};
+use dbsync_core::job::JobConfig;
+use dbsync_core::{DbsyncType, DbsyncValue, SyncJob};
+use dbsync_file::{JsonlSink, JsonlSource};
+use dbsync_sqlite::{SqliteSink, SqliteSource};
+use serde_json::json;
+use std::sync::Arc;
+
+// 用 source 顺序读出所有记录
+async fn read_records(source: &mut dyn Source) -> Result<Vec<Record>> {
+    source.init(&mut Context::new()).await?;
+    let mut records = Vec::new();
+    while let Some(batch) = source.read_batch(7).await? {
+        records.extend(batch.records);
+    }
+    source.close().await?;
+    Ok(records)
+}
+
+fn typed_batch() -> DataBatch {
+    let schema = Arc::new(BatchSchema::new(vec![
+        BatchField::new("id", DbsyncType::UnsignedBigInt).with_nullable(false),
+        BatchField::new("name", DbsyncType::VarChar(32)),
+        BatchField::new("amount", DbsyncType::Decimal(22, 2)),
+        BatchField::new("ratio", DbsyncType::Double),
+        BatchField::new("payload", DbsyncType::Blob),
+        BatchField::new("doc", DbsyncType::Json),
+        BatchField::new("created_at", DbsyncType::Timestamp(3)),
+    ]));
+    let records = vec![
+        Record::new(vec![
+            DbsyncValue::UInteger(u64::MAX),
+            DbsyncValue::String("名称 \"引号\"\n".into()),
+            DbsyncValue::Decimal("12345678901234567890.12".into()),
+            DbsyncValue::Float(0.1),
+            DbsyncValue::Binary(vec![0, 1, 254, 255]),
+            DbsyncValue::Json(json!({"a": [1, null]})),
+            DbsyncValue::Timestamp(1_704_164_645_123_000),
+        ]),
+        Record::new(vec![
+            DbsyncValue::Integer(2),
+            DbsyncValue::Null,
+            DbsyncValue::Decimal("-0.50".into()),
+            DbsyncValue::Float(-2.0),
+            DbsyncValue::Null,
+            DbsyncValue::Json(json!("text")),
+            DbsyncValue::Null,
+        ]),
+    ];
+    DataBatch::new(schema, records)
+}
+
+async fn write_batch(
+    config: dbsync_core::connector::ConnectorConfig,
+    batch: DataBatch,
+) -> Result<()> {
+    let mut sink = JsonlSink::new(config)?;
+    sink.init(&mut Context::new()).await?;
+    sink.write_batch(batch).await?;
+    sink.commit().await?;
+    sink.close().await
+}
+
+// 按类型编码的对象能按显式的列类型原样读回, 不配置列时推断出兼容的类型
+#[tokio::test]
+async fn test_jsonl_round_trip() -> Result<()> {
+    let path = common::file_test_path("round_trip.jsonl");
+    write_batch(common::create_jsonl_sink_config(&path), typed_batch()).await?;
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"id\":18446744073709551615,\"name\":\"名称 \\\"引号\\\"\\n\",\
+         \"amount\":\"12345678901234567890.12\",\"ratio\":0.1,\"payload\":\"AAH+/w==\",\
+         \"doc\":{\"a\":[1,null]},\"created_at\":\"2024-01-02T03:04:05.123Z\"}\n\
+         {\"id\":2,\"name\":null,\"amount\":\"-0.50\",\"ratio\":-2.0,\"payload\":null,\
+         \"doc\":\"text\",\"created_at\":null}\n"
+    );
+
+    let mut config = common::create_jsonl_source_config(&path);
+    config.properties.insert(
+        "columns".to_string(),
+        json!([
+            {"name": "id", "type": "BIGINT UNSIGNED", "nullable": false},
+            {"name": "name", "type": "VARCHAR(32)"},
+            {"name": "amount", "type": "DECIMAL(22,2)"},
+            {"name": "ratio", "type": "DOUBLE"},
+            {"name": "payload", "type": "BLOB"},
+            {"name": "doc", "type": "JSON"},
+            {"name": "created_at", "type": "TIMESTAMP(3)"}
+        ]),
+    );
+    let values = read_records(&mut JsonlSource::new(config)?)
+        .await?
+        .into_iter()
+        .map(|record| record.values)
+        .collect::<Vec<_>>();
+    let expected = typed_batch()
+        .records
+        .into_iter()
+        .map(|record| record.values)
+        .collect::<Vec<_>>();
+    assert_eq!(values, expected);
+
+    let mut source = JsonlSource::new(common::create_jsonl_source_config(&path))?;
+    let mut ctx = Context::new();
+    source.init(&mut ctx).await?;
+    let schema = ctx.schema.unwrap();
+    assert_eq!(
+        schema.name,
+        format!("dbsync_{}_round_trip", std::process::id())
+    );
+    let columns = schema
+        .columns
+        .into_iter()
+        .map(|column| (column.name, column.data_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        columns,
+        vec![
+            ("id".to_string(), DbsyncType::UnsignedBigInt),
+            ("name".to_string(), DbsyncType::Text),
+            ("amount".to_string(), DbsyncType::Decimal(22, 2)),
+            ("ratio".to_string(), DbsyncType::Double),
+            ("payload".to_string(), DbsyncType::Text),
+            ("doc".to_string(), DbsyncType::Json),
+            ("created_at".to_string(), DbsyncType::Timestamp(3)),
+        ]
+    );
+    Ok(())
+}
+
+// 带变更类型的文件按顺序回放到 SQLite, 更新和删除按主键生效
+#[tokio::test]
+async fn test_jsonl_replay_into_sqlite() -> Result<()> {
+    let path = common::file_test_path("changes.jsonl");
+    let mut lines = (1..=300)
+        .map(|i| json!({"id": i, "name": format!("user-{}", i), "score": "1.5"}).to_string())
+        .collect::<Vec<_>>();
+    lines.push(json!({"id": 7, "name": "renamed", "score": "2.5", "op": "update"}).to_string());
+    lines.push(json!({"id": 8, "op": "delete"}).to_string());
+    lines.push(json!({"id": 301, "name": "late", "extra": true, "op": "upsert"}).to_string());
+    // 空行被跳过
+    std::fs::write(&path, format!("\n{}\n\n", lines.join("\r\n"))).unwrap();
+
+    let source_config = || {
+        let mut config = common::create_jsonl_source_config(&path);
+        config.properties.extend([
+            ("primary_key".to_string(), json!(["id"])),
+            ("op_field".to_string(), json!("op")),
+            // 推断之后才出现的字段被忽略
+            ("infer_rows".to_string(), json!(300)),
+        ]);
+        config
+    };
+    let mut source = JsonlSource::new(source_config())?;
+    source.init(&mut Context::new()).await?;
+    assert_eq!(source.get_total_records().await?, 303);
+    let schema = source.get_schema().await?;
+    assert_eq!(schema.primary_key, vec!["id"]);
+    assert_eq!(
+        schema
+            .columns
+            .iter()
+            .map(|c| &c.data_type)
+            .collect::<Vec<_>>(),
+        [
+            &DbsyncType::BigInt,
+            &DbsyncType::Text,
+            &DbsyncType::Decimal(2, 1)
+        ]
+    );
+
+    let url = common::sqlite_test_url("jsonl_replay");
+    SyncJob::new(
+        Box::new(JsonlSource::new(source_config())?),
+        vec![],
+        Box::new(SqliteSink::new(common::create_sqlite_sink_config(
+            &url, "target",
+        ))?),
+    )
+    .with_config(JobConfig {
+        job_name: "jsonl_to_sqlite".to_string(),
+        reader_count: 1,
+        range_count: 1,
+        writer_count: 1,
+        ..Default::default()
+    })
+    .run()
+    .await?;
+
+    let mut rows = read_records(&mut SqliteSource::new(
+        common::create_sqlite_source_config(&url, "target"),
+    )?)
+    .await?
+    .into_iter()
+    .map(|record| record.values)
+    .collect::<Vec<_>>();
+    rows.sort_by_key(|row| match row[0] {
+        DbsyncValue::Integer(id) => id,
+        _ => panic!("unexpected id: {:?}", row[0]),
+    });
+    assert_eq!(rows.len(), 300);
+    assert_eq!(
+        rows[6],
+        vec![
+            DbsyncValue::Integer(7),
+            DbsyncValue::String("renamed".into()),
+            DbsyncValue::Decimal("2.5".into()),
+        ]
+    );
+    assert_eq!(rows[7][0], DbsyncValue::Integer(9));
+    assert_eq!(
+        rows[299],
+        vec![
+            DbsyncValue::Integer(301),
+            DbsyncValue::String("late".into()),
+            DbsyncValue::Null,
+        ]
+    );
+    Ok(())
+}
+
+// 设置 op_field 时写出变更类型并能读回; 不设置时更新和删除报错
+#[tokio::test]
+async fn test_jsonl_sink_operations() -> Result<()> {
+    let path = common::file_test_path("operations.jsonl");
+    let batch = || {
+        let schema = Arc::new(BatchSchema::new(vec![BatchField::new(
+            "id",
+            DbsyncType::BigInt,
+        )]));
+        let records = [Operation::Insert, Operation::Update, Operation::Delete]
+            .into_iter()
+            .enumerate()
+            .map(|(i, op)| Record::new(vec![DbsyncValue::Integer(i as i64 + 1)]).with_op(op))
+            .collect();
+        DataBatch::new(schema, records)
+    };
+
+    let mut config = common::create_jsonl_sink_config(&path);
+    config
+        .properties
+        .insert("op_field".to_string(), json!("_op"));
+    write_batch(config, batch()).await?;
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "{\"id\":1,\"_op\":\"insert\"}\n{\"id\":2,\"_op\":\"update\"}\n{\"id\":3,\"_op\":\"delete\"}\n"
+    );
+
+    let mut config = common::create_jsonl_source_config(&path);
+    config
+        .properties
+        .insert("op_field".to_string(), json!("_op"));
+    let ops = read_records(&mut JsonlSource::new(config)?)
+        .await?
+        .into_iter()
+        .map(|record| (record.values, record.op))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ops,
+        vec![
+            (vec![DbsyncValue::Integer(1)], Operation::Insert),
+            (vec![DbsyncValue::Integer(2)], Operation::Update),
+            (vec![DbsyncValue::Integer(3)], Operation::Delete),
+        ]
+    );
+
+    assert!(
+        write_batch(common::create_jsonl_sink_config(&path), batch())
+            .await
+            .is_err()
+    );
+
+    std::fs::write(&path, "{\"id\":1}\n[1, 2]\n").unwrap();
+    let mut source = JsonlSource::new(common::create_jsonl_source_config(&path))?;
+    let err = source.init(&mut Context::new()).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Read(msg) if msg.contains("row 2")),
+        "{}",
+        err
+    );
+    Ok(())
+}
+
+// write_batch 返回时数据已在磁盘上, 没有 commit 和 close 就丢弃 sink 也不会丢失
+#[tokio::test]
+async fn test_jsonl_write_batch_is_durable() -> Result<()> {
+    let path = common::file_test_path("durable.jsonl");
+    let mut sink = JsonlSink::new(common::create_jsonl_sink_config(&path))?;
+    sink.init(&mut Context::new()).await?;
+    sink.write_batch(typed_batch()).await?;
+    drop(sink);
+
+    let mut config = common::create_jsonl_source_config(&path);
+    config.properties.insert(
+        "columns".to_string(),
+        json!([{"name": "id", "type": "BIGINT UNSIGNED"}]),
+    );
+    let ids = read_records(&mut JsonlSource::new(config)?)
+        .await?
+        .into_iter()
+        .map(|record| record.values)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            vec![DbsyncValue::UInteger(u64::MAX)],
+            vec![DbsyncValue::Integer(2)]
+        ]
+    );
+    Ok(())
+}
+
+// 与 CSV 相同, 恢复时写了一半的区间会重复写入, JSON Lines sink 报错且不修改文件
+#[tokio::test]
+async fn test_jsonl_rejects_resume_with_partly_written_range() -> Result<()> {
+    let url = common::sqlite_test_url("jsonl_resume");
+    common::create_resume_source(&url).await;
+    let path = common::file_test_path("resume.jsonl");
+    let store = common::resume_checkpoint_store("jsonl_resume");
+    let sink = || JsonlSink::new(common::create_jsonl_sink_config(&path));
+    let lines = || std::fs::read_to_string(&path).unwrap().lines().count();
+
+    let failing = common::FailingTransform::new(4);
+    let err = common::run_resume_job(
+        &url,
+        Box::new(sink()?),
+        &store,
+        vec![Box::new(failing)],
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::Transform(_)),
+        "unexpected error: {}",
+        err
+    );
+    assert_eq!(lines(), 300);
+
+    let err = common::run_resume_job(&url, Box::new(sink()?), &store, vec![], true)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Config(msg) if msg.contains("without resume")),
+        "unexpected error: {}",
+        err
+    );
+    assert_eq!(lines(), 300);
+
+    common::run_resume_job(&url, Box::new(sink()?), &store, vec![], false).await?;
+    assert_eq!(lines(), 400);
+    Ok(())
+}
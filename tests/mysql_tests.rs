@@ -1,15 +1,27 @@
 mod common;
 
-use dbsync_core::{connector::DataBatch, Result};
-use dbsync_mysql::{MySQLSink, MySQLSource};
-use sqlx::mysql::MySqlPoolOptions;
+use dbsync_core::checkpoint::FileCheckpointStore;
+use dbsync_core::connector::{
+    ConnectorConfig, Context, DataBatch, KeyValue, RangeBatch, Record, ShardedSink, ShardedSource,
+    Sink, Source,
+};
+use dbsync_core::error::{Error, Result};
+use dbsync_core::job::JobConfig;
+use dbsync_core::{ColumnSchema, DbsyncType, DbsyncValue, SyncJob, TableSchema};
+use dbsync_mysql::{MySQLBinlogSource, MySQLSink, MySQLSource};
+use serde_json::json;
+use sqlx::mysql::{MySqlPool, MySqlPoolOptions};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
 async fn test_mysql_sync() -> Result<()> {
-    let url = common::setup_mysql_env();
+    let url = common::mysql_url();
     let pool = MySqlPoolOptions::new()
-        .connect_timeout(Duration::from_secs(5))
+        .acquire_timeout(Duration::from_secs(5))
         .connect(&url)
         .await
         .expect("Failed to connect to MySQL");
@@ -51,17 +63,24 @@ async fn test_mysql_sync() -> Result<()> {
     // 创建并初始化 source
     let source_config = common::create_mysql_source_config(&url, "source_table");
     let mut source = MySQLSource::new(source_config)?;
-    source.init().await?;
+    let mut ctx = Context::new();
+    source.init(&mut ctx).await?;
 
     // 创建并初始化 sink
     let sink_config = common::create_mysql_sink_config(&url, "target_table");
     let mut sink = MySQLSink::new(sink_config)?;
-    sink.init().await?;
+    sink.init(&mut ctx).await?;
 
-    // 执行同步
-    while let Some(batch) = source.read_batch(100).await? {
+    // 执行同步, 每页 2 行, 3 行数据需要读两页
+    let mut pages = 0;
+    let mut read = 0;
+    while let Some(batch) = source.read_batch(2).await? {
+        pages += 1;
+        read += batch.records.len();
         sink.write_batch(batch).await?;
     }
+    assert_eq!(pages, 2);
+    assert_eq!(read, 3);
 
     // 验证同步结果
     let rows: Vec<(i32, String, i32)> =
@@ -87,3 +106,1191 @@ async fn test_mysql_sync() -> Result<()> {
 
     Ok(())
 }
+
+// 将主键范围切分为 chunks 个区间, 逐个区间用 keyset 分页读取, 返回按读取顺序排列的主键
+async fn read_all_keys(
+    url: &str,
+    table: &str,
+    batch_size: usize,
+    chunks: usize,
+) -> Result<Vec<KeyValue>> {
+    let mut config = common::create_mysql_source_config(url, table);
+    config
+        .properties
+        .insert("batch_size".to_string(), json!(batch_size));
+    let mut source = MySQLSource::new(config)?;
+    source.init(&mut Context::new()).await?;
+    let key = source.get_primary_key().await?;
+
+    let mut keys = Vec::new();
+    for range in source.split_id_range(chunks).await? {
+        let mut cursor = range.start.clone();
+        while let Some(RangeBatch { batch, last_key }) =
+            source.read_batch_range(cursor.as_ref(), &range.end).await?
+        {
+            assert!(batch.records.len() <= batch_size);
+            for row in 0..batch.records.len() {
+                keys.push(KeyValue(
+                    key.iter()
+                        .map(|c| batch.value(row, c).unwrap().clone())
+                        .collect(),
+                ));
+            }
+            if last_key == range.end {
+                break;
+            }
+            cursor = Some(last_key);
+        }
+    }
+
+    source.close().await?;
+    Ok(keys)
+}
+
+async fn read_all_ids(
+    url: &str,
+    table: &str,
+    batch_size: usize,
+    chunks: usize,
+) -> Result<Vec<i64>> {
+    Ok(read_all_keys(url, table, batch_size, chunks)
+        .await?
+        .into_iter()
+        .map(|key| match key.0.as_slice() {
+            [DbsyncValue::Integer(id)] => *id,
+            other => panic!("unexpected id value: {:?}", other),
+        })
+        .collect())
+}
+
+// 不依赖 DbsyncValue 的排序, 用 Debug 输出比较两组主键是否一一对应
+fn sorted_keys(keys: &[KeyValue]) -> Vec<String> {
+    let mut keys = keys.iter().map(|k| format!("{:?}", k)).collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+async fn connect(url: &str) -> MySqlPool {
+    MySqlPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(url)
+        .await
+        .expect("Failed to connect to MySQL")
+}
+
+async fn execute(pool: &MySqlPool, sql: &str) {
+    sqlx::query(sql).execute(pool).await.unwrap();
+}
+
+async fn create_id_table(pool: &MySqlPool, table: &str, ids: &[i64]) {
+    sqlx::query(&format!("DROP TABLE IF EXISTS {}", table))
+        .execute(pool)
+        .await
+        .unwrap();
+    sqlx::query(&format!(
+        "CREATE TABLE {} (id BIGINT PRIMARY KEY, name VARCHAR(255))",
+        table
+    ))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    for chunk in ids.chunks(500) {
+        let values = chunk
+            .iter()
+            .map(|id| format!("({}, 'row-{}')", id, id))
+            .collect::<Vec<_>>()
+            .join(",");
+        sqlx::query(&format!(
+            "INSERT INTO {} (id, name) VALUES {}",
+            table, values
+        ))
+        .execute(pool)
+        .await
+        .unwrap();
+    }
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_keyset_reads_every_row_once() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = MySqlPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(&url)
+        .await
+        .expect("Failed to connect to MySQL");
+
+    // 连续的主键
+    let dense = (1..=2_000).collect::<Vec<i64>>();
+    // 稀疏且带有大段空洞的主键, 空洞跨度远大于批次大小
+    let gapped = (0..500)
+        .map(|i: i64| if i < 250 { i * 7 } else { 1_000_000 + i * i })
+        .chain([i64::MAX - 1, i64::MAX])
+        .collect::<Vec<i64>>();
+
+    for (table, ids) in [("keyset_dense", &dense), ("keyset_gapped", &gapped)] {
+        create_id_table(&pool, table, ids).await;
+
+        for batch_size in [1, 3, 100, 5_000] {
+            for chunks in [1, 4] {
+                let read = read_all_ids(&url, table, batch_size, chunks).await?;
+                assert_eq!(&read, ids, "table {} batch_size {}", table, batch_size);
+            }
+        }
+
+        sqlx::query(&format!("DROP TABLE {}", table))
+            .execute(&pool)
+            .await
+            .unwrap();
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_keyset_empty_table() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = MySqlPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(&url)
+        .await
+        .expect("Failed to connect to MySQL");
+
+    create_id_table(&pool, "keyset_empty", &[]).await;
+    assert!(read_all_ids(&url, "keyset_empty", 10, 4).await?.is_empty());
+
+    sqlx::query("DROP TABLE keyset_empty")
+        .execute(&pool)
+        .await
+        .unwrap();
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_string_primary_key() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+
+    execute(&pool, "DROP TABLE IF EXISTS keyset_uuid").await;
+    execute(
+        &pool,
+        "CREATE TABLE keyset_uuid (id CHAR(36) PRIMARY KEY, payload INT)",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO keyset_uuid (id, payload)
+         WITH RECURSIVE seq (n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM seq WHERE n < 500)
+         SELECT UUID(), n FROM seq",
+    )
+    .await;
+
+    let expected = sqlx::query_as::<_, (String,)>("SELECT id FROM keyset_uuid")
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(id,)| KeyValue(vec![DbsyncValue::String(id)]))
+        .collect::<Vec<_>>();
+
+    for chunks in [1, 3, 7] {
+        let keys = read_all_keys(&url, "keyset_uuid", 37, chunks).await?;
+        assert_eq!(keys.len(), expected.len(), "chunks {}", chunks);
+        assert_eq!(sorted_keys(&keys), sorted_keys(&expected));
+    }
+
+    execute(&pool, "DROP TABLE keyset_uuid").await;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_composite_primary_key() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+
+    execute(&pool, "DROP TABLE IF EXISTS keyset_composite").await;
+    execute(
+        &pool,
+        "CREATE TABLE keyset_composite (
+            tenant_id INT NOT NULL,
+            order_id BIGINT NOT NULL,
+            amount INT,
+            PRIMARY KEY (tenant_id, order_id)
+        )",
+    )
+    .await;
+
+    // 每个租户的订单号互相重叠且稀疏
+    let mut expected = Vec::new();
+    let mut values = Vec::new();
+    for tenant_id in 1..=6i64 {
+        for order_id in (0..100i64).map(|i| i * tenant_id * 3) {
+            expected.push(KeyValue(vec![
+                DbsyncValue::Integer(tenant_id),
+                DbsyncValue::Integer(order_id),
+            ]));
+            values.push(format!("({}, {}, 1)", tenant_id, order_id));
+        }
+    }
+    execute(
+        &pool,
+        &format!(
+            "INSERT INTO keyset_composite (tenant_id, order_id, amount) VALUES {}",
+            values.join(",")
+        ),
+    )
+    .await;
+
+    for (batch_size, chunks) in [(1, 1), (7, 4), (1000, 5)] {
+        // 复合主键按 (tenant_id, order_id) 字典序读取
+        let keys = read_all_keys(&url, "keyset_composite", batch_size, chunks).await?;
+        assert_eq!(
+            keys, expected,
+            "batch_size {} chunks {}",
+            batch_size, chunks
+        );
+    }
+
+    // 600 行切成 5 段, 每段 120 行
+    let mut source =
+        MySQLSource::new(common::create_mysql_source_config(&url, "keyset_composite"))?;
+    source.init(&mut Context::new()).await?;
+    let ranges = source.split_id_range(5).await?;
+    assert_eq!(ranges.len(), 5);
+    for (range, index) in ranges.iter().zip([119, 239, 359, 479, 599]) {
+        assert_eq!(range.end, expected[index]);
+    }
+    source.close().await?;
+
+    execute(&pool, "DROP TABLE keyset_composite").await;
+    Ok(())
+}
+
+// 表名和主键列都是保留字, 每个查询都要加引号
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_reserved_word_key() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+
+    execute(&pool, "DROP TABLE IF EXISTS `select`").await;
+    execute(
+        &pool,
+        "CREATE TABLE `select` (
+            `order` INT NOT NULL,
+            `group` INT NOT NULL,
+            `from` VARCHAR(20),
+            PRIMARY KEY (`order`, `group`)
+        )",
+    )
+    .await;
+
+    let mut expected = Vec::new();
+    let mut values = Vec::new();
+    for order in 1..=20i64 {
+        for group in 1..=5i64 {
+            expected.push(KeyValue(vec![
+                DbsyncValue::Integer(order),
+                DbsyncValue::Integer(group),
+            ]));
+            values.push(format!("({}, {}, 'x')", order, group));
+        }
+    }
+    execute(
+        &pool,
+        &format!(
+            "INSERT INTO `select` (`order`, `group`, `from`) VALUES {}",
+            values.join(",")
+        ),
+    )
+    .await;
+
+    for (batch_size, chunks) in [(1, 1), (7, 4)] {
+        let keys = read_all_keys(&url, "select", batch_size, chunks).await?;
+        assert_eq!(
+            keys, expected,
+            "batch_size {} chunks {}",
+            batch_size, chunks
+        );
+    }
+
+    execute(&pool, "DROP TABLE `select`").await;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_unsigned_bigint_primary_key() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+
+    execute(&pool, "DROP TABLE IF EXISTS keyset_unsigned").await;
+    execute(
+        &pool,
+        "CREATE TABLE keyset_unsigned (id BIGINT UNSIGNED PRIMARY KEY, name VARCHAR(32))",
+    )
+    .await;
+
+    // 包含超出 i64 范围的主键
+    let ids = [
+        0u64,
+        1,
+        42,
+        i64::MAX as u64,
+        i64::MAX as u64 + 1,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+    let values = ids
+        .iter()
+        .map(|id| format!("({}, 'row')", id))
+        .collect::<Vec<_>>()
+        .join(",");
+    execute(
+        &pool,
+        &format!("INSERT INTO keyset_unsigned (id, name) VALUES {}", values),
+    )
+    .await;
+
+    let expected = ids
+        .iter()
+        .map(|id| KeyValue(vec![DbsyncValue::UInteger(*id)]))
+        .collect::<Vec<_>>();
+    for chunks in [1, 3, 16] {
+        let keys = read_all_keys(&url, "keyset_unsigned", 2, chunks).await?;
+        assert_eq!(keys, expected, "chunks {}", chunks);
+    }
+
+    execute(&pool, "DROP TABLE keyset_unsigned").await;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_table_without_primary_key() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+
+    execute(&pool, "DROP TABLE IF EXISTS keyset_no_pk").await;
+    execute(&pool, "CREATE TABLE keyset_no_pk (name VARCHAR(32))").await;
+    execute(&pool, "INSERT INTO keyset_no_pk (name) VALUES ('a'), ('b')").await;
+
+    let config = common::create_mysql_source_config(&url, "keyset_no_pk");
+    let mut source = MySQLSource::new(config)?;
+    source.init(&mut Context::new()).await?;
+
+    let err = source.split_id_range(4).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Config(msg) if msg.contains("no primary key")),
+        "unexpected error: {}",
+        err
+    );
+
+    source.close().await?;
+    execute(&pool, "DROP TABLE keyset_no_pk").await;
+    Ok(())
+}
+
+// 用额外的 sink 配置初始化 sink, 写入一批 (id, name) 行
+async fn write_rows(
+    url: &str,
+    table: &str,
+    properties: serde_json::Value,
+    rows: &[(i64, &str)],
+) -> Result<()> {
+    let mut config = common::create_mysql_sink_config(url, table);
+    if let serde_json::Value::Object(properties) = properties {
+        config.properties.extend(properties);
+    }
+    let mut sink = MySQLSink::new(config)?;
+
+    let mut ctx = Context::new();
+    let table_schema = TableSchema::new("source")
+        .with_column(ColumnSchema::new("id", DbsyncType::BigInt).with_nullable(false))
+        .with_column(ColumnSchema::new("name", DbsyncType::VarChar(255)))
+        .with_primary_key(vec!["id".to_string()]);
+    ctx.set_schema(table_schema.clone());
+    sink.init(&mut ctx).await?;
+
+    let schema = table_schema.batch_schema();
+    let records = rows
+        .iter()
+        .map(|(id, name)| {
+            Record::new(vec![
+                DbsyncValue::Integer(*id),
+                DbsyncValue::String(name.to_string()),
+            ])
+        })
+        .collect();
+    sink.write_batch(DataBatch::new(Arc::new(schema), records))
+        .await?;
+    sink.close().await
+}
+
+// 目标表按 source 的表结构建表, 列属性, 主键, 索引和注释都应保留
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_table_schema_round_trip() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS schema_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS schema_target").await;
+    // 列名与表名相同, 旧的按文本替换表名的做法会把它改坏
+    execute(
+        &pool,
+        "CREATE TABLE schema_source (
+            id BIGINT NOT NULL AUTO_INCREMENT,
+            schema_source VARCHAR(64) NOT NULL DEFAULT 'it''s' COMMENT '名称',
+            score INT NULL,
+            created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+            updated_at DATETIME(3) NULL DEFAULT CURRENT_TIMESTAMP(3) ON UPDATE CURRENT_TIMESTAMP(3),
+            uid VARCHAR(36) NOT NULL DEFAULT (uuid()),
+            PRIMARY KEY (id),
+            UNIQUE KEY uk_name (schema_source),
+            KEY idx_score_created (score DESC, created_at),
+            KEY idx_name_prefix (schema_source(8))
+        ) COMMENT='测试表'",
+    )
+    .await;
+
+    let mut ctx = Context::new();
+    let mut source = MySQLSource::new(common::create_mysql_source_config(&url, "schema_source"))?;
+    source.init(&mut ctx).await?;
+    let source_schema = ctx.schema.clone().expect("source sets the table schema");
+    assert_eq!(source_schema.primary_key, vec!["id".to_string()]);
+    assert_eq!(
+        source_schema
+            .column("updated_at")
+            .unwrap()
+            .on_update
+            .as_deref(),
+        Some("CURRENT_TIMESTAMP(3)")
+    );
+    assert_eq!(source_schema.indexes.len(), 3);
+    let index = |name: &str| {
+        source_schema
+            .indexes
+            .iter()
+            .find(|i| i.name == name)
+            .expect("index is loaded")
+    };
+    assert!(index("idx_score_created").columns[0].descending);
+    assert!(!index("idx_score_created").columns[1].descending);
+    assert_eq!(index("idx_name_prefix").columns[0].prefix_length, Some(8));
+    assert_eq!(index("uk_name").columns[0].prefix_length, None);
+    assert_eq!(source_schema.comment.as_deref(), Some("测试表"));
+
+    let mut sink = MySQLSink::new(common::create_mysql_sink_config(&url, "schema_target"))?;
+    sink.init(&mut ctx).await?;
+    let target_schema = sink.get_target_schema().await?;
+    assert_eq!(target_schema.name, "schema_target");
+    assert_eq!(target_schema.columns, source_schema.columns);
+    assert_eq!(target_schema.primary_key, source_schema.primary_key);
+    assert_eq!(target_schema.indexes, source_schema.indexes);
+    assert_eq!(target_schema.comment, source_schema.comment);
+
+    sink.close().await?;
+    source.close().await?;
+    execute(&pool, "DROP TABLE schema_source").await;
+    execute(&pool, "DROP TABLE schema_target").await;
+    Ok(())
+}
+
+async fn column_types(pool: &MySqlPool, table: &str) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT CAST(COLUMN_TYPE AS CHAR) FROM INFORMATION_SCHEMA.COLUMNS \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? ORDER BY ORDINAL_POSITION",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+// 目标表的 DECIMAL 精度, 字符串长度和二进制长度与源表一致, 大精度的值不被截断或舍入
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_exact_type_parameters() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS precise_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS precise_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE precise_source (
+            id BIGINT PRIMARY KEY,
+            amount DECIMAL(38,10),
+            note VARCHAR(1000),
+            code CHAR(3),
+            digest BINARY(16),
+            flag TINYINT(1)
+        )",
+    )
+    .await;
+    let amount = "1234567890123456789012345678.0123456789";
+    let note = "x".repeat(1000);
+    // TINYINT(1) 默认按整数读取, 0 和 1 以外的值原样写入
+    sqlx::query("INSERT INTO precise_source VALUES (1, ?, ?, 'abc', NULL, 2)")
+        .bind(amount)
+        .bind(&note)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let mut ctx = Context::new();
+    let mut source = MySQLSource::new(common::create_mysql_source_config(&url, "precise_source"))?;
+    source.init(&mut ctx).await?;
+    let mut sink = MySQLSink::new(common::create_mysql_sink_config(&url, "precise_target"))?;
+    sink.init(&mut ctx).await?;
+    while let Some(batch) = source.read_batch(10).await? {
+        sink.write_batch(batch).await?;
+    }
+    sink.close().await?;
+    source.close().await?;
+
+    assert_eq!(
+        column_types(&pool, "precise_target").await,
+        vec![
+            "bigint",
+            "decimal(38,10)",
+            "varchar(1000)",
+            "char(3)",
+            "binary(16)",
+            "tinyint"
+        ]
+    );
+    let (target_amount, target_note, target_flag): (String, String, i8) =
+        sqlx::query_as("SELECT CAST(amount AS CHAR), note, flag FROM precise_target WHERE id = 1")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(target_amount, amount);
+    assert_eq!(target_note, note);
+    assert_eq!(target_flag, 2);
+
+    // 显式开启后 TINYINT(1) 读作布尔值, 无法表示的值报错
+    let mut config = common::create_mysql_source_config(&url, "precise_source");
+    config
+        .properties
+        .insert("tinyint1_as_boolean".to_string(), json!(true));
+    let mut source = MySQLSource::new(config.clone())?;
+    source.init(&mut Context::new()).await?;
+    let err = source.read_batch(10).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Type(msg) if msg.contains("flag")),
+        "{}",
+        err
+    );
+    source.close().await?;
+
+    execute(&pool, "UPDATE precise_source SET flag = 1").await;
+    let mut source = MySQLSource::new(config)?;
+    source.init(&mut Context::new()).await?;
+    let batch = source.read_batch(10).await?.expect("one row");
+    assert_eq!(batch.value(0, "flag"), Some(&DbsyncValue::Boolean(true)));
+    source.close().await?;
+
+    execute(&pool, "DROP TABLE precise_source").await;
+    execute(&pool, "DROP TABLE precise_target").await;
+    Ok(())
+}
+
+// MySQL 8 的各种列类型都能读出, 并在目标表上按原类型建表
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_all_column_types() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS types_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS types_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE types_source (
+            id BIGINT UNSIGNED PRIMARY KEY,
+            m MEDIUMINT,
+            tu TINYINT UNSIGNED,
+            b BIT(12),
+            y YEAR,
+            e ENUM('small','it''s'),
+            s SET('a','b','c'),
+            tt TINYTEXT,
+            lt LONGTEXT,
+            mb MEDIUMBLOB,
+            fb BINARY(4),
+            g POINT,
+            j JSON
+        )",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO types_source VALUES (18446744073709551615, -8388608, 255, b'101010111100', \
+         2024, 'it''s', 'a,c', 'tiny', 'long', x'00ff', x'0102', ST_GeomFromText('POINT(1 2)'), \
+         '{\"a\": [1, \"it''s\"]}')",
+    )
+    .await;
+
+    let mut ctx = Context::new();
+    let mut source = MySQLSource::new(common::create_mysql_source_config(&url, "types_source"))?;
+    source.init(&mut ctx).await?;
+    let batch = source.read_batch(10).await?.expect("one row");
+    let values = &batch.records[0].values;
+    assert_eq!(values[0], DbsyncValue::UInteger(u64::MAX));
+    assert_eq!(values[1], DbsyncValue::Integer(-8_388_608));
+    assert_eq!(values[2], DbsyncValue::UInteger(255));
+    assert_eq!(values[3], DbsyncValue::UInteger(0b1010_1011_1100));
+    assert_eq!(values[4], DbsyncValue::Integer(2024));
+    assert_eq!(values[5], DbsyncValue::String("it's".to_string()));
+    assert_eq!(values[6], DbsyncValue::String("a,c".to_string()));
+    assert_eq!(values[7], DbsyncValue::String("tiny".to_string()));
+    assert_eq!(values[8], DbsyncValue::String("long".to_string()));
+    assert_eq!(values[9], DbsyncValue::Binary(vec![0x00, 0xff]));
+    // BINARY 按定长补 0
+    assert_eq!(values[10], DbsyncValue::Binary(vec![1, 2, 0, 0]));
+    // SRID 0 + WKB 点 (1 2)
+    let DbsyncValue::Binary(point) = &values[11] else {
+        panic!("point is not binary: {:?}", values[11]);
+    };
+    assert_eq!(point.len(), 25);
+    assert_eq!(&point[..4], &[0, 0, 0, 0]);
+    assert_eq!(values[12], DbsyncValue::Json(json!({"a": [1, "it's"]})));
+    assert_eq!(values[12].json_pointer("/a/1"), Some(&json!("it's")));
+
+    // 按原生类型写入, 二进制, BIT, 空间和 JSON 列的值与源表逐字节相同
+    let mut sink = MySQLSink::new(common::create_mysql_sink_config(&url, "types_target"))?;
+    sink.init(&mut ctx).await?;
+    sink.write_batch(batch).await?;
+    sink.close().await?;
+    source.close().await?;
+    assert_eq!(
+        column_types(&pool, "types_target").await,
+        column_types(&pool, "types_source").await
+    );
+    let mut rows = Vec::new();
+    for table in ["types_source", "types_target"] {
+        let row: (String, String, String, String, String) = sqlx::query_as(&format!(
+            "SELECT CAST(id AS CHAR), HEX(b), HEX(CONCAT(mb, fb)), HEX(g), CAST(j AS CHAR) \
+             FROM {}",
+            table
+        ))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        rows.push(row);
+    }
+    assert_eq!(rows[0], rows[1]);
+
+    execute(&pool, "DROP TABLE types_source").await;
+    execute(&pool, "DROP TABLE types_target").await;
+    Ok(())
+}
+
+// 时间类型保留微秒, TIMESTAMP 按各自的会话时区换算后仍是同一时刻
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_temporal_types() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS temporal_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS temporal_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE temporal_source (
+            id BIGINT PRIMARY KEY,
+            d DATE,
+            t TIME(6),
+            dt DATETIME(6),
+            ts TIMESTAMP(6) NULL,
+            ts0 TIMESTAMP NULL
+        )",
+    )
+    .await;
+    // connect 的会话时区为 +00:00
+    execute(
+        &pool,
+        "INSERT INTO temporal_source VALUES (1, '1969-12-31', '-838:59:59.000001', \
+         '2024-02-29 23:59:59.123456', '2024-01-01 00:00:00.000001', '2038-01-19 03:14:07')",
+    )
+    .await;
+
+    let mut ctx = Context::new();
+    let mut source_config = common::create_mysql_source_config(&url, "temporal_source");
+    source_config
+        .properties
+        .insert("time_zone".to_string(), json!("+08:00"));
+    let mut source = MySQLSource::new(source_config)?;
+    source.init(&mut ctx).await?;
+    let batch = source.read_batch(10).await?.expect("one row");
+    let values = &batch.records[0].values;
+    assert_eq!(values[1], DbsyncValue::Date(-1));
+    assert_eq!(
+        values[2],
+        DbsyncValue::Time(-((838 * 3600 + 59 * 60 + 59) * 1_000_000 + 1))
+    );
+    assert_eq!(values[3], DbsyncValue::DateTime(1_709_251_199_123_456));
+    assert_eq!(values[4], DbsyncValue::Timestamp(1_704_067_200_000_001));
+    assert_eq!(values[5], DbsyncValue::Timestamp(2_147_483_647_000_000));
+
+    let mut sink_config = common::create_mysql_sink_config(&url, "temporal_target");
+    sink_config
+        .properties
+        .insert("time_zone".to_string(), json!("-05:00"));
+    let mut sink = MySQLSink::new(sink_config)?;
+    sink.init(&mut ctx).await?;
+    sink.write_batch(batch).await?;
+    sink.close().await?;
+    source.close().await?;
+
+    assert_eq!(
+        column_types(&pool, "temporal_target").await,
+        column_types(&pool, "temporal_source").await
+    );
+    let mut rows = Vec::new();
+    for table in ["temporal_source", "temporal_target"] {
+        let row: (String, String, String, String, String) = sqlx::query_as(&format!(
+            "SELECT CAST(d AS CHAR), CAST(t AS CHAR), CAST(dt AS CHAR), \
+             CAST(ts AS CHAR), CAST(ts0 AS CHAR) FROM {} WHERE id = 1",
+            table
+        ))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        rows.push(row);
+    }
+    assert_eq!(rows[0], rows[1]);
+
+    execute(&pool, "DROP TABLE temporal_source").await;
+    execute(&pool, "DROP TABLE temporal_target").await;
+    Ok(())
+}
+
+// 除主键外每一列都允许 NULL, NULL 读出为 DbsyncValue::Null, 写入为 SQL NULL
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_nullable_columns() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS nullable_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS nullable_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE nullable_source (
+            id BIGINT PRIMARY KEY,
+            i INT NULL,
+            u BIGINT UNSIGNED NULL,
+            b BIT(8) NULL,
+            f DOUBLE NULL,
+            d DECIMAL(10,2) NULL,
+            vc VARCHAR(16) NULL,
+            e ENUM('a','b') NULL,
+            s SET('a','b') NULL,
+            dt DATE NULL,
+            t TIME NULL,
+            dtm DATETIME(6) NULL,
+            ts TIMESTAMP NULL,
+            y YEAR NULL,
+            flag TINYINT(1) NULL,
+            bin VARBINARY(8) NULL,
+            j JSON NULL,
+            g POINT NULL
+        )",
+    )
+    .await;
+    execute(&pool, "INSERT INTO nullable_source (id) VALUES (1)").await;
+
+    let mut ctx = Context::new();
+    let mut source = MySQLSource::new(common::create_mysql_source_config(&url, "nullable_source"))?;
+    source.init(&mut ctx).await?;
+    let batch = source.read_batch(10).await?.expect("one row");
+    assert_eq!(batch.schema.len(), 18);
+    assert!(!batch.schema.fields[0].nullable);
+    assert!(batch.schema.fields[1..].iter().all(|field| field.nullable));
+    let values = &batch.records[0].values;
+    assert_eq!(values[0], DbsyncValue::Integer(1));
+    assert!(values[1..].iter().all(|value| *value == DbsyncValue::Null));
+
+    let mut sink = MySQLSink::new(common::create_mysql_sink_config(&url, "nullable_target"))?;
+    sink.init(&mut ctx).await?;
+    sink.write_batch(batch.clone()).await?;
+
+    // 不允许 NULL 的列写入 NULL 时在绑定前报错
+    let mut values = batch.records[0].values.clone();
+    values[0] = DbsyncValue::Null;
+    let err = sink
+        .write_batch(DataBatch::new(
+            batch.schema.clone(),
+            vec![Record::new(values)],
+        ))
+        .await
+        .unwrap_err();
+    assert!(matches!(&err, Error::Write(msg) if msg.contains("not nullable")));
+    sink.close().await?;
+    source.close().await?;
+
+    let nulls: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM nullable_target WHERE id = 1 AND i IS NULL AND u IS NULL \
+         AND b IS NULL AND f IS NULL AND d IS NULL AND vc IS NULL AND e IS NULL AND s IS NULL \
+         AND dt IS NULL AND t IS NULL AND dtm IS NULL AND ts IS NULL AND y IS NULL \
+         AND flag IS NULL AND bin IS NULL AND j IS NULL AND g IS NULL",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(nulls, 1);
+
+    execute(&pool, "DROP TABLE nullable_source").await;
+    execute(&pool, "DROP TABLE nullable_target").await;
+    Ok(())
+}
+
+async fn sink_with_mode(url: &str, table: &str, mode: &str, id: i64) -> Result<()> {
+    write_rows(url, table, json!({ "save_mode": mode }), &[(id, "row")]).await
+}
+
+async fn target_ids(pool: &MySqlPool, table: &str) -> Vec<i64> {
+    sqlx::query_scalar(&format!("SELECT id FROM {} ORDER BY id", table))
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_sink_save_modes() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    let table = "sink_save_mode";
+
+    execute(&pool, &format!("DROP TABLE IF EXISTS {}", table)).await;
+
+    // 表不存在时 error_if_exists 和 append 都会建表
+    sink_with_mode(&url, table, "error_if_exists", 1).await?;
+    assert_eq!(target_ids(&pool, table).await, vec![1]);
+
+    sink_with_mode(&url, table, "append", 2).await?;
+    assert_eq!(target_ids(&pool, table).await, vec![1, 2]);
+
+    // 目标表上的额外索引用于验证 truncate 保留表结构
+    execute(&pool, &format!("CREATE INDEX idx_name ON {} (name)", table)).await;
+    sink_with_mode(&url, table, "truncate", 3).await?;
+    assert_eq!(target_ids(&pool, table).await, vec![3]);
+    let indexes: i64 = sqlx::query_scalar(
+        "SELECT COUNT(*) FROM INFORMATION_SCHEMA.STATISTICS \
+         WHERE TABLE_SCHEMA = DATABASE() AND TABLE_NAME = ? AND INDEX_NAME = 'idx_name'",
+    )
+    .bind(table)
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(indexes, 1);
+
+    sink_with_mode(&url, table, "ignore", 4).await?;
+    assert_eq!(target_ids(&pool, table).await, vec![3]);
+
+    let err = sink_with_mode(&url, table, "error_if_exists", 5)
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Write(msg) if msg.contains("already exists")),
+        "unexpected error: {}",
+        err
+    );
+
+    sink_with_mode(&url, table, "overwrite", 6).await?;
+    assert_eq!(target_ids(&pool, table).await, vec![6]);
+
+    execute(&pool, &format!("DROP TABLE {}", table)).await;
+    Ok(())
+}
+
+async fn target_rows(pool: &MySqlPool, table: &str) -> Vec<(i64, String)> {
+    sqlx::query_as(&format!("SELECT id, name FROM {} ORDER BY id", table))
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_sink_write_modes() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    let table = "sink_write_mode";
+    let rows = |rows: &[(i64, &str)]| {
+        rows.iter()
+            .map(|(id, name)| (*id, name.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    write_rows(&url, table, json!({}), &[(1, "a"), (2, "b")]).await?;
+
+    // 普通 INSERT 重复主键时报错
+    let err = write_rows(&url, table, json!({ "save_mode": "append" }), &[(2, "x")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Write(_)), "unexpected error: {}", err);
+
+    let mode = |mode: &str| json!({ "save_mode": "append", "write_mode": mode });
+
+    write_rows(&url, table, mode("insert_ignore"), &[(2, "x"), (3, "c")]).await?;
+    assert_eq!(
+        target_rows(&pool, table).await,
+        rows(&[(1, "a"), (2, "b"), (3, "c")])
+    );
+
+    write_rows(&url, table, mode("replace"), &[(3, "y")]).await?;
+    assert_eq!(
+        target_rows(&pool, table).await,
+        rows(&[(1, "a"), (2, "b"), (3, "y")])
+    );
+
+    // 重复执行 upsert 结果不变
+    for _ in 0..2 {
+        write_rows(&url, table, mode("upsert"), &[(1, "z"), (4, "d")]).await?;
+        assert_eq!(
+            target_rows(&pool, table).await,
+            rows(&[(1, "z"), (2, "b"), (3, "y"), (4, "d")])
+        );
+    }
+
+    // 只更新 update_columns 中的列
+    write_rows(
+        &url,
+        table,
+        json!({ "save_mode": "append", "write_mode": "upsert", "update_columns": ["id"] }),
+        &[(2, "ignored")],
+    )
+    .await?;
+    assert_eq!(target_rows(&pool, table).await[1], (2, "b".to_string()));
+
+    // 没有主键或唯一键的表无法 upsert, 在 init 时报错而不是重复插入
+    execute(&pool, "DROP TABLE IF EXISTS write_modes_keyless").await;
+    execute(
+        &pool,
+        "CREATE TABLE write_modes_keyless (id BIGINT, name VARCHAR(255))",
+    )
+    .await;
+    let err = write_rows(&url, "write_modes_keyless", mode("upsert"), &[(1, "a")])
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Config(msg) if msg.contains("no primary or unique key")),
+        "unexpected error: {}",
+        err
+    );
+    execute(&pool, "DROP TABLE write_modes_keyless").await;
+
+    execute(&pool, &format!("DROP TABLE {}", table)).await;
+    Ok(())
+}
+
+async fn run_incremental_job(url: &str, store: &Arc<FileCheckpointStore>) -> Result<()> {
+    let mut source_config = common::create_mysql_source_config(url, "incremental_source");
+    source_config
+        .properties
+        .insert("incremental_column".to_string(), json!("updated_at"));
+    let mut sink_config = common::create_mysql_sink_config(url, "incremental_target");
+    sink_config
+        .properties
+        .insert("save_mode".to_string(), json!("append"));
+
+    SyncJob::new(
+        Box::new(MySQLSource::new(source_config)?),
+        vec![],
+        Box::new(MySQLSink::new(sink_config)?),
+    )
+    .with_config(JobConfig {
+        job_name: "mysql_incremental".to_string(),
+        ..Default::default()
+    })
+    .with_checkpoint_store(store.clone())
+    .run()
+    .await
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_incremental_sync() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    let dir = std::env::temp_dir().join(format!("dbsync-incremental-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let store = Arc::new(FileCheckpointStore::new(&dir));
+
+    execute(&pool, "DROP TABLE IF EXISTS incremental_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS incremental_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE incremental_source (
+            id BIGINT PRIMARY KEY,
+            name VARCHAR(255),
+            updated_at DATETIME NOT NULL
+        )",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO incremental_source VALUES
+            (1, 'a', '2024-01-01 00:00:01'),
+            (2, 'b', '2024-01-01 00:00:02'),
+            (3, 'c', '2024-01-01 00:00:03')",
+    )
+    .await;
+
+    run_incremental_job(&url, &store).await?;
+    assert_eq!(
+        target_rows(&pool, "incremental_target").await,
+        vec![
+            (1, "a".to_string()),
+            (2, "b".to_string()),
+            (3, "c".to_string())
+        ]
+    );
+
+    // 修改已同步的行并新增一行, 第二次运行只读取水位之后的行并覆盖旧版本
+    execute(
+        &pool,
+        "UPDATE incremental_source SET name = 'b2', updated_at = '2024-01-02 00:00:00' WHERE id = 2",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO incremental_source VALUES (4, 'd', '2024-01-02 00:00:01')",
+    )
+    .await;
+    // 直接修改目标表中未变化的行, 用于确认它没有被重新读取
+    execute(
+        &pool,
+        "UPDATE incremental_target SET name = 'untouched' WHERE id = 1",
+    )
+    .await;
+
+    run_incremental_job(&url, &store).await?;
+    assert_eq!(
+        target_rows(&pool, "incremental_target").await,
+        vec![
+            (1, "untouched".to_string()),
+            (2, "b2".to_string()),
+            (3, "c".to_string()),
+            (4, "d".to_string())
+        ]
+    );
+
+    execute(&pool, "DROP TABLE incremental_source").await;
+    execute(&pool, "DROP TABLE incremental_target").await;
+    std::fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
+
+async fn binlog_status(pool: &MySqlPool) -> (String, u64) {
+    let row = match sqlx::query("SHOW MASTER STATUS").fetch_one(pool).await {
+        Ok(row) => row,
+        Err(_) => sqlx::query("SHOW BINARY LOG STATUS")
+            .fetch_one(pool)
+            .await
+            .unwrap(),
+    };
+    (row.get(0), row.get(1))
+}
+
+async fn run_cdc_job(
+    url: &str,
+    start: &(String, u64),
+    store: &Arc<FileCheckpointStore>,
+    resume: bool,
+) -> Result<()> {
+    let source_config = ConnectorConfig {
+        name: "test_mysql_cdc".to_string(),
+        connector_type: "mysql_cdc".to_string(),
+        properties: HashMap::from_iter(vec![
+            ("url".to_string(), json!(url)),
+            ("table".to_string(), json!("cdc_source")),
+            ("stop_at_end".to_string(), json!(true)),
+            ("binlog_file".to_string(), json!(start.0)),
+            ("binlog_position".to_string(), json!(start.1)),
+        ]),
+    };
+    let mut sink_config = common::create_mysql_sink_config(url, "cdc_target");
+    sink_config
+        .properties
+        .insert("save_mode".to_string(), json!("append"));
+
+    SyncJob::new(
+        Box::new(MySQLBinlogSource::new(source_config)?),
+        vec![],
+        Box::new(MySQLSink::new(sink_config)?),
+    )
+    .with_config(JobConfig {
+        job_name: "mysql_cdc".to_string(),
+        batch_size: 2,
+        resume,
+        ..Default::default()
+    })
+    .with_checkpoint_store(store.clone())
+    .run()
+    .await
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_MYSQL_URL"]
+async fn test_mysql_binlog_cdc() -> Result<()> {
+    let url = common::mysql_url();
+    let pool = connect(&url).await;
+    let dir = std::env::temp_dir().join(format!("dbsync-cdc-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let store = Arc::new(FileCheckpointStore::new(&dir));
+
+    execute(&pool, "DROP TABLE IF EXISTS cdc_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS cdc_target").await;
+    let create = "(id BIGINT PRIMARY KEY, name VARCHAR(255))";
+    execute(&pool, &format!("CREATE TABLE cdc_source {}", create)).await;
+    execute(&pool, &format!("CREATE TABLE cdc_target {}", create)).await;
+    let start = binlog_status(&pool).await;
+
+    execute(
+        &pool,
+        "INSERT INTO cdc_source VALUES (1, 'a'), (2, 'b'), (3, 'c')",
+    )
+    .await;
+    execute(&pool, "UPDATE cdc_source SET name = 'b2' WHERE id = 2").await;
+
+    run_cdc_job(&url, &start, &store, false).await?;
+    assert_eq!(
+        target_rows(&pool, "cdc_target").await,
+        vec![
+            (1, "a".to_string()),
+            (2, "b2".to_string()),
+            (3, "c".to_string())
+        ]
+    );
+
+    // 恢复时从 checkpoint 的位置继续, 已应用的变更不会重放
+    execute(
+        &pool,
+        "UPDATE cdc_target SET name = 'untouched' WHERE id = 1",
+    )
+    .await;
+    execute(&pool, "INSERT INTO cdc_source VALUES (4, 'd')").await;
+    // 删除按主键应用, 修改主键的更新按变更前的主键定位目标行
+    execute(&pool, "DELETE FROM cdc_source WHERE id = 2").await;
+    execute(&pool, "UPDATE cdc_source SET id = 5 WHERE id = 3").await;
+
+    run_cdc_job(&url, &start, &store, true).await?;
+    assert_eq!(
+        target_rows(&pool, "cdc_target").await,
+        vec![
+            (1, "untouched".to_string()),
+            (4, "d".to_string()),
+            (5, "c".to_string())
+        ]
+    );
+
+    execute(&pool, "DROP TABLE cdc_source").await;
+    execute(&pool, "DROP TABLE cdc_target").await;
+    std::fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
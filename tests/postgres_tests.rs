@@ -0,0 +1,650 @@
+mod common;
+
+use dbsync_core::checkpoint::{CheckpointStore, FileCheckpointStore};
+use dbsync_core::connector::{
+    ConnectorConfig, Context, DataBatch, KeyValue, Operation, RangeBatch, Record, ShardedSink,
+    ShardedSource, Sink, Source,
+};
+use dbsync_core::error::{Error, Result};
+use dbsync_core::job::JobConfig;
+use dbsync_core::{ColumnSchema, DbsyncType, DbsyncValue, SyncJob, TableSchema};
+use dbsync_postgres::{PostgresReplicationSource, PostgresSink, PostgresSource};
+use serde_json::json;
+use sqlx::postgres::{PgPool, PgPoolOptions};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+async fn connect(url: &str) -> PgPool {
+    PgPoolOptions::new()
+        .acquire_timeout(Duration::from_secs(5))
+        .connect(url)
+        .await
+        .expect("Failed to connect to PostgreSQL")
+}
+
+async fn execute(pool: &PgPool, sql: &str) {
+    sqlx::query(sql).execute(pool).await.unwrap();
+}
+
+async fn column_types(pool: &PgPool, table: &str) -> Vec<String> {
+    sqlx::query_scalar(
+        "SELECT format_type(atttypid, atttypmod) FROM pg_attribute \
+         WHERE attrelid = $1::regclass AND attnum > 0 AND NOT attisdropped ORDER BY attnum",
+    )
+    .bind(table)
+    .fetch_all(pool)
+    .await
+    .unwrap()
+}
+
+// 逐个区间用 keyset 分页读取, 返回按读取顺序排列的主键
+async fn read_all_keys(
+    url: &str,
+    table: &str,
+    batch_size: usize,
+    chunks: usize,
+) -> Result<Vec<KeyValue>> {
+    let mut config = common::create_postgres_source_config(url, table);
+    config
+        .properties
+        .insert("batch_size".to_string(), json!(batch_size));
+    let mut source = PostgresSource::new(config)?;
+    source.init(&mut Context::new()).await?;
+    let key = source.get_primary_key().await?;
+
+    let mut keys = Vec::new();
+    for range in source.split_id_range(chunks).await? {
+        let mut cursor = range.start.clone();
+        while let Some(RangeBatch { batch, last_key }) =
+            source.read_batch_range(cursor.as_ref(), &range.end).await?
+        {
+            assert!(batch.records.len() <= batch_size);
+            for row in 0..batch.records.len() {
+                keys.push(KeyValue(
+                    key.iter()
+                        .map(|c| batch.value(row, c).unwrap().clone())
+                        .collect(),
+                ));
+            }
+            if last_key == range.end {
+                break;
+            }
+            cursor = Some(last_key);
+        }
+    }
+
+    source.close().await?;
+    Ok(keys)
+}
+
+// 不依赖 DbsyncValue 的排序, 用 Debug 输出比较两组主键是否一一对应
+fn sorted_keys(keys: &[KeyValue]) -> Vec<String> {
+    let mut keys = keys.iter().map(|k| format!("{:?}", k)).collect::<Vec<_>>();
+    keys.sort();
+    keys
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_keyset_reads_every_row_once() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_keyset_ids").await;
+    execute(
+        &pool,
+        "CREATE TABLE pg_keyset_ids (id BIGINT PRIMARY KEY, name TEXT)",
+    )
+    .await;
+    // 主键不连续, 含负数
+    execute(
+        &pool,
+        "INSERT INTO pg_keyset_ids SELECT i * 7 - 300, 'row-' || i FROM generate_series(1, 257) i",
+    )
+    .await;
+
+    for (batch_size, chunks) in [(1, 1), (10, 4), (100, 3), (1000, 8)] {
+        let ids = read_all_keys(&url, "pg_keyset_ids", batch_size, chunks)
+            .await?
+            .into_iter()
+            .map(|key| match key.0.as_slice() {
+                [DbsyncValue::Integer(id)] => *id,
+                other => panic!("unexpected id value: {:?}", other),
+            })
+            .collect::<Vec<_>>();
+        let expected = (1..=257).map(|i| i * 7 - 300).collect::<Vec<i64>>();
+        assert_eq!(ids, expected, "batch_size={} chunks={}", batch_size, chunks);
+    }
+
+    execute(&pool, "DROP TABLE pg_keyset_ids").await;
+    Ok(())
+}
+
+// UUID 与文本组成的复合主键按采样切分, 每行恰好读到一次
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_composite_uuid_key() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_keyset_uuid").await;
+    execute(
+        &pool,
+        "CREATE TABLE pg_keyset_uuid (
+            tenant TEXT NOT NULL,
+            id UUID NOT NULL,
+            PRIMARY KEY (tenant, id)
+        )",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO pg_keyset_uuid \
+         SELECT 't' || (i % 3), md5(i::text)::uuid FROM generate_series(1, 100) i",
+    )
+    .await;
+
+    let keys = read_all_keys(&url, "pg_keyset_uuid", 7, 4).await?;
+    assert_eq!(keys.len(), 100);
+    let expected =
+        sqlx::query_as::<_, (String, String)>("SELECT tenant, id::text FROM pg_keyset_uuid")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(tenant, id)| {
+                KeyValue(vec![DbsyncValue::String(tenant), DbsyncValue::String(id)])
+            })
+            .collect::<Vec<_>>();
+    assert_eq!(sorted_keys(&keys), sorted_keys(&expected));
+
+    execute(&pool, "DROP TABLE pg_keyset_uuid").await;
+    Ok(())
+}
+
+// 按目标列的原生类型绑定参数, 读出后再写入新表, 列类型和值都与源表一致
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_all_column_types() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_types_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_types_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE pg_types_source (
+            id BIGINT GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+            si SMALLINT,
+            n NUMERIC(20, 4),
+            r REAL,
+            dp DOUBLE PRECISION,
+            vc VARCHAR(32),
+            c CHAR(4),
+            t TEXT,
+            u UUID,
+            j JSONB,
+            tz TIMESTAMPTZ(3),
+            ts TIMESTAMP(6),
+            d DATE,
+            tm TIME(6),
+            b BYTEA,
+            flag BOOLEAN,
+            ia INTEGER[],
+            ta TEXT[]
+        )",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO pg_types_source VALUES (
+            1, -32768, 1234567890123456.7891, 1.5, 2.25, 'it''s', 'ab', '多字节',
+            'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', '{\"a\": [1, \"it''s\"]}',
+            '2024-02-29 12:34:56.789+08', '2024-02-29 23:59:59.123456', '0001-01-01',
+            '23:59:59.999999', '\\x00ff', true, '{1,NULL,3}', '{\"a,b\",NULL,\"\"}'
+        ), (2, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL,
+            NULL, NULL, NULL, NULL, NULL)",
+    )
+    .await;
+
+    let mut ctx = Context::new();
+    let mut source = PostgresSource::new(common::create_postgres_source_config(
+        &url,
+        "pg_types_source",
+    ))?;
+    source.init(&mut ctx).await?;
+    let batch = source.read_batch(10).await?.expect("two rows");
+    assert_eq!(batch.records.len(), 2);
+    let values = &batch.records[0].values;
+    assert_eq!(values[0], DbsyncValue::Integer(1));
+    assert_eq!(values[1], DbsyncValue::Integer(-32768));
+    assert_eq!(
+        values[8],
+        DbsyncValue::String("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11".to_string())
+    );
+    assert_eq!(values[9], DbsyncValue::Json(json!({"a": [1, "it's"]})));
+    assert_eq!(values[14], DbsyncValue::Binary(vec![0x00, 0xff]));
+    assert_eq!(values[15], DbsyncValue::Boolean(true));
+    assert_eq!(values[16], DbsyncValue::Json(json!([1, null, 3])));
+    assert_eq!(values[17], DbsyncValue::Json(json!(["a,b", null, ""])));
+    assert!(batch.records[1].values[1..]
+        .iter()
+        .all(|v| *v == DbsyncValue::Null));
+
+    let mut sink = PostgresSink::new(common::create_postgres_sink_config(&url, "pg_types_target"))?;
+    sink.init(&mut ctx).await?;
+    sink.write_batch(batch).await?;
+    sink.close().await?;
+    source.close().await?;
+
+    assert_eq!(
+        column_types(&pool, "pg_types_target").await,
+        column_types(&pool, "pg_types_source").await
+    );
+    // 整行按文本比较, 包括 NULL 行
+    let rows = |table: &str| {
+        let sql = format!(
+            "SELECT array_agg(t::text ORDER BY id)::text FROM {} t",
+            table
+        );
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, String>(&sql)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(rows("pg_types_target").await, rows("pg_types_source").await);
+
+    execute(&pool, "DROP TABLE pg_types_source").await;
+    execute(&pool, "DROP TABLE pg_types_target").await;
+    Ok(())
+}
+
+// 目标表按 source 的表结构建表, 标识列, 默认值, 索引和注释都应保留
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_table_schema_round_trip() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_schema_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_schema_target").await;
+    execute(
+        &pool,
+        "CREATE TABLE pg_schema_source (
+            id BIGINT GENERATED BY DEFAULT AS IDENTITY,
+            \"Name\" VARCHAR(64) NOT NULL DEFAULT 'it''s',
+            score INTEGER,
+            created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+            PRIMARY KEY (id)
+        )",
+    )
+    .await;
+    execute(
+        &pool,
+        "CREATE UNIQUE INDEX uk_name ON pg_schema_source (\"Name\")",
+    )
+    .await;
+    execute(
+        &pool,
+        "CREATE INDEX idx_score_created ON pg_schema_source (score, created_at)",
+    )
+    .await;
+    // 部分索引无法按列重建, 不计入
+    execute(
+        &pool,
+        "CREATE INDEX idx_partial ON pg_schema_source (score) WHERE score > 0",
+    )
+    .await;
+    execute(&pool, "COMMENT ON TABLE pg_schema_source IS '测试表'").await;
+    execute(
+        &pool,
+        "COMMENT ON COLUMN pg_schema_source.\"Name\" IS '名称'",
+    )
+    .await;
+
+    let mut ctx = Context::new();
+    let mut source = PostgresSource::new(common::create_postgres_source_config(
+        &url,
+        "pg_schema_source",
+    ))?;
+    source.init(&mut ctx).await?;
+    let source_schema = ctx.schema.clone().expect("source sets the table schema");
+    assert_eq!(source_schema.primary_key, vec!["id".to_string()]);
+    assert_eq!(source_schema.indexes.len(), 2);
+    assert_eq!(source_schema.comment.as_deref(), Some("测试表"));
+    assert!(source_schema.columns[0].auto_increment);
+
+    let mut sink = PostgresSink::new(common::create_postgres_sink_config(
+        &url,
+        "pg_schema_target",
+    ))?;
+    sink.init(&mut ctx).await?;
+    let target_schema = sink.get_target_schema().await?;
+    assert_eq!(target_schema.columns, source_schema.columns);
+    assert_eq!(target_schema.primary_key, source_schema.primary_key);
+    // 目标表上的索引名带有表名前缀
+    let index_columns = |schema: &TableSchema| {
+        let mut indexes = schema
+            .indexes
+            .iter()
+            .map(|i| (i.column_names(), i.unique))
+            .collect::<Vec<_>>();
+        indexes.sort();
+        indexes
+    };
+    assert_eq!(index_columns(&target_schema), index_columns(&source_schema));
+    assert_eq!(target_schema.comment, source_schema.comment);
+
+    sink.close().await?;
+    source.close().await?;
+    execute(&pool, "DROP TABLE pg_schema_source").await;
+    execute(&pool, "DROP TABLE pg_schema_target").await;
+    Ok(())
+}
+
+// 用额外的 sink 配置初始化 sink, 写入一批 (id, name) 行
+async fn write_rows(
+    url: &str,
+    table: &str,
+    properties: serde_json::Value,
+    rows: &[(i64, &str)],
+) -> Result<()> {
+    let mut config = common::create_postgres_sink_config(url, table);
+    if let serde_json::Value::Object(properties) = properties {
+        config.properties.extend(properties);
+    }
+    let mut sink = PostgresSink::new(config)?;
+
+    let mut ctx = Context::new();
+    let table_schema = TableSchema::new("source")
+        .with_column(ColumnSchema::new("id", DbsyncType::BigInt).with_nullable(false))
+        .with_column(ColumnSchema::new("name", DbsyncType::VarChar(255)))
+        .with_primary_key(vec!["id".to_string()]);
+    ctx.set_schema(table_schema.clone());
+    sink.init(&mut ctx).await?;
+
+    let schema = table_schema.batch_schema();
+    let records = rows
+        .iter()
+        .map(|(id, name)| {
+            Record::new(vec![
+                DbsyncValue::Integer(*id),
+                DbsyncValue::String(name.to_string()),
+            ])
+        })
+        .collect();
+    sink.write_batch(DataBatch::new(Arc::new(schema), records))
+        .await?;
+    sink.close().await
+}
+
+async fn target_rows(pool: &PgPool, table: &str) -> Vec<(i64, String)> {
+    sqlx::query_as(&format!("SELECT id, name FROM {} ORDER BY id", table))
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_sink_save_modes() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    let table = "pg_sink_save_mode";
+    let ids = |rows: Vec<(i64, String)>| rows.into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+    let mode = |mode: &str| json!({ "save_mode": mode });
+
+    execute(&pool, &format!("DROP TABLE IF EXISTS {}", table)).await;
+
+    write_rows(&url, table, mode("error_if_exists"), &[(1, "row")]).await?;
+    write_rows(&url, table, mode("append"), &[(2, "row")]).await?;
+    assert_eq!(ids(target_rows(&pool, table).await), vec![1, 2]);
+
+    write_rows(&url, table, mode("truncate"), &[(3, "row")]).await?;
+    assert_eq!(ids(target_rows(&pool, table).await), vec![3]);
+
+    write_rows(&url, table, mode("ignore"), &[(4, "row")]).await?;
+    assert_eq!(ids(target_rows(&pool, table).await), vec![3]);
+
+    let err = write_rows(&url, table, mode("error_if_exists"), &[(5, "row")])
+        .await
+        .unwrap_err();
+    assert!(
+        matches!(&err, Error::Write(msg) if msg.contains("already exists")),
+        "unexpected error: {}",
+        err
+    );
+
+    write_rows(&url, table, mode("overwrite"), &[(6, "row")]).await?;
+    assert_eq!(ids(target_rows(&pool, table).await), vec![6]);
+
+    execute(&pool, &format!("DROP TABLE {}", table)).await;
+    Ok(())
+}
+
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_sink_write_modes() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    let table = "pg_sink_write_mode";
+    let rows = |rows: &[(i64, &str)]| {
+        rows.iter()
+            .map(|(id, name)| (*id, name.to_string()))
+            .collect::<Vec<_>>()
+    };
+
+    execute(&pool, &format!("DROP TABLE IF EXISTS {}", table)).await;
+    write_rows(&url, table, json!({}), &[(1, "a"), (2, "b")]).await?;
+
+    // 普通 INSERT 重复主键时报错
+    let err = write_rows(&url, table, json!({ "save_mode": "append" }), &[(2, "x")])
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Write(_)), "unexpected error: {}", err);
+
+    let mode = |mode: &str| json!({ "save_mode": "append", "write_mode": mode });
+
+    write_rows(&url, table, mode("insert_ignore"), &[(2, "x"), (3, "c")]).await?;
+    assert_eq!(
+        target_rows(&pool, table).await,
+        rows(&[(1, "a"), (2, "b"), (3, "c")])
+    );
+
+    // 重复执行 upsert 结果不变
+    for _ in 0..2 {
+        write_rows(&url, table, mode("upsert"), &[(1, "z"), (4, "d")]).await?;
+        assert_eq!(
+            target_rows(&pool, table).await,
+            rows(&[(1, "z"), (2, "b"), (3, "c"), (4, "d")])
+        );
+    }
+
+    execute(&pool, &format!("DROP TABLE {}", table)).await;
+    Ok(())
+}
+
+fn replication_config(url: &str) -> ConnectorConfig {
+    ConnectorConfig {
+        name: "test_postgres_cdc".to_string(),
+        connector_type: "postgres_cdc".to_string(),
+        properties: HashMap::from_iter(vec![
+            ("url".to_string(), json!(url)),
+            ("table".to_string(), json!("pg_cdc_source")),
+            ("slot_name".to_string(), json!("dbsync_test_slot")),
+            ("publication".to_string(), json!("dbsync_test_publication")),
+            ("stop_at_end".to_string(), json!(true)),
+        ]),
+    }
+}
+
+async fn run_replication_job(url: &str, store: &Arc<FileCheckpointStore>) -> Result<()> {
+    let mut sink_config = common::create_postgres_sink_config(url, "pg_cdc_target");
+    sink_config
+        .properties
+        .insert("save_mode".to_string(), json!("append"));
+
+    SyncJob::new(
+        Box::new(PostgresReplicationSource::new(replication_config(url))?),
+        vec![],
+        Box::new(PostgresSink::new(sink_config)?),
+    )
+    .with_config(JobConfig {
+        job_name: "postgres_cdc".to_string(),
+        batch_size: 2,
+        resume: true,
+        ..Default::default()
+    })
+    .with_checkpoint_store(store.clone())
+    .run()
+    .await
+}
+
+async fn drop_replication(pool: &PgPool) {
+    execute(
+        pool,
+        "SELECT pg_drop_replication_slot(slot_name) FROM pg_replication_slots \
+         WHERE slot_name = 'dbsync_test_slot'",
+    )
+    .await;
+    execute(pool, "DROP PUBLICATION IF EXISTS dbsync_test_publication").await;
+}
+
+// 复制槽只在 sink 写入后才推进, 未写入的变更再次启动时重新读取
+#[tokio::test]
+#[ignore = "requires TEST_POSTGRES_URL"]
+async fn test_postgres_logical_replication() -> Result<()> {
+    let url = common::postgres_url();
+    let pool = connect(&url).await;
+    let wal_level: String = sqlx::query_scalar("SHOW wal_level")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(
+        wal_level, "logical",
+        "logical replication test needs a server started with -c wal_level=logical"
+    );
+
+    let dir = std::env::temp_dir().join(format!("dbsync-pg-cdc-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let store = Arc::new(FileCheckpointStore::new(&dir));
+    drop_replication(&pool).await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_cdc_source").await;
+    execute(&pool, "DROP TABLE IF EXISTS pg_cdc_target").await;
+    let create = "(id BIGINT PRIMARY KEY, name TEXT, tags TEXT[])";
+    execute(&pool, &format!("CREATE TABLE pg_cdc_source {}", create)).await;
+    execute(&pool, &format!("CREATE TABLE pg_cdc_target {}", create)).await;
+
+    // 第一次运行创建发布和复制槽, 此后的变更都会被读取
+    run_replication_job(&url, &store).await?;
+    execute(
+        &pool,
+        "INSERT INTO pg_cdc_source VALUES (1, 'a', '{x}'), (2, 'b', NULL), (3, 'c', '{\"y,z\"}')",
+    )
+    .await;
+    execute(&pool, "UPDATE pg_cdc_source SET name = 'b2' WHERE id = 2").await;
+
+    run_replication_job(&url, &store).await?;
+    let rows = |pool: PgPool| async move {
+        sqlx::query_as::<_, (i64, Option<String>, Option<Vec<String>>)>(
+            "SELECT id, name, tags FROM pg_cdc_target ORDER BY id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+    };
+    assert_eq!(
+        rows(pool.clone()).await,
+        vec![
+            (1, Some("a".into()), Some(vec!["x".into()])),
+            (2, Some("b2".into()), None),
+            (3, Some("c".into()), Some(vec!["y,z".into()])),
+        ]
+    );
+    // 复制槽确认到 checkpoint 的位置
+    let checkpoint = store.load("postgres_cdc").await?.expect("checkpoint saved");
+    let confirmed: String = sqlx::query_scalar(
+        "SELECT confirmed_flush_lsn::text FROM pg_replication_slots \
+         WHERE slot_name = 'dbsync_test_slot'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(checkpoint.position, Some(json!({ "lsn": confirmed })));
+
+    execute(
+        &pool,
+        "UPDATE pg_cdc_target SET name = 'untouched' WHERE id = 1",
+    )
+    .await;
+    execute(&pool, "INSERT INTO pg_cdc_source VALUES (4, 'd', '{}')").await;
+    // 删除按主键应用, 修改主键的更新按变更前的主键定位目标行
+    execute(&pool, "DELETE FROM pg_cdc_source WHERE id = 2").await;
+    execute(&pool, "UPDATE pg_cdc_source SET id = 5 WHERE id = 3").await;
+
+    // 只读取不确认, 两次读到同样的变更
+    for _ in 0..2 {
+        let mut source = PostgresReplicationSource::new(replication_config(&url))?;
+        source.init(&mut Context::new()).await?;
+        let mut ops = Vec::new();
+        while let Some(batch) = source.read_batch(10).await? {
+            ops.extend(batch.records.iter().map(|record| record.op));
+        }
+        source.close().await?;
+        assert_eq!(
+            ops,
+            vec![Operation::Insert, Operation::Delete, Operation::Update]
+        );
+    }
+
+    run_replication_job(&url, &store).await?;
+    assert_eq!(
+        rows(pool.clone()).await,
+        vec![
+            (1, Some("untouched".into()), Some(vec!["x".into()])),
+            (4, Some("d".into()), Some(vec![])),
+            (5, Some("c".into()), Some(vec!["y,z".into()])),
+        ]
+    );
+
+    // 默认的 REPLICA IDENTITY 下, 未修改的 TOAST 列不在变更中, 更新时保留目标的值.
+    // 同一事务中的两行更新缺少的列不同, 分批输出
+    execute(
+        &pool,
+        "ALTER TABLE pg_cdc_source ALTER COLUMN name SET STORAGE EXTERNAL",
+    )
+    .await;
+    execute(
+        &pool,
+        "INSERT INTO pg_cdc_source SELECT 6, string_agg(md5(i::text), ''), NULL \
+         FROM generate_series(1, 100) i",
+    )
+    .await;
+    run_replication_job(&url, &store).await?;
+    execute(
+        &pool,
+        "UPDATE pg_cdc_source SET tags = '{t}' WHERE id IN (4, 6)",
+    )
+    .await;
+    execute(&pool, "UPDATE pg_cdc_source SET tags = '{u}' WHERE id = 6").await;
+    run_replication_job(&url, &store).await?;
+    let large: String = sqlx::query_scalar("SELECT name FROM pg_cdc_source WHERE id = 6")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert!(large.len() > 2048);
+    assert_eq!(
+        rows(pool.clone()).await[1..],
+        vec![
+            (4, Some("d".into()), Some(vec!["t".into()])),
+            (5, Some("c".into()), Some(vec!["y,z".into()])),
+            (6, Some(large), Some(vec!["u".into()])),
+        ]
+    );
+
+    drop_replication(&pool).await;
+    execute(&pool, "DROP TABLE pg_cdc_source").await;
+    execute(&pool, "DROP TABLE pg_cdc_target").await;
+    std::fs::remove_dir_all(dir).unwrap();
+    Ok(())
+}
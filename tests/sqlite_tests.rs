@@ -0,0 +1,401 @@
+mod common;
+
+use dbsync_core::checkpoint::CheckpointStore;
+use dbsync_core::connector::{
+    Context, DataBatch, Operation, RangeBatch, Record, ShardedSource, Sink, Source,
+};
+use dbsync_core::error::{Error, Result};
+use dbsync_core::job::JobConfig;
+use dbsync_core::{ColumnSchema, DbsyncType, DbsyncValue, SyncJob, TableSchema};
+use dbsync_sqlite::{SqliteSink, SqliteSource};
+use serde_json::json;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
+use std::str::FromStr;
+use std::sync::Arc;
+
+async fn connect(url: &str) -> SqlitePool {
+    let options = SqliteConnectOptions::from_str(url)
+        .unwrap()
+        .create_if_missing(true);
+    SqlitePool::connect_with(options)
+        .await
+        .expect("Failed to open SQLite database")
+}
+
+async fn execute(pool: &SqlitePool, sql: &str) {
+    sqlx::query(sql).execute(pool).await.unwrap();
+}
+
+async fn column_types(pool: &SqlitePool, table: &str) -> Vec<(String, String)> {
+    sqlx::query_as("SELECT name, type FROM pragma_table_info(?) ORDER BY cid")
+        .bind(table)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+// 用 source 顺序读出整张表
+async fn read_table(url: &str, table: &str) -> Result<Vec<Vec<DbsyncValue>>> {
+    let mut source = SqliteSource::new(common::create_sqlite_source_config(url, table))?;
+    source.init(&mut Context::new()).await?;
+    let mut rows = Vec::new();
+    while let Some(batch) = source.read_batch(7).await? {
+        rows.extend(batch.records.into_iter().map(|record| record.values));
+    }
+    source.close().await?;
+    Ok(rows)
+}
+
+// 源表和目标表在同一个数据库文件中, 整个任务不需要任何服务器
+#[tokio::test]
+async fn test_sqlite_sync_job() -> Result<()> {
+    let url = common::sqlite_test_url("sync_job");
+    let pool = connect(&url).await;
+    execute(
+        &pool,
+        "CREATE TABLE source (
+            id INTEGER PRIMARY KEY,
+            name VARCHAR(20) NOT NULL DEFAULT 'unknown',
+            price DECIMAL_TEXT(20,4),
+            ratio DOUBLE,
+            active BOOLEAN,
+            born DATE,
+            alarm TIME(3),
+            updated_at DATETIME(6),
+            payload BLOB,
+            doc JSON,
+            untyped
+        )",
+    )
+    .await;
+    execute(&pool, "CREATE INDEX idx_source_name ON source (name)").await;
+    execute(
+        &pool,
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 500)
+         INSERT INTO source
+         SELECT i * 3, 'row-' || i, '1234567890123456.' || printf('%04d', i), i / 8.0, i % 2,
+                date('2024-01-01', '+' || i || ' days'), printf('%02d:30:00.125', i % 24),
+                '2024-01-02 03:04:05.' || printf('%06d', i), randomblob(4),
+                json_object('i', i, 'tags', json_array('a', i)),
+                CASE WHEN i % 3 = 0 THEN NULL ELSE i END
+         FROM n",
+    )
+    .await;
+    // rowid 不连续
+    execute(&pool, "DELETE FROM source WHERE id % 7 = 0").await;
+
+    let mut sink_config = common::create_sqlite_sink_config(&url, "target");
+    sink_config
+        .properties
+        .insert("create_if_missing".to_string(), json!(false));
+    SyncJob::new(
+        Box::new(SqliteSource::new(common::create_sqlite_source_config(
+            &url, "source",
+        ))?),
+        vec![],
+        Box::new(SqliteSink::new(sink_config)?),
+    )
+    .with_config(JobConfig {
+        job_name: "sqlite_sync".to_string(),
+        batch_size: 50,
+        reader_count: 3,
+        range_count: 6,
+        writer_count: 3,
+        ..Default::default()
+    })
+    .run()
+    .await?;
+
+    let source_rows = read_table(&url, "source").await?;
+    let mut target_rows = read_table(&url, "target").await?;
+    // 并行写入, 目标表的 rowid 顺序与源表不同
+    target_rows.sort_by_key(|row| match row[0] {
+        DbsyncValue::Integer(id) => id,
+        _ => panic!("unexpected id: {:?}", row[0]),
+    });
+    assert_eq!(source_rows.len(), 500 - 71);
+    assert_eq!(target_rows, source_rows);
+    assert_eq!(
+        source_rows[0][2],
+        DbsyncValue::Decimal("1234567890123456.0001".into())
+    );
+
+    let types = column_types(&pool, "target").await;
+    let types = types.iter().map(|(_, t)| t.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        types,
+        vec![
+            "BIGINT",
+            "VARCHAR(20)",
+            "DECIMAL_TEXT(20,4)",
+            "DOUBLE",
+            "BOOLEAN",
+            "DATE",
+            "TIME(3)",
+            "DATETIME(6)",
+            "BLOB",
+            "JSON",
+            "TEXT",
+        ]
+    );
+    let indexes: Vec<String> =
+        sqlx::query_scalar("SELECT name FROM pragma_index_list('target') WHERE origin = 'c'")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(indexes, vec!["target_idx_source_name"]);
+
+    pool.close().await;
+    Ok(())
+}
+
+// 中断时写了一半的区间在恢复时从头重新写入, 默认的 insert 写入模式不能因主键重复失败
+#[tokio::test]
+async fn test_sqlite_resume_partially_written_range() -> Result<()> {
+    let url = common::sqlite_test_url("resume");
+    common::create_resume_source(&url).await;
+    let pool = connect(&url).await;
+    let store = common::resume_checkpoint_store("sqlite_resume");
+    let sink = || SqliteSink::new(common::create_sqlite_sink_config(&url, "target"));
+
+    let failing = common::FailingTransform::new(4);
+    let err = common::run_resume_job(
+        &url,
+        Box::new(sink()?),
+        &store,
+        vec![Box::new(failing)],
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(err, Error::Transform(_)),
+        "unexpected error: {}",
+        err
+    );
+    let checkpoint = store.load("resume").await?.unwrap();
+    assert_eq!(checkpoint.pending_ranges().len(), 1);
+    let written: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM target")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(written, 300);
+
+    common::run_resume_job(&url, Box::new(sink()?), &store, vec![], true).await?;
+
+    let source_rows = read_table(&url, "source").await?;
+    let mut target_rows = read_table(&url, "target").await?;
+    target_rows.sort_by_key(|row| match row[0] {
+        DbsyncValue::Integer(id) => id,
+        _ => panic!("unexpected id: {:?}", row[0]),
+    });
+    assert_eq!(target_rows.len(), 400);
+    assert_eq!(target_rows, source_rows);
+
+    pool.close().await;
+    Ok(())
+}
+
+// 没有主键的表按 rowid 切分, 每行恰好读到一次; WITHOUT ROWID 表无法读取
+#[tokio::test]
+async fn test_sqlite_rowid_ranges() -> Result<()> {
+    let url = common::sqlite_test_url("rowid_ranges");
+    let pool = connect(&url).await;
+    execute(&pool, "CREATE TABLE events (name TEXT, amount REAL)").await;
+    execute(
+        &pool,
+        "WITH RECURSIVE n(i) AS (SELECT 1 UNION ALL SELECT i + 1 FROM n WHERE i < 257)
+         INSERT INTO events SELECT 'event-' || i, i * 1.5 FROM n",
+    )
+    .await;
+    execute(&pool, "DELETE FROM events WHERE rowid % 5 = 0").await;
+
+    let mut source = SqliteSource::new(common::create_sqlite_source_config(&url, "events"))?;
+    source.init(&mut Context::new()).await?;
+    assert_eq!(source.get_primary_key().await?, vec!["rowid"]);
+    assert_eq!(source.get_total_records().await?, 206);
+
+    for chunks in [1, 4, 100, 1000] {
+        let mut names = Vec::new();
+        for range in source.split_id_range(chunks).await? {
+            let mut cursor = range.start.clone();
+            while let Some(RangeBatch { batch, last_key }) =
+                source.read_batch_range(cursor.as_ref(), &range.end).await?
+            {
+                assert!(batch.records.len() <= 100);
+                assert_eq!(batch.schema.names().collect::<Vec<_>>(), ["name", "amount"]);
+                names.extend(batch.records.into_iter().map(|r| r.values[0].clone()));
+                if last_key == range.end {
+                    break;
+                }
+                cursor = Some(last_key);
+            }
+        }
+        let expected = (1..=257)
+            .filter(|i| i % 5 != 0)
+            .map(|i| DbsyncValue::String(format!("event-{}", i)))
+            .collect::<Vec<_>>();
+        assert_eq!(names, expected, "chunks={}", chunks);
+    }
+    source.close().await?;
+
+    execute(
+        &pool,
+        "CREATE TABLE keyed (id TEXT PRIMARY KEY, value TEXT) WITHOUT ROWID",
+    )
+    .await;
+    let mut source = SqliteSource::new(common::create_sqlite_source_config(&url, "keyed"))?;
+    let err = source.init(&mut Context::new()).await.unwrap_err();
+    assert!(
+        matches!(&err, Error::Config(msg) if msg.contains("WITHOUT ROWID")),
+        "unexpected error: {}",
+        err
+    );
+
+    pool.close().await;
+    Ok(())
+}
+
+async fn write_records(
+    url: &str,
+    table: &str,
+    properties: serde_json::Value,
+    records: Vec<Record>,
+) -> Result<()> {
+    let mut config = common::create_sqlite_sink_config(url, table);
+    if let serde_json::Value::Object(properties) = properties {
+        config.properties.extend(properties);
+    }
+    let mut sink = SqliteSink::new(config)?;
+
+    let mut ctx = Context::new();
+    let table_schema = TableSchema::new("source")
+        .with_column(ColumnSchema::new("id", DbsyncType::BigInt).with_nullable(false))
+        .with_column(ColumnSchema::new("name", DbsyncType::VarChar(255)))
+        .with_primary_key(vec!["id".to_string()]);
+    ctx.set_schema(table_schema.clone());
+    sink.init(&mut ctx).await?;
+
+    let schema = table_schema.batch_schema();
+    sink.write_batch(DataBatch::new(Arc::new(schema), records))
+        .await?;
+    sink.close().await
+}
+
+fn row(id: i64, name: &str) -> Record {
+    Record::new(vec![
+        DbsyncValue::Integer(id),
+        DbsyncValue::String(name.to_string()),
+    ])
+}
+
+fn rows(rows: &[(i64, &str)]) -> Vec<Record> {
+    rows.iter().map(|(id, name)| row(*id, name)).collect()
+}
+
+async fn target_rows(pool: &SqlitePool, table: &str) -> Vec<(i64, String)> {
+    sqlx::query_as(&format!("SELECT id, name FROM {} ORDER BY id", table))
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_sqlite_sink_save_and_write_modes() -> Result<()> {
+    let url = common::sqlite_test_url("sink_modes");
+    let table = "sink_modes";
+    let expect = |rows: &[(i64, &str)]| {
+        rows.iter()
+            .map(|(id, name)| (*id, name.to_string()))
+            .collect::<Vec<_>>()
+    };
+    let mode = |save_mode: &str, write_mode: &str| json!({ "save_mode": save_mode, "write_mode": write_mode });
+
+    write_records(&url, table, json!({}), rows(&[(1, "a"), (2, "b")])).await?;
+    let pool = connect(&url).await;
+
+    // 普通 INSERT 重复主键时报错, 整批回滚
+    let err = write_records(
+        &url,
+        table,
+        mode("append", "insert"),
+        rows(&[(3, "c"), (2, "x")]),
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(err, Error::Write(_)), "unexpected error: {}", err);
+    assert_eq!(
+        target_rows(&pool, table).await,
+        expect(&[(1, "a"), (2, "b")])
+    );
+
+    write_records(
+        &url,
+        table,
+        mode("append", "insert_ignore"),
+        rows(&[(2, "x"), (3, "c")]),
+    )
+    .await?;
+    assert_eq!(
+        target_rows(&pool, table).await,
+        expect(&[(1, "a"), (2, "b"), (3, "c")])
+    );
+    write_records(&url, table, mode("append", "replace"), rows(&[(3, "r")])).await?;
+    for _ in 0..2 {
+        write_records(
+            &url,
+            table,
+            mode("append", "upsert"),
+            rows(&[(1, "z"), (4, "d")]),
+        )
+        .await?;
+        assert_eq!(
+            target_rows(&pool, table).await,
+            expect(&[(1, "z"), (2, "b"), (3, "r"), (4, "d")])
+        );
+    }
+
+    // 变更记录按顺序应用, 更新按变更前的主键定位
+    write_records(
+        &url,
+        table,
+        mode("append", "insert"),
+        vec![
+            row(5, "e"),
+            row(20, "b2").with_op(Operation::Update).with_before(vec![
+                DbsyncValue::Integer(2),
+                DbsyncValue::String("b".into()),
+            ]),
+            row(1, "z").with_op(Operation::Delete),
+            row(4, "d").with_op(Operation::Delete),
+        ],
+    )
+    .await?;
+    assert_eq!(
+        target_rows(&pool, table).await,
+        expect(&[(3, "r"), (5, "e"), (20, "b2")])
+    );
+
+    write_records(&url, table, mode("truncate", "insert"), rows(&[(6, "f")])).await?;
+    assert_eq!(target_rows(&pool, table).await, expect(&[(6, "f")]));
+    write_records(&url, table, mode("ignore", "insert"), rows(&[(7, "g")])).await?;
+    assert_eq!(target_rows(&pool, table).await, expect(&[(6, "f")]));
+    let err = write_records(
+        &url,
+        table,
+        mode("error_if_exists", "insert"),
+        rows(&[(8, "h")]),
+    )
+    .await
+    .unwrap_err();
+    assert!(
+        matches!(&err, Error::Write(msg) if msg.contains("already exists")),
+        "unexpected error: {}",
+        err
+    );
+    write_records(&url, table, mode("overwrite", "insert"), rows(&[(9, "i")])).await?;
+    assert_eq!(target_rows(&pool, table).await, expect(&[(9, "i")]));
+
+    pool.close().await;
+    Ok(())
+}
@@ -1,22 +1,24 @@
 use async_trait::async_trait;
 use dbsync_core::connector::DataBatch;
 use dbsync_core::{
-    connector::{Record, Transform},
+    connector::{BatchSchema, Transform},
     error::{Error, Result},
 };
 use lazy_static::lazy_static;
 use std::any::Any;
-use std::collections::HashMap;
-use std::sync::Mutex;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+type TransformFactory = Box<dyn Fn(BatchSchema) -> Result<FieldRenameTransform> + Send + Sync>;
 
 lazy_static! {
-    static ref TRANSFORM_FACTORIES: Mutex<HashMap<String, Box<dyn Fn(Record) -> Result<FieldRenameTransform> + Send + Sync>>> =
+    static ref TRANSFORM_FACTORIES: Mutex<HashMap<String, TransformFactory>> =
         Mutex::new(HashMap::new());
 }
 
 fn register_transform<F>(name: &str, factory: F)
 where
-    F: Fn(Record) -> Result<FieldRenameTransform> + Send + Sync + 'static,
+    F: Fn(BatchSchema) -> Result<FieldRenameTransform> + Send + Sync + 'static,
 {
     TRANSFORM_FACTORIES
         .lock()
@@ -25,8 +27,8 @@ where
 }
 
 pub fn get_transform_factory(
-    transform_type: &str,
-) -> Option<Box<dyn Fn(Record) -> Result<FieldRenameTransform>>> {
+    _transform_type: &str,
+) -> Option<Box<dyn Fn(BatchSchema) -> Result<FieldRenameTransform>>> {
     // TODO: 实现转换器工厂
     None
 }
@@ -35,11 +37,11 @@ pub fn register_transforms() {
     register_transform("field_rename", create_field_rename_transform);
 }
 
-fn create_field_rename_transform(record: Record) -> Result<FieldRenameTransform> {
-    let mappings = record
+fn create_field_rename_transform(schema: BatchSchema) -> Result<FieldRenameTransform> {
+    let mappings = schema
         .fields
         .into_iter()
-        .map(|(name, _)| (name.clone(), name))
+        .map(|field| (field.name.clone(), field.name))
         .collect::<HashMap<String, String>>();
 
     Ok(FieldRenameTransform { mappings })
@@ -52,21 +54,24 @@ pub struct FieldRenameTransform {
 
 #[async_trait]
 impl Transform for FieldRenameTransform {
-    async fn transform(&mut self, batch: DataBatch) -> Result<DataBatch> {
-        let records = batch
-            .records
-            .into_iter()
-            .map(|mut record| {
-                for (old_name, new_name) in &self.mappings {
-                    if let Some(value) = record.fields.remove(old_name) {
-                        record.fields.insert(new_name.clone(), value);
-                    }
-                }
-                record
-            })
-            .collect();
-
-        Ok(DataBatch { records })
+    // 只需修改批次共享的 schema, 记录本身不变. 所有列同时改名, 因此可以互换列名;
+    // schema 被其他批次共享时先复制一份, 不影响其他批次
+    async fn transform(&mut self, mut batch: DataBatch) -> Result<DataBatch> {
+        let schema = Arc::make_mut(&mut batch.schema);
+        for field in &mut schema.fields {
+            if let Some(new_name) = self.mappings.get(&field.name) {
+                field.name = new_name.clone();
+            }
+        }
+
+        let mut names = HashSet::new();
+        if let Some(name) = schema.names().find(|name| !names.insert(*name)) {
+            return Err(Error::Transform(format!(
+                "Renaming fields produces duplicate field {}",
+                name
+            )));
+        }
+        Ok(batch)
     }
 
     fn as_any(&self) -> &dyn Any {
@@ -77,3 +82,80 @@ impl Transform for FieldRenameTransform {
         Box::new(self.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dbsync_core::connector::{BatchField, Record};
+    use dbsync_core::types::{DbsyncType, DbsyncValue};
+
+    fn rename(mappings: &[(&str, &str)]) -> FieldRenameTransform {
+        FieldRenameTransform {
+            mappings: mappings
+                .iter()
+                .map(|(from, to)| (from.to_string(), to.to_string()))
+                .collect(),
+        }
+    }
+
+    fn batch(names: &[&str]) -> DataBatch {
+        let schema = BatchSchema::new(
+            names
+                .iter()
+                .map(|name| BatchField::new(*name, DbsyncType::BigInt))
+                .collect(),
+        );
+        let values = (0..names.len() as i64).map(DbsyncValue::Integer).collect();
+        DataBatch::new(Arc::new(schema), vec![Record::new(values)])
+    }
+
+    fn names(batch: &DataBatch) -> Vec<&str> {
+        batch.schema.names().collect()
+    }
+
+    #[tokio::test]
+    async fn test_rename_field() {
+        let batch = rename(&[("a", "x")])
+            .transform(batch(&["a", "b"]))
+            .await
+            .unwrap();
+        assert_eq!(names(&batch), ["x", "b"]);
+        assert_eq!(batch.value(0, "x"), Some(&DbsyncValue::Integer(0)));
+        assert_eq!(batch.value(0, "a"), None);
+    }
+
+    #[tokio::test]
+    async fn test_swap_fields() {
+        let batch = rename(&[("a", "b"), ("b", "a")])
+            .transform(batch(&["a", "b"]))
+            .await
+            .unwrap();
+        assert_eq!(names(&batch), ["b", "a"]);
+        assert_eq!(batch.value(0, "b"), Some(&DbsyncValue::Integer(0)));
+        assert_eq!(batch.value(0, "a"), Some(&DbsyncValue::Integer(1)));
+    }
+
+    #[tokio::test]
+    async fn test_rename_onto_existing_field() {
+        let err = rename(&[("a", "b")])
+            .transform(batch(&["a", "b"]))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, Error::Transform(msg) if msg.contains("duplicate field b")),
+            "unexpected error: {}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_shared_schema_is_not_modified() {
+        let original = batch(&["a", "b"]);
+        let other = DataBatch::new(original.schema.clone(), original.records.clone());
+
+        let renamed = rename(&[("a", "x")]).transform(original).await.unwrap();
+        assert_eq!(names(&renamed), ["x", "b"]);
+        assert_eq!(names(&other), ["a", "b"]);
+        assert!(!Arc::ptr_eq(&renamed.schema, &other.schema));
+    }
+}
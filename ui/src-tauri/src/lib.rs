@@ -49,6 +49,9 @@ async fn create_sync_job(config: Config) -> Result<(), String> {
     plugin_manager.register_source("mysql", |config| {
         Ok(Box::new(dbsync_mysql::MySQLSource::new(config)?))
     });
+    plugin_manager.register_source("mysql_cdc", |config| {
+        Ok(Box::new(dbsync_mysql::MySQLBinlogSource::new(config)?))
+    });
     plugin_manager.register_sink("mysql", |config| {
         Ok(Box::new(dbsync_mysql::MySQLSink::new(config)?))
     });
@@ -75,6 +78,11 @@ async fn get_available_connectors() -> Result<Vec<ConnectorInfo>, String> {
             connector_type: "source".into(),
             schema: include_str!("../../../connectors/mysql/schema/source.json").into(),
         },
+        ConnectorInfo {
+            name: "mysql_cdc".into(),
+            connector_type: "source".into(),
+            schema: include_str!("../../../connectors/mysql/schema/binlog.json").into(),
+        },
         ConnectorInfo {
             name: "mysql".into(),
             connector_type: "sink".into(),